@@ -0,0 +1,108 @@
+use std::time::Duration;
+use engine::{
+    chrono::{
+        DateTime,
+        Utc,
+    },
+    storage::change_stream,
+};
+pub use engine::storage::change_stream::{
+    ChangeOperation,
+    ChangeStreamOptions,
+};
+use crate::{
+    client::error::{
+        DatabaseClientError,
+        DatabaseClientErrorKind,
+    },
+    collection::{
+        transform_document_dto_to_document,
+        transform_id_to_document_id,
+    },
+    document::{
+        DocumentId,
+        DocumentModel,
+    },
+};
+
+/// Change to a document in a watched database.
+#[derive(Debug, Clone)]
+pub struct ChangeEvent {
+    /// Sequence number of the event. Use this to resume watching.
+    pub sequence: u64,
+
+    /// Time when the change was written.
+    pub timestamp: DateTime<Utc>,
+
+    /// Name of the collection that has the document.
+    pub collection: String,
+
+    /// Id of the changed document.
+    pub document_id: DocumentId,
+
+    /// Operation that changed the document.
+    pub operation: ChangeOperation,
+
+    /// Document before the change if the change stream includes documents.
+    pub before: Option<DocumentModel>,
+
+    /// Document after the change if the change stream includes documents.
+    pub after: Option<DocumentModel>,
+}
+
+/// Stream of document changes in a database or collection.
+/// 
+/// Stops watching when it is dropped.
+pub struct ChangeStream {
+    inner: change_stream::ChangeStream,
+}
+
+impl ChangeStream {
+    pub(crate) fn new(inner: change_stream::ChangeStream) -> ChangeStream {
+        ChangeStream { inner }
+    }
+
+    /// Waits for the next change event.
+    /// 
+    /// Returns an error if a document in the event cannot be decoded.
+    pub fn recv(&self) -> Option<Result<ChangeEvent, DatabaseClientError>> {
+        self.inner.recv().map(transform_change_event)
+    }
+
+    /// Waits for the next change event until the timeout.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Result<ChangeEvent, DatabaseClientError>> {
+        self.inner.recv_timeout(timeout).map(transform_change_event)
+    }
+
+    /// Gets the next change event without waiting.
+    pub fn try_recv(&self) -> Option<Result<ChangeEvent, DatabaseClientError>> {
+        self.inner.try_recv().map(transform_change_event)
+    }
+
+    /// Gets all received change events without waiting.
+    pub fn try_iter(&self) -> impl Iterator<Item = Result<ChangeEvent, DatabaseClientError>> + '_ {
+        self.inner.try_iter().map(transform_change_event)
+    }
+}
+
+/// Transforms engine change event to driver change event.
+fn transform_change_event(event: change_stream::ChangeEvent) -> Result<ChangeEvent, DatabaseClientError> {
+    let before = event.before.map(transform_document_dto_to_document).transpose();
+    let after = event.after.map(transform_document_dto_to_document).transpose();
+    let (before, after) = match (before, after) {
+        (Ok(before), Ok(after)) => (before, after),
+        (Err(e), _) | (_, Err(e)) => return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::Watch,
+            e)),
+    };
+
+    Ok(ChangeEvent {
+        sequence: event.sequence,
+        timestamp: event.timestamp,
+        collection: event.collection,
+        document_id: transform_id_to_document_id(event.document_id),
+        operation: event.operation,
+        before,
+        after,
+    })
+}
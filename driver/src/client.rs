@@ -13,6 +13,7 @@ use engine::{
     config::Config,
     storage::{
         error::{
+            DatabaseError,
             DatabaseOperationError,
             DatabaseOperationErrorKind,
         },
@@ -20,16 +21,34 @@ use engine::{
     },
 };
 use crate::{
-    database::Database,
-    collection::Collection
+    database::{
+        Database,
+        DatabaseOptions,
+        EncryptionOptions,
+    },
+    collection::Collection,
+    hook::WriteHooks,
 };
 use self::error::{
     DatabaseClientError,
     DatabaseClientErrorKind,
     UNEXPECTED_ERROR,
+    DATA_NOT_RECEIVED,
 };
 use tempfile::tempdir;
 
+pub use engine::storage::{
+    integrity::{
+        IntegrityProblem,
+        IntegrityReportDto,
+        RepairReportDto,
+    },
+    backup::{
+        BackupEntry,
+        BackupManifest,
+    },
+};
+
 /// Connect to databases using this. Multiple databases can be connected
 /// using the same database client.
 /// 
@@ -38,6 +57,18 @@ use tempfile::tempdir;
 pub struct DatabaseClient {
     pub engine: DriverEngine,
     pub db_dir: PathBuf,
+    pub options: ClientOptions,
+    hooks: WriteHooks,
+}
+
+/// Options for building a database client.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientOptions {
+    /// Encryption of databases.
+    /// 
+    /// New databases are encrypted with this and
+    /// encrypted databases are unlocked with its key.
+    pub encryption: Option<EncryptionOptions>,
 }
 
 impl DatabaseClient {
@@ -45,21 +76,63 @@ impl DatabaseClient {
     /// 
     /// Database directory is where databases will be created to and accessed from. 
     pub fn build(db_dir: &Path) -> DatabaseClient {
+        Self::build_with_options(db_dir, &ClientOptions::default())
+    }
+
+    /// Builds a new database client using the database directory and options.
+    pub fn build_with_options(db_dir: &Path, options: &ClientOptions) -> DatabaseClient {
         let mut config = Config::default();
         config.db_dir_path = PathBuf::from(db_dir);
 
         DatabaseClient {
             engine: DriverEngine::build_logger_disabled(&config),
             db_dir: PathBuf::from(db_dir),
+            options: options.clone(),
+            hooks: WriteHooks::new(),
         }
     }
 
+    /// Sets write hooks that run for documents in all collections
+    /// except the reserved migrations collection.
+    /// 
+    /// Client hooks run before the hooks of a collection.
+    pub fn with_hooks(mut self, hooks: WriteHooks) -> DatabaseClient {
+        self.hooks = hooks;
+
+        return self;
+    }
+
+    /// Gets the write hooks of this client.
+    pub fn hooks(&self) -> &WriteHooks {
+        &self.hooks
+    }
+
     /// Gets a database using the database name.
     /// 
     /// Creates the database if it doesn't exist.
     /// Databases will be created to the database directory.
+    /// New databases are encrypted if the client has an encryption.
     pub fn get_database(&self, name: &str) -> Result<Database, DatabaseClientError> {
-        let file_path = self.db_dir.join(&format!("{}.{}", name, DB_FILE_EXTENSION));
+        let options = DatabaseOptions {
+            encryption: self.options.encryption.clone(),
+            ..Default::default()
+        };
+        self.get_database_with_options(name, &options)
+    }
+
+    /// Gets a database using the database name.
+    /// 
+    /// Creates the database with the given options if it doesn't exist.
+    /// Options of existing databases are not changed.
+    /// 
+    /// Encrypted databases are unlocked with the key in the options
+    /// or with the client's key. Expired documents are removed when the database is opened.
+    pub fn get_database_with_options(
+        &self,
+        name: &str,
+        options: &DatabaseOptions,
+    ) -> Result<Database<'_>, DatabaseClientError> {
+        let file_path = self.db_file_path(name);
         
         let result = self.engine
             .storage_api()
@@ -73,13 +146,29 @@ impl DatabaseClient {
 
         if result.success {
             if let Some(db) = result.data {
-                if let None = db {
-                    if let Err(e) = self.create_database(name) {
-                        return Err(DatabaseClientError::new(
-                            DatabaseClientErrorKind::GetDatabase,
-                            format!("Cannot create database: {}", e.message)));
-                    }
+                match db {
+                    Some(db) if db.locked() => {
+                        let encryption = options.encryption
+                            .as_ref()
+                            .or(self.options.encryption.as_ref());
+                        if let Err(e) = self.unlock_database(&file_path, encryption) {
+                            return Err(DatabaseClientError::new(
+                                DatabaseClientErrorKind::GetDatabase,
+                                format!("Cannot unlock database: {}", e.message)));
+                        }
+                    },
+                    Some(_) => {},
+                    None => {
+                        if let Err(e) = self.create_database(name, options) {
+                            return Err(DatabaseClientError::new(
+                                DatabaseClientErrorKind::GetDatabase,
+                                format!("Cannot create database: {}", e.message)));
+                        }
+                    },
                 }
+                self.engine
+                    .storage_api()
+                    .sweep_expired_documents(&file_path);
 
                 return Ok(Database::new(&self, &file_path));
             }
@@ -89,14 +178,104 @@ impl DatabaseClient {
             DatabaseClientErrorKind::GetDatabase,
             UNEXPECTED_ERROR.to_string()));
     }
+
+    /// Renames a database in the database directory.
+    /// 
+    /// The database file is renamed too.
+    pub fn rename_database(&self, name: &str, new_name: &str) -> Result<(), DatabaseClientError> {
+        let result = self.engine
+            .storage_api()
+            .rename_database(&self.db_file_path(name), new_name);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::ModifyDatabase,
+                e.message));
+        }
+
+        if result.success {
+            return Ok(());
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::ModifyDatabase,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Clones a database in the database directory to a new database.
+    /// 
+    /// Returns the cloned database.
+    pub fn clone_database(&self, name: &str, new_name: &str) -> Result<Database<'_>, DatabaseClientError> {
+        let result = self.engine
+            .storage_api()
+            .clone_database(&self.db_file_path(name), new_name);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::CreateDatabase,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(file_path) = result.data {
+                return Ok(Database::new(self, &file_path));
+            } else {
+                return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::CreateDatabase,
+                    DATA_NOT_RECEIVED.to_string()));
+            }
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::CreateDatabase,
+            UNEXPECTED_ERROR.to_string()));
+    }
 }
 
 impl DatabaseClient {
-    /// Creates a database with the given name.
-    fn create_database(&self, name: &str) -> Result<(), DatabaseOperationError> {
+    /// Gets the file path of a database in the database directory.
+    fn db_file_path(&self, name: &str) -> PathBuf {
+        self.db_dir.join(&format!("{}.{}", name, DB_FILE_EXTENSION))
+    }
+
+    /// Unlocks an encrypted database with the key of the encryption.
+    fn unlock_database(
+        &self,
+        file_path: &Path,
+        encryption: Option<&EncryptionOptions>,
+    ) -> Result<(), DatabaseOperationError> {
+        let encryption = match encryption {
+            Some(encryption) => encryption,
+            None => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::UnlockDatabase,
+                DatabaseError::Locked.to_string())),
+        };
+        let result = self.engine
+            .storage_api()
+            .unlock_database(file_path, &encryption.key);
+
+        if let Some(e) = result.error {
+            return Err(e);
+        }
+
+        if result.success {
+            return Ok(());
+        }
+
+        return Err(DatabaseOperationError::new(
+            DatabaseOperationErrorKind::UnlockDatabase,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Creates a database with the given name and options.
+    fn create_database(
+        &self,
+        name: &str,
+        options: &DatabaseOptions,
+    ) -> Result<(), DatabaseOperationError> {
         let result = self.engine
             .storage_api()
-            .create_database_to_db_dir(name);
+            .create_database_to_db_dir_with_options(name, options);
 
         if let Some(e) = result.error {
             return Err(e);
@@ -110,4 +289,203 @@ impl DatabaseClient {
             DatabaseOperationErrorKind::CreateDatabase,
             UNEXPECTED_ERROR.to_string()));
     }
+
+    /// Checks the integrity of a database in the database directory.
+    /// 
+    /// Returns a report of all problems found in the database file.
+    pub fn check_database(&self, name: &str) -> Result<IntegrityReportDto, DatabaseClientError> {
+        let result = self.engine
+            .storage_api()
+            .check_database(&self.db_file_path(name));
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::CheckDatabase,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(report) = result.data {
+                return Ok(report);
+            } else {
+                return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::CheckDatabase,
+                    DATA_NOT_RECEIVED.to_string()));
+            }
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::CheckDatabase,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Repairs a database in the database directory by salvaging it to a new database.
+    /// 
+    /// The original database is not modified.
+    pub fn repair_database(&self, name: &str, new_name: &str) -> Result<RepairReportDto, DatabaseClientError> {
+        let result = self.engine
+            .storage_api()
+            .repair_database(&self.db_file_path(name), new_name);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::CreateDatabase,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(repair) = result.data {
+                return Ok(repair);
+            } else {
+                return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::CreateDatabase,
+                    DATA_NOT_RECEIVED.to_string()));
+            }
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::CreateDatabase,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Backs up a database in the database directory to a backup directory.
+    /// 
+    /// The database can be used while it is backed up.
+    /// Returns the backup manifest.
+    pub fn backup_database(&self, name: &str, backup_dir_path: &Path) -> Result<BackupManifest, DatabaseClientError> {
+        let result = self.engine
+            .storage_api()
+            .backup_database(&self.db_file_path(name), backup_dir_path);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::Backup,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(manifest) = result.data {
+                return Ok(manifest);
+            } else {
+                return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::Backup,
+                    DATA_NOT_RECEIVED.to_string()));
+            }
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::Backup,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Backs up all databases in the database directory to a backup directory.
+    /// 
+    /// Returns the backup manifest.
+    pub fn backup_all_databases(&self, backup_dir_path: &Path) -> Result<BackupManifest, DatabaseClientError> {
+        let result = self.engine
+            .storage_api()
+            .backup_all_databases(backup_dir_path);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::Backup,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(manifest) = result.data {
+                return Ok(manifest);
+            } else {
+                return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::Backup,
+                    DATA_NOT_RECEIVED.to_string()));
+            }
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::Backup,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Verifies that a backup is complete and not corrupted.
+    /// 
+    /// Returns the backup manifest.
+    pub fn verify_backup(&self, backup_dir_path: &Path) -> Result<BackupManifest, DatabaseClientError> {
+        let result = self.engine
+            .storage_api()
+            .verify_backup(backup_dir_path);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::Restore,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(manifest) = result.data {
+                return Ok(manifest);
+            } else {
+                return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::Restore,
+                    DATA_NOT_RECEIVED.to_string()));
+            }
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::Restore,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Restores a database from a backup to the database directory.
+    /// 
+    /// The backup is verified before it replaces the database.
+    /// Returns the restored database.
+    pub fn restore_database(&self, backup_dir_path: &Path, name: &str) -> Result<Database<'_>, DatabaseClientError> {
+        let result = self.engine
+            .storage_api()
+            .restore_database(backup_dir_path, name);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::Restore,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(file_path) = result.data {
+                return Ok(Database::new(self, &file_path));
+            } else {
+                return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::Restore,
+                    DATA_NOT_RECEIVED.to_string()));
+            }
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::Restore,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Restores all databases from a backup to the database directory.
+    /// 
+    /// The whole backup is verified before any database is replaced.
+    pub fn restore_all_databases(&self, backup_dir_path: &Path) -> Result<(), DatabaseClientError> {
+        let result = self.engine
+            .storage_api()
+            .restore_all_databases(backup_dir_path);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::Restore,
+                e.message));
+        }
+
+        if result.success {
+            return Ok(());
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::Restore,
+            UNEXPECTED_ERROR.to_string()));
+    }
 }
@@ -1,5 +1,10 @@
 use std::error::Error;
 use std::fmt;
+pub use engine::storage::error::{
+    SchemaValidationError,
+    SchemaViolation,
+    SchemaViolationKind,
+};
 
 pub const UNEXPECTED_ERROR: &str = "Unexpected error";
 pub const DATA_NOT_RECEIVED: &str = "Data expected but not received";
@@ -9,6 +14,8 @@ pub const DATA_NOT_RECEIVED: &str = "Data expected but not received";
 pub struct DatabaseClientError {
     pub kind: DatabaseClientErrorKind,
     pub message: String,
+    /// Schema violations if a document didn't follow the collection's schema.
+    pub validation_error: Option<SchemaValidationError>,
 }
 
 /// Kind of database client error.
@@ -28,8 +35,12 @@ pub enum DatabaseClientErrorKind {
     GetCollection,
     /// Failed to create database.
     CreateDatabase,
+    /// Failed to modify database.
+    ModifyDatabase,
     /// Failed to create collection.
     CreateCollection,
+    /// Failed to modify collection.
+    ModifyCollection,
     /// Failed to insert document.
     InsertOneDocument,
     /// Failed to replace document.
@@ -44,6 +55,16 @@ pub enum DatabaseClientErrorKind {
     FindOneDocument,
     /// Failed to find documents.
     FindManyDocuments,
+    /// Failed to migrate database.
+    Migration,
+    /// Failed to check database.
+    CheckDatabase,
+    /// Failed to back up database.
+    Backup,
+    /// Failed to restore database.
+    Restore,
+    /// Failed to watch changes.
+    Watch,
 }
 
 impl fmt::Display for DatabaseClientError {
@@ -59,7 +80,9 @@ impl fmt::Display for DatabaseClientError {
                 DatabaseClientErrorKind::GetDatabase => "Failed to get database",
                 DatabaseClientErrorKind::GetCollection => "Failed to get collection",
                 DatabaseClientErrorKind::CreateDatabase => "Failed to create database",
+                DatabaseClientErrorKind::ModifyDatabase => "Failed to modify database",
                 DatabaseClientErrorKind::CreateCollection => "Failed to create collection",
+                DatabaseClientErrorKind::ModifyCollection => "Failed to modify collection",
                 DatabaseClientErrorKind::InsertOneDocument => "Failed to insert document",
                 DatabaseClientErrorKind::ReplaceOneDocument => "Failed to replace document",
                 DatabaseClientErrorKind::DeleteOneDocument => "Failed to delete document",
@@ -67,6 +90,11 @@ impl fmt::Display for DatabaseClientError {
                 DatabaseClientErrorKind::FindAllDocuments => "Failed to find all documents",
                 DatabaseClientErrorKind::FindOneDocument => "Failed to find document",
                 DatabaseClientErrorKind::FindManyDocuments => "Failed to find documents",
+                DatabaseClientErrorKind::Migration => "Failed to migrate database",
+                DatabaseClientErrorKind::CheckDatabase => "Failed to check database",
+                DatabaseClientErrorKind::Backup => "Failed to back up database",
+                DatabaseClientErrorKind::Restore => "Failed to restore database",
+                DatabaseClientErrorKind::Watch => "Failed to watch changes",
             },
             self.message,
         )
@@ -75,7 +103,7 @@ impl fmt::Display for DatabaseClientError {
 
 impl DatabaseClientError {
     pub fn new(kind: DatabaseClientErrorKind, message: String) -> Self {
-        Self { kind, message }
+        Self { kind, message, validation_error: None }
     }
 }
 
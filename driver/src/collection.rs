@@ -1,12 +1,29 @@
-use std::collections::HashMap;
+use std::time::Duration;
 use engine::{
+    indexmap::IndexMap,
     storage::{
         error::DatabaseOperationError,
-        document::DocumentDto,
+        document::{
+            DocumentDto,
+            FindOptions,
+            decode_decimal128,
+            decode_int128,
+            decode_uint128,
+        },
         pb::document::data_type,
+        pb::document::Id,
+        history::DocumentRevisionDto,
     },
     DocumentInputDataField,
 };
+pub use engine::storage::{
+    collection::CollectionOptions,
+    pb::IdStrategy,
+    pb::CollectionSchema,
+    pb::FieldSchema,
+    pb::TtlPolicy,
+    pb::CappedLimits,
+};
 use crate::{
     client::{
         DatabaseClient,
@@ -18,12 +35,23 @@ use crate::{
         },
     },
     database::Database,
+    change_stream::{
+        ChangeStream,
+        ChangeStreamOptions,
+    },
+    hook::{
+        WriteContext,
+        WriteHooks,
+        WriteOperation,
+    },
+    migration::MIGRATIONS_COLLECTION,
     document::{
         DocumentModel,
         DataType,
         DocumentId,
         DocumentQuery,
         DocumentQueryOptions,
+        DocumentRevision,
     },
 };
 
@@ -36,6 +64,7 @@ pub struct Collection<'a> {
     client: &'a DatabaseClient,
     database: &'a Database<'a>,
     name: String,
+    hooks: WriteHooks,
 }
 
 impl<'a> Collection<'a> {
@@ -50,9 +79,19 @@ impl<'a> Collection<'a> {
             client,
             database,
             name: name.to_string(),
+            hooks: WriteHooks::new(),
         }
     }
 
+    /// Sets write hooks that run for documents in this collection.
+    /// 
+    /// They run after the hooks of the database client.
+    pub fn with_hooks(mut self, hooks: WriteHooks) -> Collection<'a> {
+        self.hooks = hooks;
+
+        return self;
+    }
+
     /// Finds all documents in this collection.
     /// 
     /// Returns the found documents.
@@ -77,12 +116,17 @@ impl<'a> Collection<'a> {
 
         if result.success {
             if let Some(document_dtos) = result.data {
-                let documents: Vec<DocumentModel> = document_dtos
+                let documents: Result<Vec<DocumentModel>, String> = document_dtos
                     .into_iter()
-                    .map(|document| transform_document_dto_to_document(document))
+                    .map(transform_document_dto_to_document)
                     .collect();
 
-                return Ok(documents);
+                match documents {
+                    Ok(documents) => return Ok(documents),
+                    Err(e) => return Err(DatabaseClientError::new(
+                        DatabaseClientErrorKind::FindAllDocuments,
+                        e)),
+                }
             }
             return Err(DatabaseClientError::new(
                 DatabaseClientErrorKind::FindAllDocuments,
@@ -97,6 +141,7 @@ impl<'a> Collection<'a> {
     /// Finds documents in this collection using query.
     /// 
     /// Query contains fields with values that the document needs to match.
+    /// Numbers match across data types unless strict types are set in options.
     /// 
     /// Returns the found documents.
     pub fn find_many(
@@ -104,15 +149,16 @@ impl<'a> Collection<'a> {
         query: &DocumentQuery,
         options: Option<&DocumentQueryOptions>,
     ) -> Result<Vec<DocumentModel>, DatabaseClientError> {
-        let mut limit = None;
+        let mut find_options = FindOptions::default();
         if let Some(options) = options {
-            limit = options.limit;
+            find_options.limit = options.limit;
+            find_options.strict_types = options.strict_types;
         }
         let query = transform_document_data_to_input(&query.data);
 
         let result = self.client.engine
             .storage_api()
-            .find_documents(self.database.connection_string(), self.name(), &query, limit);
+            .find_documents_with_options(self.database.connection_string(), self.name(), &query, &find_options);
 
         if let Some(e) = result.error {
             return Err(DatabaseClientError::new(
@@ -122,12 +168,17 @@ impl<'a> Collection<'a> {
 
         if result.success {
             if let Some(document_dtos) = result.data {
-                let documents: Vec<DocumentModel> = document_dtos
+                let documents: Result<Vec<DocumentModel>, String> = document_dtos
                     .into_iter()
-                    .map(|document| transform_document_dto_to_document(document))
+                    .map(transform_document_dto_to_document)
                     .collect();
 
-                return Ok(documents);
+                match documents {
+                    Ok(documents) => return Ok(documents),
+                    Err(e) => return Err(DatabaseClientError::new(
+                        DatabaseClientErrorKind::FindManyDocuments,
+                        e)),
+                }
             }
             return Err(DatabaseClientError::new(
                 DatabaseClientErrorKind::FindManyDocuments,
@@ -145,7 +196,7 @@ impl<'a> Collection<'a> {
     pub fn find_one_by_id(&self, id: &DocumentId) -> Result<Option<DocumentModel>, DatabaseClientError> {
         let result = self.client.engine
             .storage_api()
-            .find_document_by_id(&id.0, self.database.connection_string(), self.name());
+            .find_document_by_id(&transform_document_id_to_id(id), self.database.connection_string(), self.name());
 
         if let Some(e) = result.error {
             return Err(DatabaseClientError::new(
@@ -156,7 +207,12 @@ impl<'a> Collection<'a> {
         if result.success {
             if let Some(data) = result.data {
                 if let Some(document_dto) = data {
-                    return Ok(Some(transform_document_dto_to_document(document_dto)));
+                    match transform_document_dto_to_document(document_dto) {
+                        Ok(document) => return Ok(Some(document)),
+                        Err(e) => return Err(DatabaseClientError::new(
+                            DatabaseClientErrorKind::FindOneDocument,
+                            e)),
+                    }
                 } else {
                     return Ok(None);
                 }
@@ -171,25 +227,323 @@ impl<'a> Collection<'a> {
             UNEXPECTED_ERROR.to_string()));
     }
 
+    /// Sets this collection's schema. `None` removes the schema.
+    /// 
+    /// Documents that are inserted or replaced must follow the schema.
+    /// Existing documents are not validated.
+    pub fn set_schema(&self, schema: Option<CollectionSchema>) -> Result<(), DatabaseClientError> {
+        let result = self.client.engine
+            .storage_api()
+            .set_collection_schema(self.name(), self.database.connection_string(), schema);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::ModifyCollection,
+                e.message));
+        }
+
+        if result.success {
+            return Ok(());
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::ModifyCollection,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Gets this collection's schema.
+    /// 
+    /// Returns `None` if the collection doesn't have a schema.
+    pub fn get_schema(&self) -> Result<Option<CollectionSchema>, DatabaseClientError> {
+        let result = self.client.engine
+            .storage_api()
+            .find_collection_schema(self.name(), self.database.connection_string());
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::GetCollection,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(schema) = result.data {
+                return Ok(schema);
+            }
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::GetCollection,
+                DATA_NOT_RECEIVED.to_string()));
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::GetCollection,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Sets this collection's time-to-live policy. `None` removes the policy.
+    /// 
+    /// Expired documents are not returned by finds. They are removed
+    /// when the database is opened, after inserts and by `Database::purge_expired`.
+    pub fn set_ttl(&self, ttl: Option<TtlPolicy>) -> Result<(), DatabaseClientError> {
+        let result = self.client.engine
+            .storage_api()
+            .set_collection_ttl(self.name(), self.database.connection_string(), ttl);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::ModifyCollection,
+                e.message));
+        }
+
+        if result.success {
+            return Ok(());
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::ModifyCollection,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Enables or disables this collection's history mode.
+    /// 
+    /// With history mode, the previous version of each replaced, updated
+    /// or deleted document is kept as a revision. Deleted documents are kept as tombstones.
+    /// Existing revisions are kept when history mode is disabled.
+    pub fn set_history(&self, enabled: bool) -> Result<(), DatabaseClientError> {
+        let result = self.client.engine
+            .storage_api()
+            .set_collection_history(self.name(), self.database.connection_string(), enabled);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::ModifyCollection,
+                e.message));
+        }
+
+        if result.success {
+            return Ok(());
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::ModifyCollection,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Finds the revisions of a document in this collection.
+    /// 
+    /// Revisions of deleted documents can be found as well.
+    /// Returns the revisions in the order they were made.
+    pub fn find_revisions(&self, id: &DocumentId) -> Result<Vec<DocumentRevision>, DatabaseClientError> {
+        let result = self.client.engine
+            .storage_api()
+            .find_document_revisions(self.database.connection_string(), self.name(), &transform_document_id_to_id(id));
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::FindManyDocuments,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(revisions) = result.data {
+                let revisions: Result<Vec<DocumentRevision>, String> = revisions
+                    .into_iter()
+                    .map(transform_revision_dto_to_revision)
+                    .collect();
+
+                match revisions {
+                    Ok(revisions) => return Ok(revisions),
+                    Err(e) => return Err(DatabaseClientError::new(
+                        DatabaseClientErrorKind::FindManyDocuments,
+                        e)),
+                }
+            }
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::FindManyDocuments,
+                DATA_NOT_RECEIVED.to_string()));
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::FindManyDocuments,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Finds a revision of a document in this collection by revision number.
+    /// 
+    /// Returns `None` if the revision doesn't exist.
+    pub fn find_revision(
+        &self,
+        id: &DocumentId,
+        revision: u64,
+    ) -> Result<Option<DocumentRevision>, DatabaseClientError> {
+        let result = self.client.engine
+            .storage_api()
+            .find_document_revision(
+                self.database.connection_string(),
+                self.name(),
+                &transform_document_id_to_id(id),
+                revision
+            );
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::FindOneDocument,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(document_revision) = result.data {
+                match document_revision.map(transform_revision_dto_to_revision).transpose() {
+                    Ok(document_revision) => return Ok(document_revision),
+                    Err(e) => return Err(DatabaseClientError::new(
+                        DatabaseClientErrorKind::FindOneDocument,
+                        e)),
+                }
+            }
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::FindOneDocument,
+                DATA_NOT_RECEIVED.to_string()));
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::FindOneDocument,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Restores a document in this collection to a revision.
+    /// 
+    /// If the document exists, its current version is kept as a new revision.
+    /// Deleted documents are inserted again with the same id.
+    /// 
+    /// Write hooks run as a replace if the document exists and as an insert otherwise.
+    /// They get the revision's document and changes to it are ignored.
+    /// 
+    /// Returns the restored document.
+    pub fn restore_revision(
+        &self,
+        id: &DocumentId,
+        revision: u64,
+    ) -> Result<DocumentModel, DatabaseClientError> {
+        let mut context = None;
+        if self.has_hooks() {
+            let operation = match self.find_one_by_id(id)? {
+                Some(_) => WriteOperation::Replace,
+                None => WriteOperation::Insert,
+            };
+            let write_context = self.write_context(operation);
+            if let Some(mut document_revision) = self.find_revision(id, revision)? {
+                self.run_pre_write_hooks(&write_context, &mut document_revision.document)?;
+            }
+            context = Some(write_context);
+        }
+
+        let result = self.client.engine
+            .storage_api()
+            .restore_document_revision(
+                self.database.connection_string(),
+                self.name(),
+                &transform_document_id_to_id(id),
+                revision
+            );
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::ReplaceOneDocument,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(document_dto) = result.data {
+                match transform_document_dto_to_document(document_dto) {
+                    Ok(document) => {
+                        if let Some(context) = &context {
+                            self.run_post_write_hooks(context, &document);
+                        }
+                        return Ok(document);
+                    },
+                    Err(e) => return Err(DatabaseClientError::new(
+                        DatabaseClientErrorKind::ReplaceOneDocument,
+                        e)),
+                }
+            }
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::ReplaceOneDocument,
+                DATA_NOT_RECEIVED.to_string()));
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::ReplaceOneDocument,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Removes revisions that are older than `older_than` from this collection.
+    /// 
+    /// Deleted documents cannot be restored after their tombstones are removed.
+    /// 
+    /// Returns the number of removed revisions.
+    pub fn purge_history(&self, older_than: Duration) -> Result<usize, DatabaseClientError> {
+        let result = self.client.engine
+            .storage_api()
+            .purge_document_history(self.database.connection_string(), self.name(), older_than.as_secs());
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::DeleteManyDocuments,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(removed_count) = result.data {
+                return Ok(removed_count);
+            }
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::DeleteManyDocuments,
+                DATA_NOT_RECEIVED.to_string()));
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::DeleteManyDocuments,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
     /// Inserts a document to this collection.
     /// 
+    /// If the collection has a schema, the returned error lists every schema violation.
+    /// The document id is generated with the collection's id strategy
+    /// unless the document has an id. Collections with a client id strategy
+    /// require the document to have an id.
+    /// 
     /// Returns the new document with id populated.
-    pub fn insert_one(&self, document: DocumentModel) -> Result<DocumentModel, DatabaseClientError> {
+    pub fn insert_one(&self, mut document: DocumentModel) -> Result<DocumentModel, DatabaseClientError> {
+        let context = self.write_context(WriteOperation::Insert);
+        self.run_pre_write_hooks(&context, &mut document)?;
         let input = transform_document_data_to_input(&document.data);
+        let document_id = match document.id {
+            DocumentId::Integer(0) => None,
+            ref id => Some(transform_document_id_to_id(id)),
+        };
 
         let result = self.client.engine
             .storage_api()
-            .create_document(self.database.connection_string(), self.name(), input);
+            .create_document_with_id(self.database.connection_string(), self.name(), input, document_id);
 
         if let Some(e) = result.error {
-            return Err(DatabaseClientError::new(
+            let mut error = DatabaseClientError::new(
                 DatabaseClientErrorKind::InsertOneDocument,
-                e.message));
+                e.message);
+            error.validation_error = e.validation_error;
+            return Err(error);
         }
 
         if result.success {
             if let Some(document) = result.data {
-                return Ok(transform_document_dto_to_document(document));
+                let document = match transform_document_dto_to_document(document) {
+                    Ok(document) => document,
+                    Err(e) => return Err(DatabaseClientError::new(
+                        DatabaseClientErrorKind::InsertOneDocument,
+                        e)),
+                };
+                self.run_post_write_hooks(&context, &document);
+                return Ok(document);
             }
             return Err(DatabaseClientError::new(
                 DatabaseClientErrorKind::InsertOneDocument,
@@ -204,20 +558,27 @@ impl<'a> Collection<'a> {
     /// Replaces a document in this collection with a new one.
     /// 
     /// Only the data is replaced, id remains the same.
-    pub fn replace_one_by_id(&self, id: &DocumentId, document: DocumentModel) -> Result<(), DatabaseClientError> {
+    pub fn replace_one_by_id(&self, id: &DocumentId, mut document: DocumentModel) -> Result<(), DatabaseClientError> {
+        let context = self.write_context(WriteOperation::Replace);
+        document.id = id.clone();
+        self.run_pre_write_hooks(&context, &mut document)?;
         let input = transform_document_data_to_input(&document.data);
-        
+
         let result = self.client.engine
             .storage_api()
-            .replace_document(self.database.connection_string(), &id.0, self.name(), input);
+            .replace_document(self.database.connection_string(), &transform_document_id_to_id(id), self.name(), input);
 
         if let Some(e) = result.error {
-            return Err(DatabaseClientError::new(
+            let mut error = DatabaseClientError::new(
                 DatabaseClientErrorKind::ReplaceOneDocument,
-                e.message));
+                e.message);
+            error.validation_error = e.validation_error;
+            return Err(error);
         }
 
         if result.success {
+            document.id = id.clone();
+            self.run_post_write_hooks(&context, &document);
             return Ok(());
         }
 
@@ -227,10 +588,21 @@ impl<'a> Collection<'a> {
     }
 
     /// Deletes a document by id from this collection.
+    /// 
+    /// Write hooks get the document that is deleted.
     pub fn delete_one_by_id(&self, id: &DocumentId) -> Result<(), DatabaseClientError> {
+        let context = self.write_context(WriteOperation::Delete);
+        let mut document = DocumentModel::with_id(id.clone());
+        if self.has_hooks() {
+            if let Some(found_document) = self.find_one_by_id(id)? {
+                document = found_document;
+            }
+            self.run_pre_write_hooks(&context, &mut document.clone())?;
+        }
+
         let result = self.client.engine
             .storage_api()
-            .delete_document(self.database.connection_string(), &id.0, self.name());
+            .delete_document(self.database.connection_string(), &transform_document_id_to_id(id), self.name());
 
         if let Some(e) = result.error {
             return Err(DatabaseClientError::new(
@@ -239,6 +611,7 @@ impl<'a> Collection<'a> {
         }
 
         if result.success {
+            self.run_post_write_hooks(&context, &document);
             return Ok(());
         }
 
@@ -249,8 +622,20 @@ impl<'a> Collection<'a> {
 
     /// Deletes all documents from this collection.
     /// 
+    /// Write hooks run for each deleted document.
+    /// If a pre-write hook fails, no documents are deleted.
+    /// 
     /// Returns the number of deleted documents.
     pub fn delete_all(&self) -> Result<usize, DatabaseClientError> {
+        let context = self.write_context(WriteOperation::Delete);
+        let mut documents = Vec::new();
+        if self.has_hooks() {
+            documents = self.find_all(None)?;
+            for document in documents.iter() {
+                self.run_pre_write_hooks(&context, &mut document.clone())?;
+            }
+        }
+
         let result = self.client.engine
             .storage_api()
             .delete_all_documents(self.database.connection_string(), self.name());
@@ -263,6 +648,9 @@ impl<'a> Collection<'a> {
 
         if result.success {
             if let Some(deleted_count) = result.data {
+                for document in documents.iter() {
+                    self.run_post_write_hooks(&context, document);
+                }
                 return Ok(deleted_count);
             }
             return Err(DatabaseClientError::new(
@@ -274,11 +662,72 @@ impl<'a> Collection<'a> {
             DatabaseClientErrorKind::DeleteManyDocuments,
             UNEXPECTED_ERROR.to_string()));
     }
+
+    /// Watches this collection for inserted, replaced, updated and deleted documents.
+    /// 
+    /// The collection in the options is ignored.
+    /// Returns a change stream that receives the changes.
+    pub fn watch(&self, options: &ChangeStreamOptions) -> Result<ChangeStream, DatabaseClientError> {
+        let options = ChangeStreamOptions {
+            collection: Some(self.name.clone()),
+            ..options.clone()
+        };
+        self.database.watch(&options)
+    }
+}
+
+impl<'a> Collection<'a> {
+    /// Gets the write hooks of the database client.
+    /// 
+    /// Client hooks don't run for the reserved migrations collection.
+    fn client_hooks(&self) -> Option<&WriteHooks> {
+        if self.name == MIGRATIONS_COLLECTION {
+            return None;
+        }
+
+        return Some(self.client.hooks());
+    }
+
+    /// Checks if any write hooks run for this collection.
+    fn has_hooks(&self) -> bool {
+        !self.hooks.is_empty() || self.client_hooks().is_some_and(|hooks| !hooks.is_empty())
+    }
+
+    fn write_context(&self, operation: WriteOperation) -> WriteContext {
+        WriteContext {
+            operation,
+            collection: self.name.clone(),
+        }
+    }
+
+    /// Runs the pre-write hooks of the client and this collection.
+    fn run_pre_write_hooks(
+        &self,
+        context: &WriteContext,
+        document: &mut DocumentModel,
+    ) -> Result<(), DatabaseClientError> {
+        if let Some(hooks) = self.client_hooks() {
+            hooks.run_pre_write(context, document)?;
+        }
+
+        self.hooks.run_pre_write(context, document)
+    }
+
+    /// Runs the post-write hooks of the client and this collection.
+    fn run_post_write_hooks(&self, context: &WriteContext, document: &DocumentModel) {
+        if let Some(hooks) = self.client_hooks() {
+            hooks.run_post_write(context, document);
+        }
+
+        self.hooks.run_post_write(context, document);
+    }
 }
 
 /// Transforms engine `DocumentDto` to driver document model.
-fn transform_document_dto_to_document(document_dto: DocumentDto) -> DocumentModel {
-    let mut data = HashMap::new();
+/// 
+/// Returns an error message if a field value cannot be decoded.
+pub(crate) fn transform_document_dto_to_document(document_dto: DocumentDto) -> Result<DocumentModel, String> {
+    let mut data = IndexMap::new();
     for (key, value) in document_dto.data {
         let data_type = match value.data_type {
             Some(data_type::DataType::Int32(v)) => DataType::Int32(v),
@@ -286,20 +735,60 @@ fn transform_document_dto_to_document(document_dto: DocumentDto) -> DocumentMode
             Some(data_type::DataType::Decimal(v)) => DataType::Decimal(v),
             Some(data_type::DataType::Bool(v)) => DataType::Bool(v),
             Some(data_type::DataType::Text(v)) => DataType::Text(v),
+            Some(data_type::DataType::Decimal128(v)) => match decode_decimal128(&v) {
+                Some(v) => DataType::Decimal128(v),
+                None => return Err(format!("Cannot decode Decimal128 value of field '{}'", key)),
+            },
+            Some(data_type::DataType::Uint32(v)) => DataType::UInt32(v),
+            Some(data_type::DataType::Uint64(v)) => DataType::UInt64(v),
+            Some(data_type::DataType::Int128(v)) => match decode_int128(&v) {
+                Some(v) => DataType::Int128(v),
+                None => return Err(format!("Cannot decode Int128 value of field '{}'", key)),
+            },
+            Some(data_type::DataType::Uint128(v)) => match decode_uint128(&v) {
+                Some(v) => DataType::UInt128(v),
+                None => return Err(format!("Cannot decode UInt128 value of field '{}'", key)),
+            },
             _ => continue,
         };
 
         data.insert(key, data_type);
     }
 
-    return DocumentModel {
-        id: DocumentId(document_dto.id),
+    return Ok(DocumentModel {
+        id: transform_id_to_document_id(document_dto.id),
         data
-    };
+    });
+}
+
+/// Transforms engine `DocumentRevisionDto` to driver document revision.
+fn transform_revision_dto_to_revision(revision_dto: DocumentRevisionDto) -> Result<DocumentRevision, String> {
+    Ok(DocumentRevision {
+        revision: revision_dto.revision,
+        timestamp: revision_dto.timestamp,
+        deleted: revision_dto.deleted,
+        document: transform_document_dto_to_document(revision_dto.document)?,
+    })
+}
+
+/// Transforms engine document id to driver document id.
+pub(crate) fn transform_id_to_document_id(id: Id) -> DocumentId {
+    match id {
+        Id::Integer(id) => DocumentId::Integer(id),
+        Id::Text(id) => DocumentId::Text(id),
+    }
+}
+
+/// Transforms driver document id to engine document id.
+fn transform_document_id_to_id(id: &DocumentId) -> Id {
+    match id {
+        DocumentId::Integer(id) => Id::Integer(*id),
+        DocumentId::Text(id) => Id::Text(id.to_string()),
+    }
 }
 
 /// Transforms driver document data to engine input data.
-fn transform_document_data_to_input(data: &HashMap<String, DataType>) -> Vec<DocumentInputDataField> {
+fn transform_document_data_to_input(data: &IndexMap<String, DataType>) -> Vec<DocumentInputDataField> {
     let mut input = Vec::new();
     for (key, value) in data {
         let (data_type, data_value) = match value {
@@ -308,6 +797,11 @@ fn transform_document_data_to_input(data: &HashMap<String, DataType>) -> Vec<Doc
             DataType::Decimal(v) => ("Decimal", v.to_string()),
             DataType::Bool(v) => ("Bool", v.to_string()),
             DataType::Text(v) => ("Text", v.to_string()),
+            DataType::Decimal128(v) => ("Decimal128", v.to_string()),
+            DataType::UInt32(v) => ("UInt32", v.to_string()),
+            DataType::UInt64(v) => ("UInt64", v.to_string()),
+            DataType::Int128(v) => ("Int128", v.to_string()),
+            DataType::UInt128(v) => ("UInt128", v.to_string()),
         };
 
         input.push(DocumentInputDataField::new(&key, data_type, &data_value));
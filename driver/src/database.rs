@@ -9,15 +9,32 @@ use engine::storage::{
         DatabaseOperationErrorKind,
     },
 };
-use crate::collection::Collection;
+pub use engine::storage::{
+    database::DatabaseOptions,
+    encryption::{
+        Cipher,
+        EncryptionKey,
+        EncryptionOptions,
+    },
+    pb::Compression,
+};
+use crate::collection::{
+    Collection,
+    CollectionOptions,
+};
 use crate::client::{
     error::{
         DatabaseClientError,
         DatabaseClientErrorKind,
         UNEXPECTED_ERROR,
+        DATA_NOT_RECEIVED,
     },
     DatabaseClient,
 };
+use crate::change_stream::{
+    ChangeStream,
+    ChangeStreamOptions,
+};
 
 /// Database API.
 /// 
@@ -33,6 +50,10 @@ impl<'a> Database<'a> {
     pub fn connection_string(&self) -> &Path {
         &self.connection_string
     }
+
+    pub(crate) fn client(&self) -> &'a DatabaseClient {
+        self.client
+    }
 }
 
 impl<'a> Database<'a> {
@@ -45,8 +66,20 @@ impl<'a> Database<'a> {
 
     /// Gets a collection from this database using the collection name.
     /// 
-    /// Creates the collection if it doesn't exist.
+    /// Creates the collection with default options if it doesn't exist.
     pub fn get_collection(&self, name: &str) -> Result<Collection, DatabaseClientError> {
+        self.get_collection_with_options(name, &CollectionOptions::default())
+    }
+
+    /// Gets a collection from this database using the collection name.
+    /// 
+    /// Creates the collection with the given options if it doesn't exist.
+    /// Options of existing collections are not changed.
+    pub fn get_collection_with_options(
+        &self,
+        name: &str,
+        options: &CollectionOptions,
+    ) -> Result<Collection<'_>, DatabaseClientError> {
         let result = self.client.engine
             .storage_api()
             .find_collection(name, self.connection_string());
@@ -60,7 +93,7 @@ impl<'a> Database<'a> {
         if result.success {
             if let Some(collection) = result.data {
                 if let None = collection {
-                    if let Err(e) = self.create_collection(name) {
+                    if let Err(e) = self.create_collection(name, options) {
                         return Err(DatabaseClientError::new(
                             DatabaseClientErrorKind::GetCollection,
                             format!("Cannot create collection: {}", e.message)));
@@ -106,12 +139,124 @@ impl<'a> Database<'a> {
     }
 }
 
+impl<'a> Database<'a> {
+    /// Renames a collection in this database.
+    pub fn rename_collection(&self, name: &str, new_name: &str) -> Result<(), DatabaseClientError> {
+        let result = self.client.engine
+            .storage_api()
+            .rename_collection(name, new_name, self.connection_string());
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::ModifyCollection,
+                e.message));
+        }
+
+        if result.success {
+            return Ok(());
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::ModifyCollection,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Copies a collection in this database to the target database with a new name.
+    /// 
+    /// The target database can be this database.
+    pub fn copy_collection(
+        &self,
+        name: &str,
+        new_name: &str,
+        target: &Database,
+    ) -> Result<(), DatabaseClientError> {
+        let result = self.client.engine
+            .storage_api()
+            .copy_collection(name, self.connection_string(), new_name, target.connection_string());
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::CreateCollection,
+                e.message));
+        }
+
+        if result.success {
+            return Ok(());
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::CreateCollection,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Removes expired documents from all collections in this database.
+    /// 
+    /// Returns the number of removed documents.
+    pub fn purge_expired(&self) -> Result<usize, DatabaseClientError> {
+        let result = self.client.engine
+            .storage_api()
+            .purge_expired_documents(self.connection_string());
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::DeleteManyDocuments,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(removed_count) = result.data {
+                return Ok(removed_count);
+            }
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::DeleteManyDocuments,
+                DATA_NOT_RECEIVED.to_string()));
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::DeleteManyDocuments,
+            UNEXPECTED_ERROR.to_string()));
+    }
+
+    /// Watches this database for inserted, replaced, updated and deleted documents.
+    /// 
+    /// Set the collection in the options to watch only one collection.
+    /// Returns a change stream that receives the changes.
+    pub fn watch(&self, options: &ChangeStreamOptions) -> Result<ChangeStream, DatabaseClientError> {
+        let result = self.client.engine
+            .storage_api()
+            .watch_database(self.connection_string(), options);
+
+        if let Some(e) = result.error {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::Watch,
+                e.message));
+        }
+
+        if result.success {
+            if let Some(change_stream) = result.data {
+                return Ok(ChangeStream::new(change_stream));
+            }
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::Watch,
+                DATA_NOT_RECEIVED.to_string()));
+        }
+
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::Watch,
+            UNEXPECTED_ERROR.to_string()));
+    }
+}
+
 impl<'a> Database<'a> {
     /// Creates a collection to this database.
-    fn create_collection(&self, name: &str) -> Result<(), DatabaseOperationError> {
+    fn create_collection(
+        &self,
+        name: &str,
+        options: &CollectionOptions,
+    ) -> Result<(), DatabaseOperationError> {
         let result = self.client.engine
             .storage_api()
-            .create_collection(name, self.connection_string());
+            .create_collection_with_options(name, self.connection_string(), options);
 
         if let Some(e) = result.error {
             return Err(e);
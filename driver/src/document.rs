@@ -1,14 +1,53 @@
-use std::{collections::HashMap, fmt};
+use std::fmt;
+use engine::{
+    chrono::{
+        DateTime,
+        Utc,
+    },
+    rust_decimal::Decimal,
+    indexmap::IndexMap,
+    storage::{
+        pb,
+        pb::document::data_type,
+        document::{
+            encode_decimal128,
+            encode_int128,
+            encode_uint128,
+        },
+    },
+};
 
 /// Data type for document id.
 /// 
-/// DocumentId is an unsigned 64-bit integer.
-#[derive(Debug, Clone)]
-pub struct DocumentId(pub u64);
+/// DocumentId is an unsigned 64-bit integer or text,
+/// depending on the collection's id strategy.
+/// Integer id 0 means the document has no id.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocumentId {
+    /// Integer id. Used by auto increment and client integer id strategies.
+    Integer(u64),
+    /// Text id. Used by UUID and client text id strategies.
+    Text(String),
+}
 
 impl fmt::Display for DocumentId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        match self {
+            DocumentId::Integer(id) => write!(f, "{}", id),
+            DocumentId::Text(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+impl From<u64> for DocumentId {
+    fn from(id: u64) -> Self {
+        DocumentId::Integer(id)
+    }
+}
+
+impl From<&str> for DocumentId {
+    fn from(id: &str) -> Self {
+        DocumentId::Text(id.to_string())
     }
 }
 
@@ -25,6 +64,16 @@ pub enum DataType {
     Bool(bool),
     /// UTF-8 string for dynamic text.
     Text(String),
+    /// Exact 128-bit decimal number. Use this for money values.
+    Decimal128(Decimal),
+    /// 32-bit unsigned integer.
+    UInt32(u32),
+    /// 64-bit unsigned integer.
+    UInt64(u64),
+    /// 128-bit signed integer.
+    Int128(i128),
+    /// 128-bit unsigned integer.
+    UInt128(u128),
 }
 
 impl fmt::Display for DataType {
@@ -38,26 +87,68 @@ impl fmt::Display for DataType {
                 DataType::Decimal(value) => value.to_string(),
                 DataType::Bool(value) => value.to_string(),
                 DataType::Text(value) => value.to_string(),
+                DataType::Decimal128(value) => value.to_string(),
+                DataType::UInt32(value) => value.to_string(),
+                DataType::UInt64(value) => value.to_string(),
+                DataType::Int128(value) => value.to_string(),
+                DataType::UInt128(value) => value.to_string(),
             }
         )
     }
 }
 
+/// Converts to engine data type.
+/// 
+/// Use this to set values in collection schemas, e.g. min and max values.
+impl From<DataType> for pb::document::DataType {
+    fn from(value: DataType) -> Self {
+        let data_type = match value {
+            DataType::Int64(v) => data_type::DataType::Int64(v),
+            DataType::Int32(v) => data_type::DataType::Int32(v),
+            DataType::Decimal(v) => data_type::DataType::Decimal(v),
+            DataType::Bool(v) => data_type::DataType::Bool(v),
+            DataType::Text(v) => data_type::DataType::Text(v),
+            DataType::Decimal128(v) => data_type::DataType::Decimal128(encode_decimal128(&v)),
+            DataType::UInt32(v) => data_type::DataType::Uint32(v),
+            DataType::UInt64(v) => data_type::DataType::Uint64(v),
+            DataType::Int128(v) => data_type::DataType::Int128(encode_int128(v)),
+            DataType::UInt128(v) => data_type::DataType::Uint128(encode_uint128(v)),
+        };
+
+        pb::document::DataType {
+            data_type: Some(data_type),
+        }
+    }
+}
+
 /// Model for database documents.
 /// 
 /// Use this to create documents that can be saved to databases.
+/// Fields keep the order they were inserted in.
 #[derive(Debug, Clone)]
 pub struct DocumentModel {
     pub id: DocumentId,
-    pub data: HashMap<String, DataType>
+    pub data: IndexMap<String, DataType>
 }
 
 impl DocumentModel {
     /// Creates a new document model.
+    /// 
+    /// The id is generated when the document is inserted.
     pub fn new() -> DocumentModel {
         DocumentModel {
-            id: DocumentId(0),
-            data: HashMap::new(),
+            id: DocumentId::Integer(0),
+            data: IndexMap::new(),
+        }
+    }
+
+    /// Creates a new document model with an id.
+    /// 
+    /// Use this with collections that have a client id strategy.
+    pub fn with_id(id: DocumentId) -> DocumentModel {
+        DocumentModel {
+            id,
+            data: IndexMap::new(),
         }
     }
 
@@ -65,20 +156,36 @@ impl DocumentModel {
         &self.id
     }
 
-    pub fn data(&self) -> &HashMap<String, DataType> {
+    pub fn data(&self) -> &IndexMap<String, DataType> {
         &self.data
     }
 }
 
+/// Previous version of a document in a collection with history mode.
+#[derive(Debug, Clone)]
+pub struct DocumentRevision {
+    /// Revision number. Revisions of a document are numbered from 1.
+    pub revision: u64,
+
+    /// Time when the version was replaced or deleted.
+    pub timestamp: DateTime<Utc>,
+
+    /// Whether the document was deleted. Deleted documents can be restored from their tombstone.
+    pub deleted: bool,
+
+    /// Document before it was replaced, updated or deleted.
+    pub document: DocumentModel,
+}
+
 /// Query used to match specific documents in collection.
 pub struct DocumentQuery {
-    pub data: HashMap<String, DataType>,
+    pub data: IndexMap<String, DataType>,
 }
 
 impl DocumentQuery {
     /// Creates a new document query.
     pub fn new() -> DocumentQuery {
-        DocumentQuery { data: HashMap::new() }
+        DocumentQuery { data: IndexMap::new() }
     }
 }
 
@@ -86,10 +193,25 @@ impl DocumentQuery {
 pub struct DocumentQueryOptions {
     /// Maximum number of documents to return.
     pub limit: Option<usize>,
+    /// Whether query values must have the same data type as document values.
+    /// 
+    /// By default numbers match across data types, e.g. `Int64(1999)` matches `Int32(1999)`.
+    pub strict_types: bool,
+}
+
+impl Default for DocumentQueryOptions {
+    fn default() -> Self {
+        Self::new(None)
+    }
 }
 
 impl DocumentQueryOptions {
     pub fn new(limit: Option<usize>) -> DocumentQueryOptions {
-        DocumentQueryOptions { limit }
+        DocumentQueryOptions { limit, strict_types: false }
+    }
+
+    /// Sets whether query values must have the same data type as document values.
+    pub fn with_strict_types(self, strict_types: bool) -> DocumentQueryOptions {
+        DocumentQueryOptions { strict_types, ..self }
     }
 }
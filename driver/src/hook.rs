@@ -0,0 +1,109 @@
+use crate::{
+    document::DocumentModel,
+    client::error::DatabaseClientError,
+};
+
+/// Hook that runs before a document is written.
+type PreWriteHook = Box<dyn Fn(&WriteContext, &mut DocumentModel) -> Result<(), DatabaseClientError> + Send + Sync>;
+
+/// Hook that runs after a document is written.
+type PostWriteHook = Box<dyn Fn(&WriteContext, &DocumentModel) + Send + Sync>;
+
+/// Write operation that runs hooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOperation {
+    /// Document is inserted.
+    Insert,
+    /// Document is replaced.
+    Replace,
+    /// Document is deleted.
+    Delete,
+}
+
+/// Write that hooks are run for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteContext {
+    /// Operation that writes the document.
+    pub operation: WriteOperation,
+    /// Name of the collection that the document is written to.
+    pub collection: String,
+}
+
+/// Hooks that run when documents are inserted, replaced or deleted.
+/// 
+/// Pre-write hooks run in the order they were added before the document is written.
+/// They can validate or modify the document. If a pre-write hook returns an error,
+/// the operation is aborted and the error is returned to the caller.
+/// 
+/// Post-write hooks run after the document is written. Use them for side effects.
+pub struct WriteHooks {
+    pre_write: Vec<PreWriteHook>,
+    post_write: Vec<PostWriteHook>,
+}
+
+impl Default for WriteHooks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WriteHooks {
+    /// Creates new write hooks with no hooks.
+    pub fn new() -> Self {
+        Self {
+            pre_write: Vec::new(),
+            post_write: Vec::new(),
+        }
+    }
+
+    /// Adds a hook that runs before a document is written.
+    /// 
+    /// Changes to the document are written for inserts and replaces.
+    /// Deletes get the document that is deleted and changes to it are ignored.
+    /// Restores get the restored revision of the document and changes to it are ignored.
+    pub fn add_pre_write<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&WriteContext, &mut DocumentModel) -> Result<(), DatabaseClientError> + Send + Sync + 'static
+    {
+        self.pre_write.push(Box::new(hook));
+
+        return self;
+    }
+
+    /// Adds a hook that runs after a document is written.
+    /// 
+    /// Inserted documents have their id populated.
+    pub fn add_post_write<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&WriteContext, &DocumentModel) + Send + Sync + 'static
+    {
+        self.post_write.push(Box::new(hook));
+
+        return self;
+    }
+
+    /// Checks if there are no hooks.
+    pub fn is_empty(&self) -> bool {
+        self.pre_write.is_empty() && self.post_write.is_empty()
+    }
+
+    /// Runs the pre-write hooks. Stops at the first error.
+    pub(crate) fn run_pre_write(
+        &self,
+        context: &WriteContext,
+        document: &mut DocumentModel,
+    ) -> Result<(), DatabaseClientError> {
+        for hook in self.pre_write.iter() {
+            hook(context, document)?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs the post-write hooks.
+    pub(crate) fn run_post_write(&self, context: &WriteContext, document: &DocumentModel) {
+        for hook in self.post_write.iter() {
+            hook(context, document);
+        }
+    }
+}
@@ -4,3 +4,6 @@ pub mod client;
 pub mod database;
 pub mod collection;
 pub mod document;
+pub mod migration;
+pub mod change_stream;
+pub mod hook;
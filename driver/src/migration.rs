@@ -0,0 +1,278 @@
+use std::{
+    fs,
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+use crate::{
+    database::Database,
+    collection::{
+        Collection,
+        CollectionOptions,
+        IdStrategy,
+    },
+    document::{
+        DataType,
+        DocumentId,
+        DocumentModel,
+    },
+    client::error::{
+        DatabaseClientError,
+        DatabaseClientErrorKind,
+        UNEXPECTED_ERROR,
+    },
+};
+
+/// Name of the reserved collection where applied migrations are recorded.
+/// 
+/// Applications should not use this collection for their own data.
+pub const MIGRATIONS_COLLECTION: &str = "_migrations";
+
+/// Migration step that modifies a database.
+type MigrationStep = Box<dyn Fn(&Database) -> Result<(), DatabaseClientError>>;
+
+/// Named migration step.
+struct Migration {
+    name: String,
+    step: MigrationStep,
+}
+
+/// Migration that has been applied to a database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppliedMigration {
+    /// Name of the migration.
+    pub name: String,
+    /// Time when the migration was applied as seconds since Unix epoch.
+    pub applied_at: u64,
+}
+
+/// Migration runner that evolves stored data between application releases.
+/// 
+/// Migrations are registered in order with unique names and they are
+/// applied in the same order. Applied migrations are recorded in
+/// the reserved collection `_migrations` inside the database,
+/// so each migration is applied only once.
+/// 
+/// Migration names are stored as document ids, so they cannot
+/// be empty or contain whitespace.
+pub struct Migrator {
+    migrations: Vec<Migration>,
+}
+
+impl Default for Migrator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Migrator {
+    /// Creates a new migration runner with no migrations.
+    pub fn new() -> Self {
+        Self {
+            migrations: Vec::new(),
+        }
+    }
+
+    /// Registers a migration step after the already registered ones.
+    pub fn add_migration<F>(mut self, name: &str, step: F) -> Self
+    where
+        F: Fn(&Database) -> Result<(), DatabaseClientError> + 'static
+    {
+        self.migrations.push(Migration {
+            name: name.to_string(),
+            step: Box::new(step),
+        });
+
+        return self;
+    }
+
+    /// Gets the migrations that have been applied to a database.
+    pub fn applied_migrations(&self, database: &Database) -> Result<Vec<AppliedMigration>, DatabaseClientError> {
+        if !migrations_collection_exists(database)? {
+            return Ok(Vec::new());
+        }
+
+        let documents = migrations_collection(database)?.find_all(None)?;
+        let applied_migrations = documents
+            .into_iter()
+            .map(|document| AppliedMigration {
+                name: document.id.to_string(),
+                applied_at: match document.data.get("applied_at") {
+                    Some(DataType::UInt64(applied_at)) => *applied_at,
+                    _ => 0,
+                },
+            })
+            .collect();
+
+        return Ok(applied_migrations);
+    }
+
+    /// Lists the names of registered migrations that have not been applied
+    /// to a database, in the order they would be applied.
+    /// 
+    /// Use this as a dry run. The database is not modified.
+    pub fn pending_migrations(&self, database: &Database) -> Result<Vec<String>, DatabaseClientError> {
+        self.validate_errors()?;
+        let applied_migrations = self.applied_migrations(database)?;
+
+        let pending_migrations = self.migrations
+            .iter()
+            .filter(|migration| !applied_migrations
+                .iter()
+                .any(|applied_migration| applied_migration.name == migration.name))
+            .map(|migration| migration.name.clone())
+            .collect();
+
+        return Ok(pending_migrations);
+    }
+
+    /// Applies pending migrations to a database in order.
+    /// 
+    /// Migrations are applied atomically. The database is backed up before
+    /// the first migration. If a migration fails, the database file is replaced
+    /// with the backup like when restoring a backup, so the restore is recorded
+    /// in the database journal and sent to change streams.
+    /// 
+    /// Fails if a backup of a previous run is kept, because its restore failed.
+    /// 
+    /// Returns the names of the applied migrations.
+    pub fn run(&self, database: &Database) -> Result<Vec<String>, DatabaseClientError> {
+        let pending_migrations = self.pending_migrations(database)?;
+        if pending_migrations.is_empty() {
+            return Ok(pending_migrations);
+        }
+
+        let db_file_path = database.connection_string();
+        let db_name = match db_file_path.file_stem() {
+            Some(db_name) => db_name.to_string_lossy().to_string(),
+            None => return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::Migration,
+                UNEXPECTED_ERROR.to_string())),
+        };
+        let backup_dir_path = db_file_path.with_file_name(format!(".{}.migration", db_name));
+        // A backup is left behind if a failed migration could not be restored
+        if backup_dir_path.exists() {
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::Migration,
+                format!(
+                    "Migration backup '{}' already exists. Restore or remove it before running migrations",
+                    backup_dir_path.display()
+                )));
+        }
+        let result = database.client().engine
+            .storage_api()
+            .backup_database(db_file_path, &backup_dir_path);
+        if let Some(e) = result.error {
+            let _ = fs::remove_dir_all(&backup_dir_path);
+            return Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::Migration,
+                format!("Cannot back up database: {}", e.message)));
+        }
+
+        let migration_result = self.apply_migrations(database, &pending_migrations);
+        if let Err(e) = migration_result {
+            let result = database.client().engine
+                .storage_api()
+                .restore_database(&backup_dir_path, &db_name);
+            if let Some(restore_error) = result.error {
+                return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::Migration,
+                    format!(
+                        "{}. Cannot restore database, backup is kept in '{}': {}",
+                        e.message,
+                        backup_dir_path.display(),
+                        restore_error.message
+                    )));
+            }
+            let _ = fs::remove_dir_all(&backup_dir_path);
+            return Err(e);
+        }
+        let _ = fs::remove_dir_all(&backup_dir_path);
+
+        return Ok(pending_migrations);
+    }
+}
+
+impl Migrator {
+    /// Validates migrations by checking their names.
+    fn validate_errors(&self) -> Result<(), DatabaseClientError> {
+        for (index, migration) in self.migrations.iter().enumerate() {
+            if migration.name.is_empty() || migration.name.contains(char::is_whitespace) {
+                return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::Migration,
+                    format!("Invalid migration name '{}'", migration.name)));
+            }
+            if self.migrations[..index].iter().any(|other| other.name == migration.name) {
+                return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::Migration,
+                    format!("Migration '{}' is registered more than once", migration.name)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies migrations and records them as applied.
+    fn apply_migrations(&self, database: &Database, names: &[String]) -> Result<(), DatabaseClientError> {
+        let collection = migrations_collection(database)?;
+
+        for name in names {
+            let migration = match self.migrations.iter().find(|migration| &migration.name == name) {
+                Some(migration) => migration,
+                None => return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::Migration,
+                    UNEXPECTED_ERROR.to_string())),
+            };
+
+            if let Err(e) = (migration.step)(database) {
+                return Err(DatabaseClientError::new(
+                    DatabaseClientErrorKind::Migration,
+                    format!("Migration '{}' failed: {}", name, e)));
+            }
+
+            let applied_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0);
+            let mut document = DocumentModel::with_id(DocumentId::Text(name.clone()));
+            document.data.insert("applied_at".to_string(), DataType::UInt64(applied_at));
+            collection.insert_one(document)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks if a database has the migrations collection.
+fn migrations_collection_exists(database: &Database) -> Result<bool, DatabaseClientError> {
+    let result = database.client().engine
+        .storage_api()
+        .find_collection(MIGRATIONS_COLLECTION, database.connection_string());
+
+    if let Some(e) = result.error {
+        return Err(DatabaseClientError::new(
+            DatabaseClientErrorKind::GetCollection,
+            e.message));
+    }
+
+    if result.success {
+        if let Some(collection) = result.data {
+            return Ok(collection.is_some());
+        }
+    }
+
+    return Err(DatabaseClientError::new(
+        DatabaseClientErrorKind::GetCollection,
+        UNEXPECTED_ERROR.to_string()));
+}
+
+/// Gets the migrations collection. Creates it if it doesn't exist.
+fn migrations_collection<'a>(database: &'a Database) -> Result<Collection<'a>, DatabaseClientError> {
+    let options = CollectionOptions {
+        id_strategy: IdStrategy::ClientText,
+        ..Default::default()
+    };
+
+    database.get_collection_with_options(MIGRATIONS_COLLECTION, &options)
+}
@@ -0,0 +1,44 @@
+use crate::common::{
+    Config,
+    create_test_document,
+};
+use driver::{
+    client::{
+        DatabaseClient,
+        ClientOptions,
+    },
+    database::{
+        EncryptionKey,
+        EncryptionOptions,
+    },
+};
+
+#[test]
+pub fn get_encrypted_database_success() {
+    let config = Config::new();
+    let options = ClientOptions {
+        encryption: Some(EncryptionOptions::new(
+            EncryptionKey::Passphrase("secret".to_string())
+        )),
+    };
+    let client = DatabaseClient::build_with_options(config.db_dir.path(), &options);
+    let db_name = "testdb123";
+
+    let database = client.get_database(db_name).unwrap();
+    let collection = database.get_collection("people").unwrap();
+    collection.insert_one(create_test_document()).unwrap();
+
+    let result = client.engine
+        .storage_api()
+        .lock_database(database.connection_string());
+    assert!(result.success);
+
+    let other_client = DatabaseClient::build(config.db_dir.path());
+    assert!(other_client.get_database(db_name).is_err());
+
+    let database = client.get_database(db_name).unwrap();
+    let collection = database.get_collection("people").unwrap();
+    assert_eq!(collection.find_all(None).unwrap().len(), 1);
+
+    config.close_temp_dirs();
+}
@@ -1,5 +1,14 @@
-use crate::common::Config;
-use driver::client::DatabaseClient;
+use crate::common::{
+    Config,
+    create_test_document,
+};
+use driver::{
+    client::DatabaseClient,
+    database::{
+        DatabaseOptions,
+        Compression,
+    },
+};
 use engine::storage::DB_FILE_EXTENSION;
 
 #[test]
@@ -43,4 +52,27 @@ pub fn get_and_find_database_success() {
     assert_eq!(found_db.unwrap().file_path(), &file_path);
 
     config.close_temp_dirs();
-}
\ No newline at end of file
+}
+#[test]
+pub fn get_database_with_options_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let db_name = "testdb123";
+    let options = DatabaseOptions {
+        compression: Compression::Lz4,
+        ..Default::default()
+    };
+
+    let database = client.get_database_with_options(db_name, &options).unwrap();
+    let collection = database.get_collection("people").unwrap();
+    collection.insert_one(create_test_document()).unwrap();
+    assert_eq!(collection.find_all(None).unwrap().len(), 1);
+
+    let result = client.engine
+        .storage_api()
+        .find_database_by_file_path(database.connection_string());
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().unwrap().compression(), Compression::Lz4);
+
+    config.close_temp_dirs();
+}
@@ -1 +1,3 @@
-mod get_database;
\ No newline at end of file
+mod get_database;
+mod modify_database;
+mod encryption;
\ No newline at end of file
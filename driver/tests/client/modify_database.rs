@@ -0,0 +1,87 @@
+use crate::common::{
+    Config,
+    create_test_document,
+};
+use driver::client::DatabaseClient;
+use engine::storage::DB_FILE_EXTENSION;
+
+#[test]
+pub fn rename_database_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let db_name = "testdb123";
+    let new_db_name = "renameddb";
+    let file_path = config.db_dir.path().join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+    let new_file_path = config.db_dir.path().join(&format!("{}.{}", new_db_name, DB_FILE_EXTENSION));
+    client.get_database(db_name).unwrap();
+
+    client.rename_database(db_name, new_db_name).unwrap();
+    assert!(!file_path.is_file());
+    assert!(new_file_path.is_file());
+
+    let database = client.get_database(new_db_name).unwrap();
+    assert_eq!(database.get_metadata().unwrap().name(), new_db_name);
+    assert!(client.rename_database(db_name, new_db_name).is_err());
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn clone_database_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let collection = database.get_collection("people").unwrap();
+    let document = collection.insert_one(create_test_document()).unwrap();
+
+    let cloned_database = client.clone_database("testdb123", "cloneddb").unwrap();
+    assert_eq!(cloned_database.get_metadata().unwrap().name(), "cloneddb");
+
+    let cloned_collection = cloned_database.get_collection("people").unwrap();
+    let cloned_document = cloned_collection.find_one_by_id(&document.id).unwrap().unwrap();
+    assert_eq!(cloned_document.data.len(), document.data.len());
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn check_and_repair_database_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let collection = database.get_collection("people").unwrap();
+    collection.insert_one(create_test_document()).unwrap();
+
+    let report = client.check_database("testdb123").unwrap();
+    assert!(report.is_ok());
+    assert_eq!(report.document_count, 1);
+
+    let repair = client.repair_database("testdb123", "repaireddb").unwrap();
+    assert!(repair.report.is_ok());
+    let repaired_database = client.get_database("repaireddb").unwrap();
+    assert_eq!(repaired_database.get_collection("people").unwrap().find_all(None).unwrap().len(), 1);
+    assert!(client.check_database("missingdb").is_err());
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn backup_and_restore_database_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let backup_dir = tempfile::tempdir().unwrap();
+    let database = client.get_database("testdb123").unwrap();
+    let collection = database.get_collection("people").unwrap();
+    collection.insert_one(create_test_document()).unwrap();
+
+    let manifest = client.backup_database("testdb123", backup_dir.path()).unwrap();
+    assert_eq!(manifest.databases.len(), 1);
+    assert_eq!(client.verify_backup(backup_dir.path()).unwrap(), manifest);
+
+    collection.insert_one(create_test_document()).unwrap();
+    let restored_database = client.restore_database(backup_dir.path(), "testdb123").unwrap();
+    assert_eq!(restored_database.get_collection("people").unwrap().find_all(None).unwrap().len(), 1);
+
+    backup_dir.close().unwrap();
+    config.close_temp_dirs();
+}
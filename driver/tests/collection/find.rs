@@ -33,7 +33,7 @@ pub fn find_all_documents_success() {
     let found_documents = collection.find_all(None).unwrap();
 
     assert_eq!(found_documents.len(), 1);
-    assert_eq!(found_documents.first().unwrap().id.0, created_document.id.0);
+    assert_eq!(found_documents.first().unwrap().id, created_document.id);
     assert_eq!(found_documents.first().unwrap().data.len(), created_document.data.len());
 
     config.close_temp_dirs();
@@ -97,12 +97,35 @@ pub fn find_one_document_by_id_success() {
     let found_document = collection.find_one_by_id(created_document.id()).unwrap();
 
     let found_document = found_document.unwrap();
-    assert_eq!(found_document.id.0, created_document.id.0);
+    assert_eq!(found_document.id, created_document.id);
     assert_eq!(found_document.data.len(), created_document.data.len());
 
     config.close_temp_dirs();
 }
 
+#[test]
+pub fn find_document_keeps_field_order_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let collection = database.get_collection("collection1").unwrap();
+
+    let field_names = ["title", "year", "author", "isbn", "available"];
+    let mut document = DocumentModel::new();
+    for (index, field_name) in field_names.iter().enumerate() {
+        document.data.insert(field_name.to_string(), DataType::Int32(index as i32));
+    }
+    let created_document = collection.insert_one(document).unwrap();
+    let found_document = collection.find_one_by_id(created_document.id()).unwrap().unwrap();
+
+    let created_keys: Vec<&String> = created_document.data.keys().collect();
+    let found_keys: Vec<&String> = found_document.data.keys().collect();
+    assert_eq!(created_keys, field_names);
+    assert_eq!(found_keys, field_names);
+
+    config.close_temp_dirs();
+}
+
 #[test]
 pub fn find_all_documents_with_options_success() {
     let config = Config::new();
@@ -129,4 +152,27 @@ pub fn find_all_documents_with_options_success() {
     assert_eq!(found_documents.len(), limit);
 
     config.close_temp_dirs();
-}
\ No newline at end of file
+}
+
+#[test]
+pub fn find_documents_strict_types_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let collection = database.get_collection("collection1").unwrap();
+
+    let mut document = DocumentModel::new();
+    document.data.insert("year".to_string(), DataType::Int32(1999));
+    assert!(collection.insert_one(document).is_ok());
+
+    let mut query = DocumentQuery::new();
+    query.data.insert("year".to_string(), DataType::Int64(1999));
+    let found_documents = collection.find_many(&query, None).unwrap();
+    assert_eq!(found_documents.len(), 1);
+
+    let options = DocumentQueryOptions::default().with_strict_types(true);
+    let found_documents = collection.find_many(&query, Some(&options)).unwrap();
+    assert_eq!(found_documents.len(), 0);
+
+    config.close_temp_dirs();
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+use crate::common::{
+    Config,
+    create_test_document,
+};
+use driver::{
+    client::DatabaseClient,
+    collection::CollectionOptions,
+    document::DataType,
+};
+
+#[test]
+pub fn restore_deleted_document_revision_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let options = CollectionOptions {
+        history: true,
+        ..Default::default()
+    };
+    let collection = database.get_collection_with_options("collection1", &options).unwrap();
+
+    let mut document = collection.insert_one(create_test_document()).unwrap();
+    document.data.insert("age".to_string(), DataType::Int32(50));
+    collection.replace_one_by_id(document.id(), document.clone()).unwrap();
+    collection.delete_one_by_id(document.id()).unwrap();
+    assert!(collection.find_one_by_id(document.id()).unwrap().is_none());
+
+    let revisions = collection.find_revisions(document.id()).unwrap();
+    assert_eq!(revisions.len(), 2);
+    assert!(!revisions[0].deleted);
+    assert!(revisions[1].deleted);
+    assert!(matches!(revisions[1].document.data.get("age"), Some(DataType::Int32(50))));
+
+    let restored_document = collection.restore_revision(document.id(), 2).unwrap();
+    assert_eq!(restored_document.id(), document.id());
+    assert!(collection.find_one_by_id(document.id()).unwrap().is_some());
+    assert!(collection.find_revision(document.id(), 1).unwrap().is_some());
+
+    assert_eq!(collection.purge_history(Duration::from_secs(3600)).unwrap(), 0);
+
+    config.close_temp_dirs();
+}
@@ -0,0 +1,146 @@
+use std::sync::{
+    Arc,
+    Mutex,
+};
+use crate::common::{
+    Config,
+    create_test_document,
+};
+use driver::{
+    client::{
+        DatabaseClient,
+        error::{
+            DatabaseClientError,
+            DatabaseClientErrorKind,
+        },
+    },
+    collection::CollectionOptions,
+    document::DataType,
+    hook::{
+        WriteHooks,
+        WriteOperation,
+    },
+    migration::Migrator,
+};
+
+#[test]
+pub fn write_hooks_success() {
+    let config = Config::new();
+    let written = Arc::new(Mutex::new(Vec::new()));
+    let written_by_hook = written.clone();
+    let client_hooks = WriteHooks::new()
+        .add_pre_write(|context, document| {
+            if context.operation != WriteOperation::Delete {
+                document.data.insert("audited".to_string(), DataType::Bool(true));
+            }
+            Ok(())
+        })
+        .add_post_write(move |context, document| {
+            written_by_hook.lock().unwrap().push((context.operation, document.id.clone()));
+        });
+    let client = DatabaseClient::build(config.db_dir.path()).with_hooks(client_hooks);
+    let database = client.get_database("testdb123").unwrap();
+
+    let collection_hooks = WriteHooks::new()
+        .add_pre_write(|_, document| {
+            if let Some(DataType::Text(name)) = document.data.get_mut("first_name") {
+                *name = name.to_lowercase();
+            }
+            if let Some(DataType::Int32(age)) = document.data.get("age") {
+                if *age < 0 {
+                    return Err(DatabaseClientError::new(
+                        DatabaseClientErrorKind::InsertOneDocument,
+                        "Age cannot be negative".to_string()));
+                }
+            }
+            Ok(())
+        });
+    let collection = database
+        .get_collection("people")
+        .unwrap()
+        .with_hooks(collection_hooks);
+
+    let document = collection.insert_one(create_test_document()).unwrap();
+    let found_document = collection.find_one_by_id(document.id()).unwrap().unwrap();
+    assert!(matches!(found_document.data.get("audited"), Some(DataType::Bool(true))));
+    assert!(matches!(found_document.data.get("first_name"), Some(DataType::Text(name)) if name == "john"));
+
+    // Pre-write hook error aborts the write
+    let mut invalid_document = create_test_document();
+    invalid_document.data.insert("age".to_string(), DataType::Int32(-1));
+    assert!(collection.insert_one(invalid_document.clone()).is_err());
+    assert!(collection.replace_one_by_id(document.id(), invalid_document).is_err());
+    assert_eq!(collection.find_all(None).unwrap().len(), 1);
+
+    collection.delete_one_by_id(document.id()).unwrap();
+    assert_eq!(*written.lock().unwrap(), vec![
+        (WriteOperation::Insert, document.id.clone()),
+        (WriteOperation::Delete, document.id.clone()),
+    ]);
+
+    // Client hooks don't run for migration records
+    let migrator = Migrator::new()
+        .add_migration("001_test", |_| Ok(()));
+    migrator.run(&database).unwrap();
+    assert_eq!(written.lock().unwrap().len(), 2);
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn restore_revision_runs_write_hooks() {
+    let config = Config::new();
+    let written = Arc::new(Mutex::new(Vec::new()));
+    let written_by_hook = written.clone();
+    let hooks = WriteHooks::new()
+        .add_pre_write(|_, document| {
+            if let Some(DataType::Int32(age)) = document.data.get("age") {
+                if *age < 0 {
+                    return Err(DatabaseClientError::new(
+                        DatabaseClientErrorKind::ReplaceOneDocument,
+                        "Age cannot be negative".to_string()));
+                }
+            }
+            Ok(())
+        })
+        .add_post_write(move |context, document| {
+            written_by_hook.lock().unwrap().push((context.operation, document.id.clone()));
+        });
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let options = CollectionOptions {
+        history: true,
+        ..Default::default()
+    };
+    let collection = database
+        .get_collection_with_options("people", &options)
+        .unwrap()
+        .with_hooks(hooks);
+
+    let mut document = collection.insert_one(create_test_document()).unwrap();
+    document.data.insert("age".to_string(), DataType::Int32(50));
+    collection.replace_one_by_id(document.id(), document.clone()).unwrap();
+    collection.restore_revision(document.id(), 1).unwrap();
+    collection.delete_one_by_id(document.id()).unwrap();
+    collection.restore_revision(document.id(), 3).unwrap();
+    assert_eq!(*written.lock().unwrap(), vec![
+        (WriteOperation::Insert, document.id.clone()),
+        (WriteOperation::Replace, document.id.clone()),
+        (WriteOperation::Replace, document.id.clone()),
+        (WriteOperation::Delete, document.id.clone()),
+        (WriteOperation::Insert, document.id.clone()),
+    ]);
+
+    // Pre-write hook error aborts the restore
+    let collection_without_hooks = database.get_collection_with_options("people", &options).unwrap();
+    let mut invalid_document = create_test_document();
+    invalid_document.data.insert("age".to_string(), DataType::Int32(-1));
+    collection_without_hooks.replace_one_by_id(document.id(), invalid_document).unwrap();
+    collection_without_hooks.replace_one_by_id(document.id(), create_test_document()).unwrap();
+    assert!(collection.restore_revision(document.id(), 5).is_err());
+    let found_document = collection.find_one_by_id(document.id()).unwrap().unwrap();
+    assert!(matches!(found_document.data.get("age"), Some(DataType::Int32(42))));
+    assert_eq!(written.lock().unwrap().len(), 5);
+
+    config.close_temp_dirs();
+}
@@ -2,8 +2,23 @@ use crate::common::{
     Config,
     create_test_document,
 };
-use driver::client::DatabaseClient;
-use engine::storage::DB_FILE_EXTENSION;
+use driver::{
+    client::DatabaseClient,
+    document::{
+        DocumentModel,
+        DataType,
+        DocumentId,
+    },
+    collection::{
+        CollectionOptions,
+        IdStrategy,
+        CappedLimits,
+    },
+};
+use engine::{
+    storage::DB_FILE_EXTENSION,
+    rust_decimal::Decimal,
+};
 
 #[test]
 pub fn insert_document_success() {
@@ -21,13 +36,117 @@ pub fn insert_document_success() {
 
     let collection = database.get_collection(collection_name).unwrap();
     let document = create_test_document();
-    let document_id = document.id.0;
+    let document_id = document.id.clone();
     let field_count = document.data.len();
     let created_document = collection.insert_one(document).unwrap();
 
-    assert_ne!(created_document.id.0, document_id);
-    assert_eq!(created_document.id.0, 1);
+    assert_ne!(created_document.id, document_id);
+    assert_eq!(created_document.id, DocumentId::Integer(1));
     assert_eq!(created_document.data.len(), field_count);
 
     config.close_temp_dirs();
-}
\ No newline at end of file
+}
+
+#[test]
+pub fn insert_document_decimal128_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let collection = database.get_collection("invoices").unwrap();
+
+    let price = Decimal::from_str_exact("19.90").unwrap();
+    let mut document = DocumentModel::new();
+    document.data.insert("price".to_string(), DataType::Decimal128(price));
+    let created_document = collection.insert_one(document).unwrap();
+    let found_document = collection.find_one_by_id(created_document.id()).unwrap().unwrap();
+
+    match found_document.data.get("price") {
+        Some(DataType::Decimal128(value)) => {
+            assert_eq!(value, &price);
+            assert_eq!(value.to_string(), "19.90");
+        },
+        _ => panic!("Expected Decimal128 field"),
+    }
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn insert_document_uuid_id_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let options = CollectionOptions {
+        id_strategy: IdStrategy::UuidV7,
+        ..Default::default()
+    };
+    let collection = database.get_collection_with_options("collection1", &options).unwrap();
+
+    let first_document = collection.insert_one(create_test_document()).unwrap();
+    let second_document = collection.insert_one(create_test_document()).unwrap();
+
+    match (&first_document.id, &second_document.id) {
+        (DocumentId::Text(first_id), DocumentId::Text(second_id)) => {
+            assert_eq!(first_id.len(), 36);
+            assert!(first_id < second_id);
+        },
+        _ => panic!("Expected text document ids"),
+    }
+
+    let found_document = collection.find_one_by_id(&second_document.id).unwrap();
+    assert_eq!(found_document.unwrap().id, second_document.id);
+
+    let mut document = create_test_document();
+    document.id = DocumentId::from("custom-id");
+    assert!(collection.insert_one(document).is_err());
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn insert_document_client_id_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let options = CollectionOptions {
+        id_strategy: IdStrategy::ClientText,
+        ..Default::default()
+    };
+    let collection = database.get_collection_with_options("users", &options).unwrap();
+
+    let mut document = DocumentModel::with_id(DocumentId::from("john.smith"));
+    document.data.insert("age".to_string(), DataType::Int32(30));
+    let created_document = collection.insert_one(document.clone()).unwrap();
+    assert_eq!(created_document.id, DocumentId::from("john.smith"));
+
+    // Duplicate ids, missing ids and integer ids are rejected
+    assert!(collection.insert_one(document).is_err());
+    assert!(collection.insert_one(create_test_document()).is_err());
+    assert!(collection.insert_one(DocumentModel::with_id(DocumentId::Integer(5))).is_err());
+
+    let found_document = collection.find_one_by_id(&DocumentId::from("john.smith")).unwrap();
+    assert!(found_document.is_some());
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn insert_document_to_capped_collection_evicts_oldest() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let options = CollectionOptions {
+        capped: Some(CappedLimits::new(2, 0)),
+        ..Default::default()
+    };
+    let collection = database.get_collection_with_options("events", &options).unwrap();
+
+    let first_document = collection.insert_one(create_test_document()).unwrap();
+    collection.insert_one(create_test_document()).unwrap();
+    collection.insert_one(create_test_document()).unwrap();
+
+    assert_eq!(collection.find_all(None).unwrap().len(), 2);
+    assert!(collection.find_one_by_id(first_document.id()).unwrap().is_none());
+
+    config.close_temp_dirs();
+}
@@ -1,4 +1,8 @@
 mod insert;
 mod find;
 mod delete;
-mod replace;
\ No newline at end of file
+mod replace;
+mod schema;
+mod hooks;
+mod ttl;
+mod history;
\ No newline at end of file
@@ -30,14 +30,14 @@ pub fn replace_one_document_by_id_success() {
     let created_document = collection.insert_one(document).unwrap();
     let found_document = collection.find_one_by_id(created_document.id()).unwrap().unwrap();
     let original_data = found_document.data.clone();
-    assert_eq!(found_document.id.0, created_document.id.0);
+    assert_eq!(found_document.id, created_document.id);
 
     let mut new_document = DocumentModel::new();
     new_document.data.insert("email".to_string(), DataType::Text("example@example.com".to_string()));
     collection.replace_one_by_id(found_document.id(), new_document).unwrap();
     
     let found_document = collection.find_one_by_id(found_document.id()).unwrap().unwrap();
-    assert_eq!(found_document.id.0, created_document.id.0);
+    assert_eq!(found_document.id, created_document.id);
     assert_eq!(found_document.data.len(), 1);
     assert_ne!(found_document.data.len(), original_data.len());
     assert!(found_document.data.get("email").is_some());
@@ -0,0 +1,101 @@
+use crate::common::{
+    Config,
+    create_test_document,
+};
+use driver::{
+    client::{
+        DatabaseClient,
+        error::SchemaViolationKind,
+    },
+    collection::{
+        CollectionSchema,
+        FieldSchema,
+    },
+    document::{
+        DocumentModel,
+        DataType,
+    },
+};
+
+#[test]
+pub fn set_and_get_schema_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let collection = database.get_collection("collection1").unwrap();
+    assert!(collection.get_schema().unwrap().is_none());
+
+    let schema = CollectionSchema {
+        fields: vec![
+            FieldSchema {
+                name: "age".to_string(),
+                required: true,
+                min: Some(DataType::Int32(0).into()),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    collection.set_schema(Some(schema.clone())).unwrap();
+    assert_eq!(collection.get_schema().unwrap(), Some(schema));
+
+    collection.set_schema(None).unwrap();
+    assert!(collection.get_schema().unwrap().is_none());
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn insert_document_schema_violations_fail() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let collection = database.get_collection("collection1").unwrap();
+
+    let schema = CollectionSchema {
+        fields: vec![
+            FieldSchema {
+                name: "first_name".to_string(),
+                required: true,
+                data_types: vec!["Text".to_string()],
+                ..Default::default()
+            },
+            FieldSchema {
+                name: "age".to_string(),
+                data_types: vec!["Int32".to_string()],
+                max: Some(DataType::Int32(150).into()),
+                ..Default::default()
+            },
+            FieldSchema {
+                name: "status".to_string(),
+                allowed_values: vec![
+                    DataType::Text("active".to_string()).into(),
+                    DataType::Text("inactive".to_string()).into(),
+                ],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    collection.set_schema(Some(schema)).unwrap();
+    assert!(collection.insert_one(create_test_document()).is_ok());
+
+    let mut document = DocumentModel::new();
+    document.data.insert("age".to_string(), DataType::Int32(200));
+    document.data.insert("status".to_string(), DataType::Text("deleted".to_string()));
+    let error = collection.insert_one(document).unwrap_err();
+
+    let violations: Vec<(String, SchemaViolationKind)> = error.validation_error
+        .unwrap()
+        .violations
+        .into_iter()
+        .map(|violation| (violation.field, violation.kind))
+        .collect();
+    assert_eq!(violations, vec![
+        ("first_name".to_string(), SchemaViolationKind::MissingField),
+        ("age".to_string(), SchemaViolationKind::GreaterThanMax),
+        ("status".to_string(), SchemaViolationKind::ValueNotAllowed),
+    ]);
+
+    config.close_temp_dirs();
+}
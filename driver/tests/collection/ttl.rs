@@ -0,0 +1,34 @@
+use crate::common::{
+    Config,
+    create_test_document,
+};
+use driver::{
+    client::DatabaseClient,
+    collection::TtlPolicy,
+    document::{
+        DocumentModel,
+        DataType,
+    },
+};
+
+#[test]
+pub fn expired_documents_are_not_found() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let collection = database.get_collection("collection1").unwrap();
+
+    let mut document = DocumentModel::new();
+    document.data.insert("expires_at".to_string(), DataType::Text("2020-01-01T00:00:00Z".to_string()));
+    let expired_document = collection.insert_one(document).unwrap();
+    collection.insert_one(create_test_document()).unwrap();
+
+    collection.set_ttl(Some(TtlPolicy::new("expires_at", 0))).unwrap();
+    assert_eq!(collection.find_all(None).unwrap().len(), 1);
+    assert!(collection.find_one_by_id(expired_document.id()).unwrap().is_none());
+
+    assert_eq!(database.purge_expired().unwrap(), 1);
+    assert_eq!(database.purge_expired().unwrap(), 0);
+
+    config.close_temp_dirs();
+}
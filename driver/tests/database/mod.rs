@@ -1,2 +1,4 @@
 mod get_metadata;
-mod get_collection;
\ No newline at end of file
+mod get_collection;
+mod modify_collection;
+mod watch;
\ No newline at end of file
@@ -0,0 +1,46 @@
+use crate::common::{
+    Config,
+    create_test_document,
+};
+use driver::client::DatabaseClient;
+
+#[test]
+pub fn rename_collection_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let collection = database.get_collection("people").unwrap();
+    collection.insert_one(create_test_document()).unwrap();
+
+    database.rename_collection("people", "persons").unwrap();
+
+    let collection = database.get_collection("persons").unwrap();
+    assert_eq!(collection.find_all(None).unwrap().len(), 1);
+    assert!(database.rename_collection("people", "persons").is_err());
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn copy_collection_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let target_database = client.get_database("targetdb").unwrap();
+    let collection = database.get_collection("people").unwrap();
+    let document = collection.insert_one(create_test_document()).unwrap();
+
+    database.copy_collection("people", "people_copy", &database).unwrap();
+    database.copy_collection("people", "people", &target_database).unwrap();
+
+    for copied_collection in [
+        database.get_collection("people_copy").unwrap(),
+        target_database.get_collection("people").unwrap(),
+    ] {
+        let copied_document = copied_collection.find_one_by_id(&document.id).unwrap().unwrap();
+        assert_eq!(copied_document.data.len(), document.data.len());
+    }
+    assert!(database.copy_collection("people", "people", &target_database).is_err());
+
+    config.close_temp_dirs();
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+use crate::common::{
+    Config,
+    create_test_document,
+};
+use driver::{
+    client::DatabaseClient,
+    change_stream::{
+        ChangeOperation,
+        ChangeStreamOptions,
+    },
+};
+
+#[test]
+pub fn watch_collection_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let collection = database.get_collection("people").unwrap();
+    let other_collection = database.get_collection("items").unwrap();
+
+    let options = ChangeStreamOptions {
+        include_documents: true,
+        ..Default::default()
+    };
+    let change_stream = collection.watch(&options).unwrap();
+
+    let document = collection.insert_one(create_test_document()).unwrap();
+    other_collection.insert_one(create_test_document()).unwrap();
+    collection.delete_one_by_id(document.id()).unwrap();
+
+    let event = change_stream.recv_timeout(Duration::from_secs(1)).unwrap().unwrap();
+    assert_eq!(event.operation, ChangeOperation::Insert);
+    assert_eq!(event.collection, "people");
+    assert_eq!(&event.document_id, document.id());
+    assert_eq!(event.after.unwrap().data.len(), document.data.len());
+
+    let event = change_stream.try_recv().unwrap().unwrap();
+    assert_eq!(event.operation, ChangeOperation::Delete);
+    assert_eq!(event.sequence, 3);
+    assert!(event.before.is_some());
+    assert!(change_stream.try_recv().is_none());
+
+    config.close_temp_dirs();
+}
@@ -3,4 +3,5 @@
 mod common;
 mod client;
 mod database;
-mod collection;
\ No newline at end of file
+mod collection;
+mod migration;
\ No newline at end of file
@@ -0,0 +1 @@
+mod run;
\ No newline at end of file
@@ -0,0 +1,159 @@
+use std::fs;
+use crate::common::{
+    Config,
+    create_test_document,
+};
+use driver::{
+    client::{
+        DatabaseClient,
+        error::{
+            DatabaseClientError,
+            DatabaseClientErrorKind,
+        },
+    },
+    document::DataType,
+    migration::{
+        Migrator,
+        MIGRATIONS_COLLECTION,
+    },
+};
+
+fn create_test_migrator() -> Migrator {
+    Migrator::new()
+        .add_migration("001_create_people", |database| {
+            database.get_collection("people")?.insert_one(create_test_document())?;
+            Ok(())
+        })
+        .add_migration("002_add_email", |database| {
+            let collection = database.get_collection("people")?;
+            for mut document in collection.find_all(None)? {
+                document.data.insert("email".to_string(), DataType::Text("john@example.com".to_string()));
+                let id = document.id.clone();
+                collection.replace_one_by_id(&id, document)?;
+            }
+            Ok(())
+        })
+}
+
+#[test]
+pub fn run_migrations_success() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let migrator = create_test_migrator();
+
+    let pending_migrations = migrator.pending_migrations(&database).unwrap();
+    assert_eq!(pending_migrations, vec!["001_create_people", "002_add_email"]);
+    assert!(migrator.applied_migrations(&database).unwrap().is_empty());
+
+    let applied_migrations = migrator.run(&database).unwrap();
+    assert_eq!(applied_migrations, pending_migrations);
+
+    let documents = database.get_collection("people").unwrap().find_all(None).unwrap();
+    assert_eq!(documents.len(), 1);
+    assert!(documents[0].data.contains_key("email"));
+
+    let applied_migrations = migrator.applied_migrations(&database).unwrap();
+    assert_eq!(applied_migrations.len(), 2);
+    assert_eq!(applied_migrations[0].name, "001_create_people");
+    assert!(applied_migrations[0].applied_at > 0);
+
+    // Applied migrations are not applied again
+    assert!(migrator.pending_migrations(&database).unwrap().is_empty());
+    assert!(migrator.run(&database).unwrap().is_empty());
+    let documents = database.get_collection("people").unwrap().find_all(None).unwrap();
+    assert_eq!(documents.len(), 1);
+
+    let migrator = create_test_migrator()
+        .add_migration("003_noop", |_| Ok(()));
+    assert_eq!(migrator.pending_migrations(&database).unwrap(), vec!["003_noop"]);
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn dry_run_does_not_modify_database() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let file_path = database.connection_string().to_path_buf();
+    let before = std::fs::read(&file_path).unwrap();
+
+    let pending_migrations = create_test_migrator().pending_migrations(&database).unwrap();
+    assert_eq!(pending_migrations.len(), 2);
+    assert_eq!(std::fs::read(&file_path).unwrap(), before);
+
+    let result = client.engine
+        .storage_api()
+        .find_collection(MIGRATIONS_COLLECTION, &file_path);
+    assert!(result.data.unwrap().is_none());
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn failed_migration_is_rolled_back() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+
+    let migrator = create_test_migrator()
+        .add_migration("003_fail", |_| {
+            Err(DatabaseClientError::new(
+                DatabaseClientErrorKind::Unexpected,
+                "Test failure".to_string()))
+        });
+
+    let error = migrator.run(&database).unwrap_err();
+    assert!(matches!(error.kind, DatabaseClientErrorKind::Migration));
+    assert!(error.message.contains("003_fail"));
+
+    let result = client.engine
+        .storage_api()
+        .find_collection("people", database.connection_string());
+    assert!(result.data.unwrap().is_none());
+    assert!(migrator.applied_migrations(&database).unwrap().is_empty());
+    assert_eq!(migrator.pending_migrations(&database).unwrap().len(), 3);
+
+    // The backup taken before the migrations is removed
+    assert_eq!(fs::read_dir(config.db_dir.path()).unwrap().count(), 1);
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn duplicate_migration_names_fail() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+
+    let migrator = Migrator::new()
+        .add_migration("001_noop", |_| Ok(()))
+        .add_migration("001_noop", |_| Ok(()));
+    assert!(migrator.run(&database).is_err());
+
+    let migrator = Migrator::new()
+        .add_migration("invalid name", |_| Ok(()));
+    assert!(migrator.pending_migrations(&database).is_err());
+
+    config.close_temp_dirs();
+}
+
+#[test]
+pub fn kept_migration_backup_blocks_run() {
+    let config = Config::new();
+    let client = DatabaseClient::build(config.db_dir.path());
+    let database = client.get_database("testdb123").unwrap();
+    let migrator = create_test_migrator();
+
+    let backup_dir_path = config.db_dir.path().join(".testdb123.migration");
+    fs::create_dir(&backup_dir_path).unwrap();
+    fs::write(backup_dir_path.join("kept"), "backup").unwrap();
+
+    let error = migrator.run(&database).unwrap_err();
+    assert!(matches!(error.kind, DatabaseClientErrorKind::Migration));
+    assert!(migrator.applied_migrations(&database).unwrap().is_empty());
+    assert_eq!(fs::read_to_string(backup_dir_path.join("kept")).unwrap(), "backup");
+
+    config.close_temp_dirs();
+}
@@ -24,6 +24,7 @@ use serde::{
     Serialize,
     Deserialize,
 };
+use crate::storage::database::DatabaseOptions;
 
 /// Configuration file name. Config file uses JSON format.
 pub const CONFIG_FILE_NAME: &str = "engine.config.json";
@@ -34,6 +35,9 @@ pub const DB_DIR_DEFAULT_NAME: &str = "databases";
 /// Default logs directory name.
 pub const LOGS_DIR_DEFAULT_NAME: &str = "logs";
 
+/// Default compression of new database files.
+pub const DB_COMPRESSION_DEFAULT: &str = "None";
+
 /// Engine configuration.
 /// 
 /// Configuration file contains configs used by the system.
@@ -43,6 +47,12 @@ pub struct Config {
     pub config_file_path: PathBuf,
     pub db_dir_path: PathBuf,
     pub logs_dir_path: PathBuf,
+    #[serde(default = "default_db_compression")]
+    pub db_compression: String,
+}
+
+fn default_db_compression() -> String {
+    String::from(DB_COMPRESSION_DEFAULT)
 }
 
 impl Config {
@@ -57,6 +67,20 @@ impl Config {
     pub fn logs_dir_path(&self) -> &Path {
         &self.logs_dir_path
     }
+
+    pub fn db_compression(&self) -> &str {
+        &self.db_compression
+    }
+
+    /// Gets the default options of new databases.
+    /// 
+    /// Unknown compression config falls back to no compression.
+    pub fn db_options(&self) -> DatabaseOptions {
+        DatabaseOptions {
+            compression: self.db_compression.parse().unwrap_or_default(),
+            ..Default::default()
+        }
+    }
 }
 
 impl Config {
@@ -71,6 +95,7 @@ impl Config {
             config_file_path: PathBuf::from(config_file_path),
             db_dir_path: PathBuf::from(db_dir_path),
             logs_dir_path: PathBuf::from(logs_dir_path),
+            db_compression: default_db_compression(),
         }
     }
 }
@@ -81,6 +106,7 @@ impl Default for Config {
             config_file_path: PathBuf::from(""),
             db_dir_path: PathBuf::from(""),
             logs_dir_path: PathBuf::from(""),
+            db_compression: default_db_compression(),
         }
     }
 }
@@ -11,6 +11,7 @@ use crate::{
         error::LogError,
     },
 };
+use crate::storage::pb::Compression;
 use super::config_manager::ConfigManager;
 
 /// Result for calls that request configs.
@@ -111,4 +112,18 @@ impl ConfigApi {
             },
         };
     }
+
+    /// Requests `ConfigManager` to set database compression config.
+    pub fn set_db_compression(&self, compression: Compression) -> ConfigRequestResult {
+        match self.config_manager.set_db_compression(compression) {
+            Ok(()) => {
+                let content = format!("Changed database compression configuration to '{}'", compression);
+                return request_success(&self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!("Failed to change database compression configuration: {}", err);
+                return request_fail(err, &self.logger, &content);
+            },
+        };
+    }
 }
@@ -3,6 +3,7 @@ use std::{
     path::Path,
 };
 use super::*;
+use crate::storage::pb::Compression;
 
 /// Configuration manager.
 /// 
@@ -31,11 +32,8 @@ impl ConfigManager {
     /// 
     /// A program restart is required for the changes to take effect.
     pub fn set_db_dir_path(&self, path: &Path) -> io::Result<()> {
-        let new_config = Config::new(
-            &self.config.config_file_path(),
-            path,
-            &self.config.logs_dir_path(),
-        );
+        let mut new_config = self.config.clone();
+        new_config.db_dir_path = PathBuf::from(path);
         save_config(self.config_file_path(), &new_config)?;
 
         Ok(())
@@ -45,11 +43,19 @@ impl ConfigManager {
     /// 
     /// A program restart is required for the changes to take effect.
     pub fn set_logs_dir_path(&self, path: &Path) -> io::Result<()> {
-        let new_config = Config::new(
-            &self.config.config_file_path(),
-            &self.config.db_dir_path(),
-            path,
-        );
+        let mut new_config = self.config.clone();
+        new_config.logs_dir_path = PathBuf::from(path);
+        save_config(self.config_file_path(), &new_config)?;
+
+        Ok(())
+    }
+
+    /// Sets database compression config and saves it to config file.
+    /// 
+    /// A program restart is required for the changes to take effect.
+    pub fn set_db_compression(&self, compression: Compression) -> io::Result<()> {
+        let mut new_config = self.config.clone();
+        new_config.db_compression = compression.to_string();
         save_config(self.config_file_path(), &new_config)?;
 
         Ok(())
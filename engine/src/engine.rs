@@ -43,7 +43,7 @@ impl Engine {
     pub fn build(config: &Config) -> Engine {
         Engine {
             storage_api: StorageApi::build(
-                DatabaseManager::build(config.db_dir_path()),
+                DatabaseManager::build_with_options(config.db_dir_path(), &config.db_options()),
                 Logger::build(config.logs_dir_path()),
             ),
             config_api: ConfigApi::build(
@@ -81,7 +81,7 @@ impl DriverEngine {
     pub fn build(config: &Config) -> DriverEngine {
         DriverEngine {
             storage_api: StorageApi::build(
-                DatabaseManager::build(config.db_dir_path()),
+                DatabaseManager::build_with_options(config.db_dir_path(), &config.db_options()),
                 Logger::build(config.logs_dir_path()),
             ),
             version: ENGINE_VERSION,
@@ -92,7 +92,7 @@ impl DriverEngine {
     pub fn build_logger_disabled(config: &Config) -> DriverEngine {
         DriverEngine {
             storage_api: StorageApi::build(
-                DatabaseManager::build(config.db_dir_path()),
+                DatabaseManager::build_with_options(config.db_dir_path(), &config.db_options()),
                 Logger::build_disabled(config.logs_dir_path()),
             ),
             version: ENGINE_VERSION,
@@ -1,11 +1,17 @@
 // This module contains code to handle input data
 
+use rust_decimal::Decimal;
 use crate::storage::{
     error::ParseError,
     pb::document::{
         DataType,
         data_type,
-    }
+    },
+    document::{
+        encode_decimal128,
+        encode_int128,
+        encode_uint128,
+    },
 };
 
 /// Input data field which is used to create fields to documents.
@@ -78,6 +84,52 @@ impl DocumentInputDataField {
                     Err(_) => return Err(ParseError::Decimal),
                 };
             },
+            "Decimal128" => {
+                match Decimal::from_str_exact(input_data) {
+                    Ok(data) => return Ok(
+                        create_new_data_type_value(data_type::DataType::Decimal128(
+                            encode_decimal128(&data)
+                        ))
+                    ),
+                    Err(_) => return Err(ParseError::Decimal128),
+                };
+            },
+            "UInt32" => {
+                match input_data.parse::<u32>() {
+                    Ok(data) => return Ok(
+                        create_new_data_type_value(data_type::DataType::Uint32(data))
+                    ),
+                    Err(_) => return Err(ParseError::UInt32),
+                };
+            },
+            "UInt64" => {
+                match input_data.parse::<u64>() {
+                    Ok(data) => return Ok(
+                        create_new_data_type_value(data_type::DataType::Uint64(data))
+                    ),
+                    Err(_) => return Err(ParseError::UInt64),
+                };
+            },
+            "Int128" => {
+                match input_data.parse::<i128>() {
+                    Ok(data) => return Ok(
+                        create_new_data_type_value(data_type::DataType::Int128(
+                            encode_int128(data)
+                        ))
+                    ),
+                    Err(_) => return Err(ParseError::Int128),
+                };
+            },
+            "UInt128" => {
+                match input_data.parse::<u128>() {
+                    Ok(data) => return Ok(
+                        create_new_data_type_value(data_type::DataType::Uint128(
+                            encode_uint128(data)
+                        ))
+                    ),
+                    Err(_) => return Err(ParseError::UInt128),
+                };
+            },
             "Bool" => {
                 match input_data.to_lowercase().parse::<bool>() {
                     Ok(data) => return Ok(
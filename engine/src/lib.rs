@@ -9,6 +9,9 @@ mod util;
 
 pub use logging::Logger;
 pub use serde_json;
+pub use rust_decimal;
+pub use indexmap;
+pub use chrono;
 pub use input_data::DocumentInputDataField;
 pub use engine::{
     Engine,
@@ -9,6 +9,22 @@ pub mod document;
 pub mod error;
 pub mod api;
 pub mod db_manager;
+pub mod compare;
+pub mod schema;
+pub mod migration;
+pub mod encryption;
+pub mod file_header;
+pub mod integrity;
+pub mod backup;
+pub mod journal;
+pub mod json;
+pub mod csv;
+pub mod dump;
+pub mod change_stream;
+pub mod ttl;
+pub mod capped;
+pub mod history;
+mod diff;
 
 /// This module contains Protocol Buffers types.
 pub mod pb {
@@ -17,14 +33,17 @@ pub mod pb {
 }
 
 use std::{
-    fs::{self, OpenOptions},
+    borrow::Cow,
+    fs::{self, File},
     io::{self, Write, Cursor},
     path::Path,
 };
-use prost::{
-    Message,
-    EncodeError,
-    DecodeError,
+use prost::Message;
+use crate::storage::{
+    error::DatabaseError,
+    file_header::FileHeader,
+    pb::Compression,
+    change_stream::ChangeOperation,
 };
 
 /// Database file extension.
@@ -41,27 +60,128 @@ fn create_db_dir_if_not_exists(path: &Path) -> io::Result<()> {
 
 /// Serializes database to a buffer.
 /// The buffer can be used to write the database to a file.
-fn serialize_database(database: &pb::Database) -> Result<Vec<u8>, EncodeError> {
+/// 
+/// The buffer starts with the file header.
+/// The payload after it is compressed with the database's compression.
+fn serialize_database(database: &pb::Database) -> io::Result<Vec<u8>> {
     let mut buf = Vec::new();
     buf.reserve(database.encoded_len());
     database.encode(&mut buf)?;
 
-    Ok(buf)
+    let payload = match database.compression() {
+        Compression::None => buf,
+        Compression::Zstd => zstd::encode_all(buf.as_slice(), 0)?,
+        Compression::Lz4 => lz4_flex::compress_prepend_size(&buf),
+    };
+    let header = FileHeader::new(0, database.compression(), &payload);
+
+    Ok(header.to_file_buf(&payload))
 }
 
 /// Deserializes database from a buffer.
-fn deserialize_database(buf: &[u8]) -> Result<pb::Database, DecodeError> {
-    pb::Database::decode(&mut Cursor::new(buf))
+/// 
+/// The file header and the payload checksum are verified.
+/// Buffers without the file header are bare protobuf written before the header was added.
+/// These are upgraded when the database is written the next time.
+/// 
+/// Compressed buffers are decompressed and the database's compression is set.
+/// Encrypted buffers need to be decrypted first.
+fn deserialize_database(buf: &[u8]) -> io::Result<pb::Database> {
+    let (compression, payload) = match FileHeader::read(buf)? {
+        Some((header, payload)) => {
+            if header.is_encrypted() {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    DatabaseError::Locked
+                ));
+            }
+            header.verify(payload)?;
+            (header.compression, payload)
+        },
+        None => (Compression::None, buf),
+    };
+
+    let decompressed_buf = decompress_payload(compression, payload)?;
+    let mut database = pb::Database::decode(&mut Cursor::new(decompressed_buf))?;
+    database.set_compression(compression);
+
+    Ok(database)
+}
+
+/// Decompresses the payload of a database buffer.
+fn decompress_payload(compression: Compression, payload: &[u8]) -> io::Result<Cow<'_, [u8]>> {
+    match compression {
+        Compression::None => Ok(Cow::Borrowed(payload)),
+        Compression::Zstd => Ok(Cow::Owned(zstd::decode_all(payload)?)),
+        Compression::Lz4 => lz4_flex::decompress_size_prepended(payload)
+            .map(Cow::Owned)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+    }
+}
+
+/// Reads database buffer from a file.
+/// 
+/// Encrypted files are decrypted if the database has been unlocked.
+fn read_database_file(file_path: &Path) -> io::Result<Vec<u8>> {
+    let buf = fs::read(file_path)?;
+
+    encryption::decrypt_database_buf(buf, file_path)
 }
 
 /// Writes database buffer to a file.
+/// 
+/// The buffer is encrypted if the database has been unlocked.
+/// The changes are written to the database journal first if the database has one.
+/// Changed documents are sent to change streams as replaced.
 fn write_database_to_file(buf: &[u8], file_path: &Path) -> io::Result<()> {
-    let mut file = OpenOptions::new()
-        .write(true)
-        .truncate(true)
-        .open(file_path)?;
+    write_database_to_file_with_operation(buf, file_path, ChangeOperation::Replace)
+}
 
-    file.write_all(buf)?;
+/// Writes database buffer to a file.
+/// 
+/// Same as `write_database_to_file`, but changed documents
+/// are sent to change streams with the given operation.
+fn write_database_to_file_with_operation(
+    buf: &[u8],
+    file_path: &Path,
+    changed_operation: ChangeOperation,
+) -> io::Result<()>
+{
+    journal::record_write(buf, file_path)?;
+    let changes = change_stream::collect_changes(buf, file_path, changed_operation)?;
+    let encrypted_buf = encryption::encrypt_database_buf(buf, file_path)?;
+    write_file_atomically(encrypted_buf.as_deref().unwrap_or(buf), file_path)?;
+    change_stream::publish_changes(file_path, changes);
+
+    Ok(())
+}
+
+/// Writes a buffer to a file atomically.
+/// 
+/// The buffer is written to a temporary file in the same directory
+/// that is renamed over the file, so the file is never partially written.
+/// The permissions of an existing file are kept.
+pub(crate) fn write_file_atomically(buf: &[u8], file_path: &Path) -> io::Result<()> {
+    let file_name = match file_path.file_name() {
+        Some(file_name) => file_name.to_string_lossy(),
+        None => return Err(io::Error::new(io::ErrorKind::InvalidInput, DatabaseError::NotFound)),
+    };
+    let temp_file_path = file_path.with_file_name(format!(".{}.tmp", file_name));
+    let permissions = fs::metadata(file_path).map(|metadata| metadata.permissions()).ok();
+
+    let result = File::create(&temp_file_path)
+        .and_then(|mut file| {
+            file.write_all(buf)?;
+            if let Some(permissions) = permissions {
+                file.set_permissions(permissions)?;
+            }
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp_file_path, file_path));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_file_path);
+        return Err(e);
+    }
 
     Ok(())
 }
@@ -83,4 +203,67 @@ mod tests {
 
         base_dir.close().unwrap();
     }
+
+    #[test]
+    fn test_write_file_atomically() {
+        let base_dir = tempdir().unwrap();
+        let file_path = base_dir.path().join("test.db");
+
+        write_file_atomically(b"first", &file_path).unwrap();
+        write_file_atomically(b"second", &file_path).unwrap();
+        assert_eq!(fs::read(&file_path).unwrap(), b"second");
+        // The temporary file is renamed over the file
+        assert_eq!(fs::read_dir(base_dir.path()).unwrap().count(), 1);
+
+        base_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_serialize_compressed_database() {
+        let mut database = pb::Database::from("test");
+        database.description = "Text ".repeat(100);
+        let uncompressed_buf = serialize_database(&database).unwrap();
+
+        for compression in [Compression::Zstd, Compression::Lz4] {
+            database.set_compression(compression);
+            let buf = serialize_database(&database).unwrap();
+            let (header, _) = FileHeader::read(&buf).unwrap().unwrap();
+            assert_eq!(header.compression, compression);
+            assert!(buf.len() < uncompressed_buf.len());
+            assert_eq!(deserialize_database(&buf).unwrap(), database);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_uncompressed_database() {
+        let database = pb::Database::from("test");
+        let buf = serialize_database(&database).unwrap();
+        let (header, payload) = FileHeader::read(&buf).unwrap().unwrap();
+        assert_eq!(header.compression, Compression::None);
+        assert!(!header.is_encrypted());
+        assert_eq!(payload, database.encode_to_vec());
+
+        let deserialized_database = deserialize_database(&buf).unwrap();
+        assert_eq!(deserialized_database.compression(), Compression::None);
+        assert_eq!(deserialized_database, database);
+    }
+
+    #[test]
+    fn test_deserialize_corrupted_database() {
+        let database = pb::Database::from("test");
+        let mut buf = serialize_database(&database).unwrap();
+        let last = buf.len() - 1;
+        buf[last] ^= 1;
+
+        let err = deserialize_database(&buf).unwrap_err();
+        let err = err.get_ref().and_then(|e| e.downcast_ref::<DatabaseError>());
+        assert!(matches!(err, Some(DatabaseError::ChecksumMismatch)));
+    }
+
+    #[test]
+    fn test_deserialize_legacy_database() {
+        let database = pb::Database::from("test");
+        let buf = database.encode_to_vec();
+        assert_eq!(deserialize_database(&buf).unwrap(), database);
+    }
 }
@@ -1,14 +1,60 @@
 // Data storage API
 
-use std::path::Path;
+use std::path::{
+    Path,
+    PathBuf,
+};
 use crate::{
     storage::db_manager::DatabaseManager,
     Logger,
     storage::{
         error::DatabaseOperationError,
-        database::DatabaseDto,
-        collection::CollectionDto,
-        document::DocumentDto,
+        database::{
+            DatabaseDto,
+            DatabaseListDto,
+            DatabaseOptions,
+        },
+        encryption::{
+            EncryptionKey,
+            EncryptionOptions,
+        },
+        collection::{
+            CollectionDto,
+            CollectionOptions,
+        },
+        document::{
+            DocumentDto,
+            FindOptions,
+        },
+        pb::document::Id,
+        pb::CollectionSchema,
+        pb::TtlPolicy,
+        history::DocumentRevisionDto,
+        schema::SchemaReportDto,
+        backup::BackupManifest,
+        dump::DumpReportDto,
+        journal::{
+            JournalEntryDto,
+            JournalTarget,
+        },
+        change_stream::{
+            ChangeStream,
+            ChangeStreamOptions,
+        },
+        integrity::{
+            IntegrityReportDto,
+            RepairReportDto,
+        },
+        json::JsonOptions,
+        csv::{
+            CsvExportOptions,
+            CsvImportOptions,
+            CsvImportReportDto,
+        },
+        migration::{
+            FieldMigration,
+            FieldMigrationResultDto,
+        },
     },
     DocumentInputDataField,
     logging::{
@@ -92,30 +138,1118 @@ impl StorageApi {
         logger: Logger,
     ) -> StorageApi
     {
-        StorageApi {
-            db_manager,
-            logger,
+        StorageApi {
+            db_manager,
+            logger,
+        }
+    }
+
+    /// Removes expired documents from a database.
+    /// 
+    /// This is done when a database is opened.
+    /// The number of removed documents is logged if documents were removed.
+    /// Failures are ignored, because sweeps are not requested by the user.
+    pub fn sweep_expired_documents(&self, db_file_path: &Path) {
+        if let Ok(removed_count) = self.db_manager.purge_expired_documents(db_file_path) {
+            if removed_count > 0 {
+                let _ = self.logger.log_event(&format!(
+                    "Purged {} expired documents from database '{}'",
+                    removed_count,
+                    db_file_path.display()
+                ));
+            }
+        }
+    }
+
+    /// Gets the default options of new databases.
+    pub fn default_db_options(&self) -> &DatabaseOptions {
+        self.db_manager.db_options()
+    }
+}
+
+
+impl StorageApi {
+    /// Requests `DatabaseManager` to create a database to database directory.
+    pub fn create_database_to_db_dir(
+        &self,
+        db_name: &str,
+    ) -> StorageRequestResult<()>
+    {
+        return self.create_database_to_db_dir_with_options(db_name, self.default_db_options());
+    }
+
+    /// Requests `DatabaseManager` to create a database to database directory
+    /// using the given options.
+    pub fn create_database_to_db_dir_with_options(
+        &self,
+        db_name: &str,
+        options: &DatabaseOptions,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.create_database_to_db_dir(db_name, options) {
+            Ok(()) => {
+                let content = format!(
+                    "Created database '{}' to database directory with compression '{}'",
+                    db_name,
+                    options.compression
+                );
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to create database '{}' to database directory: {}",
+                    db_name,
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to create a database by file path.
+    pub fn create_database_by_file_path(
+        &self,
+        db_name: &str,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<()>
+    {
+        return self.create_database_by_file_path_with_options(db_name, db_file_path, self.default_db_options());
+    }
+
+    /// Requests `DatabaseManager` to create a database by file path
+    /// using the given options.
+    pub fn create_database_by_file_path_with_options(
+        &self,
+        db_name: &str,
+        db_file_path: &Path,
+        options: &DatabaseOptions,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.create_database_by_file_path(db_name, db_file_path, options) {
+            Ok(()) => {
+                let content = format!(
+                    "Created database '{}' with compression '{}'",
+                    db_file_path.display(),
+                    options.compression
+                );
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to create database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+    
+    /// Requests `DatabaseManager` to delete a database.
+    pub fn delete_database(
+        &self,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.delete_database(db_file_path) {
+            Ok(()) => {
+                let content = format!("Deleted database '{}'", db_file_path.display());
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to delete database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to change the description of a database.
+    pub fn change_database_description(
+        &self,
+        db_file_path: &Path,
+        description: &str,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.change_database_description(db_file_path, description) {
+            Ok(()) => {
+                let content = format!("Changed description of database '{}'", db_file_path.display());
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to change description of database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to rename a database.
+    /// 
+    /// The database file is renamed too.
+    /// 
+    /// Returns the file path of the renamed database.
+    pub fn rename_database(
+        &self,
+        db_file_path: &Path,
+        new_db_name: &str,
+    ) -> StorageRequestResult<PathBuf>
+    {
+        match self.db_manager.rename_database(db_file_path, new_db_name) {
+            Ok(file_path) => {
+                let content = format!(
+                    "Renamed database '{}' to '{}'",
+                    db_file_path.display(),
+                    file_path.display()
+                );
+                return request_success(Some(file_path), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to rename database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to clone a database.
+    /// 
+    /// The clone is created to the same directory as the original database.
+    /// 
+    /// Returns the file path of the cloned database.
+    pub fn clone_database(
+        &self,
+        db_file_path: &Path,
+        new_db_name: &str,
+    ) -> StorageRequestResult<PathBuf>
+    {
+        match self.db_manager.clone_database(db_file_path, new_db_name) {
+            Ok(file_path) => {
+                let content = format!(
+                    "Cloned database '{}' to '{}'",
+                    db_file_path.display(),
+                    file_path.display()
+                );
+                return request_success(Some(file_path), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to clone database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to check the integrity of a database.
+    /// 
+    /// Every collection and document is checked and all problems are reported.
+    pub fn check_database(
+        &self,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<IntegrityReportDto>
+    {
+        match self.db_manager.check_database(db_file_path) {
+            Ok(report) => {
+                let content = format!(
+                    "Checked database '{}': {} problems found",
+                    db_file_path.display(),
+                    report.problems.len()
+                );
+                return request_success(Some(report), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to check database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to repair a database.
+    /// 
+    /// Every collection and document that can be salvaged is copied
+    /// to a new database in the same directory as the original database.
+    pub fn repair_database(
+        &self,
+        db_file_path: &Path,
+        new_db_name: &str,
+    ) -> StorageRequestResult<RepairReportDto>
+    {
+        match self.db_manager.repair_database(db_file_path, new_db_name) {
+            Ok(repair) => {
+                let content = format!(
+                    "Repaired database '{}' to '{}'",
+                    db_file_path.display(),
+                    repair.file_path.display()
+                );
+                return request_success(Some(repair), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to repair database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to back up a database to a backup directory.
+    /// 
+    /// The database can be used while it is backed up.
+    /// Returns the backup manifest.
+    pub fn backup_database(
+        &self,
+        db_file_path: &Path,
+        backup_dir_path: &Path,
+    ) -> StorageRequestResult<BackupManifest>
+    {
+        match self.db_manager.backup_database(db_file_path, backup_dir_path) {
+            Ok(manifest) => {
+                let content = format!(
+                    "Backed up database '{}' to '{}'",
+                    db_file_path.display(),
+                    backup_dir_path.display()
+                );
+                return request_success(Some(manifest), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to back up database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to back up all databases
+    /// in database directory to a backup directory.
+    /// 
+    /// The databases can be used while they are backed up.
+    /// Returns the backup manifest.
+    pub fn backup_all_databases(
+        &self,
+        backup_dir_path: &Path,
+    ) -> StorageRequestResult<BackupManifest>
+    {
+        match self.db_manager.backup_all_databases(backup_dir_path) {
+            Ok(manifest) => {
+                let content = format!(
+                    "Backed up {} databases to '{}'",
+                    manifest.databases.len(),
+                    backup_dir_path.display()
+                );
+                return request_success(Some(manifest), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to back up databases to '{}': {}",
+                    backup_dir_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to verify a backup.
+    /// 
+    /// Returns the backup manifest.
+    pub fn verify_backup(
+        &self,
+        backup_dir_path: &Path,
+    ) -> StorageRequestResult<BackupManifest>
+    {
+        match self.db_manager.verify_backup(backup_dir_path) {
+            Ok(manifest) => {
+                let content = format!("Verified backup '{}'", backup_dir_path.display());
+                return request_success(Some(manifest), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to verify backup '{}': {}",
+                    backup_dir_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to restore a database from a backup.
+    /// 
+    /// The database is restored to database directory. The backup is verified
+    /// before it replaces the database file.
+    /// Returns the file path of the restored database.
+    pub fn restore_database(
+        &self,
+        backup_dir_path: &Path,
+        db_name: &str,
+    ) -> StorageRequestResult<PathBuf>
+    {
+        match self.db_manager.restore_database(backup_dir_path, db_name) {
+            Ok(file_path) => {
+                let content = format!(
+                    "Restored database '{}' from '{}'",
+                    db_name,
+                    backup_dir_path.display()
+                );
+                return request_success(Some(file_path), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to restore database '{}' from '{}': {}",
+                    db_name,
+                    backup_dir_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to restore all databases from a backup.
+    /// 
+    /// The databases are restored to database directory. The whole backup
+    /// is verified before any database file is replaced.
+    /// Returns the file paths of the restored databases.
+    pub fn restore_all_databases(
+        &self,
+        backup_dir_path: &Path,
+    ) -> StorageRequestResult<Vec<PathBuf>>
+    {
+        match self.db_manager.restore_all_databases(backup_dir_path) {
+            Ok(file_paths) => {
+                let content = format!(
+                    "Restored {} databases from '{}'",
+                    file_paths.len(),
+                    backup_dir_path.display()
+                );
+                return request_success(Some(file_paths), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to restore databases from '{}': {}",
+                    backup_dir_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to dump all databases in database directory to a dump file.
+    /// 
+    /// Returns the dumped databases and the number of collections and documents.
+    pub fn dump_all_databases(
+        &self,
+        dump_file_path: &Path,
+    ) -> StorageRequestResult<DumpReportDto>
+    {
+        match self.db_manager.dump_all_databases(dump_file_path) {
+            Ok(report) => {
+                let content = format!(
+                    "Dumped {} databases to '{}'",
+                    report.databases.len(),
+                    dump_file_path.display()
+                );
+                return request_success(Some(report), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to dump databases to '{}': {}",
+                    dump_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to restore all databases in a dump file to database directory.
+    /// 
+    /// Databases in the dump must not exist in database directory.
+    /// 
+    /// Returns the restored databases and the number of collections and documents.
+    pub fn restore_dump(
+        &self,
+        dump_file_path: &Path,
+    ) -> StorageRequestResult<DumpReportDto>
+    {
+        match self.db_manager.restore_dump(dump_file_path) {
+            Ok(report) => {
+                let content = format!(
+                    "Restored {} databases from dump '{}'",
+                    report.databases.len(),
+                    dump_file_path.display()
+                );
+                return request_success(Some(report), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to restore dump '{}': {}",
+                    dump_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to enable the journal of a database.
+    /// 
+    /// The journal records every write to the database,
+    /// so the database can be restored to an earlier point.
+    pub fn enable_journal(
+        &self,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.enable_journal(db_file_path) {
+            Ok(()) => {
+                let content = format!("Enabled journal of database '{}'", db_file_path.display());
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to enable journal of database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to disable the journal of a database.
+    /// 
+    /// The journal file is deleted.
+    pub fn disable_journal(
+        &self,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.disable_journal(db_file_path) {
+            Ok(()) => {
+                let content = format!("Disabled journal of database '{}'", db_file_path.display());
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to disable journal of database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to find all entries in the journal of a database.
+    /// 
+    /// Returns the found journal entries.
+    pub fn find_journal_entries(
+        &self,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<Vec<JournalEntryDto>>
+    {
+        match self.db_manager.find_journal_entries(db_file_path) {
+            Ok(entries) => {
+                let content = format!("Fetched journal of database '{}'", db_file_path.display());
+                return request_success(Some(entries), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to fetch journal of database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to restore a database to a point in its journal.
+    /// 
+    /// Returns the sequence number of the last applied journal entry.
+    pub fn restore_database_from_journal(
+        &self,
+        db_file_path: &Path,
+        target: JournalTarget,
+    ) -> StorageRequestResult<u64>
+    {
+        match self.db_manager.restore_database_from_journal(db_file_path, target) {
+            Ok(sequence) => {
+                let content = format!(
+                    "Restored database '{}' to journal entry {}",
+                    db_file_path.display(),
+                    sequence
+                );
+                return request_success(Some(sequence), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to restore database '{}' from journal: {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to watch a database for changes to its documents.
+    /// 
+    /// Inserted, replaced, updated and deleted documents are sent to the change stream
+    /// after they are written. Dropping the change stream stops watching.
+    pub fn watch_database(
+        &self,
+        db_file_path: &Path,
+        options: &ChangeStreamOptions,
+    ) -> StorageRequestResult<ChangeStream>
+    {
+        match self.db_manager.watch_database(db_file_path, options) {
+            Ok(change_stream) => {
+                let content = format!("Watching changes of database '{}'", db_file_path.display());
+                return request_success(Some(change_stream), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to watch database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to unlock an encrypted database.
+    /// 
+    /// The database can be read and written until it is locked again.
+    pub fn unlock_database(
+        &self,
+        db_file_path: &Path,
+        key: &EncryptionKey,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.unlock_database(db_file_path, key) {
+            Ok(()) => {
+                let content = format!("Unlocked database '{}'", db_file_path.display());
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to unlock database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to lock an encrypted database.
+    pub fn lock_database(
+        &self,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.lock_database(db_file_path) {
+            Ok(()) => {
+                let content = format!("Locked database '{}'", db_file_path.display());
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to lock database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to encrypt a database.
+    /// 
+    /// The database stays unlocked after it is encrypted.
+    pub fn encrypt_database(
+        &self,
+        db_file_path: &Path,
+        options: &EncryptionOptions,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.encrypt_database(db_file_path, options) {
+            Ok(()) => {
+                let content = format!("Encrypted database '{}'", db_file_path.display());
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to encrypt database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to rotate the key of an encrypted database.
+    /// 
+    /// The database must be unlocked with its current key.
+    pub fn rotate_database_key(
+        &self,
+        db_file_path: &Path,
+        options: &EncryptionOptions,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.rotate_database_key(db_file_path, options) {
+            Ok(()) => {
+                let content = format!("Rotated key of database '{}'", db_file_path.display());
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to rotate key of database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to decrypt an encrypted database.
+    /// 
+    /// The database must be unlocked with its key.
+    pub fn decrypt_database(
+        &self,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.decrypt_database(db_file_path) {
+            Ok(()) => {
+                let content = format!("Decrypted database '{}'", db_file_path.display());
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to decrypt database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to create a key file with a random key.
+    pub fn create_key_file(
+        &self,
+        file_path: &Path,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.create_key_file(file_path) {
+            Ok(()) => {
+                let content = format!("Created key file '{}'", file_path.display());
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to create key file '{}': {}",
+                    file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to create a new collection with default options.
+    pub fn create_collection(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<()>
+    {
+        self.create_collection_with_options(
+            collection_name,
+            db_file_path,
+            &CollectionOptions::default()
+        )
+    }
+
+    /// Requests `DatabaseManager` to create a new collection.
+    /// 
+    /// Options specify the collection's settings, such as document id strategy.
+    pub fn create_collection_with_options(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+        options: &CollectionOptions,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.create_collection(collection_name, db_file_path, options) {
+            Ok(()) => {
+                let content = format!(
+                    "Created collection '{}' to database '{}'",
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to create collection '{}' to database '{}': {}",
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to delete a collection.
+    pub fn delete_collection(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.delete_collection(collection_name, db_file_path) {
+            Ok(()) => {
+                let content = format!(
+                    "Deleted collection '{}' from database '{}'",
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to delete collection '{}' from database '{}': {}",
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to rename a collection.
+    pub fn rename_collection(
+        &self,
+        collection_name: &str,
+        new_collection_name: &str,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.rename_collection(collection_name, new_collection_name, db_file_path) {
+            Ok(()) => {
+                let content = format!(
+                    "Renamed collection '{}' to '{}' in database '{}'",
+                    collection_name,
+                    new_collection_name,
+                    db_file_path.display()
+                );
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to rename collection '{}' in database '{}': {}",
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to copy a collection.
+    /// 
+    /// The collection is copied with a new name to the target database,
+    /// which can be the same database.
+    pub fn copy_collection(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+        new_collection_name: &str,
+        target_db_file_path: &Path,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.copy_collection(
+            collection_name,
+            db_file_path,
+            new_collection_name,
+            target_db_file_path
+        ) {
+            Ok(()) => {
+                let content = format!(
+                    "Copied collection '{}' from database '{}' to collection '{}' in database '{}'",
+                    collection_name,
+                    db_file_path.display(),
+                    new_collection_name,
+                    target_db_file_path.display()
+                );
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to copy collection '{}' from database '{}': {}",
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to set a collection's schema.
+    /// 
+    /// `None` removes the schema. The schema is enforced when documents
+    /// are created or replaced. Existing documents are not validated.
+    pub fn set_collection_schema(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+        schema: Option<CollectionSchema>,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.set_collection_schema(collection_name, db_file_path, schema) {
+            Ok(()) => {
+                let content = format!(
+                    "Set schema of collection '{}' in database '{}'",
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to set schema of collection '{}' in database '{}': {}",
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to set a collection's time-to-live policy.
+    /// 
+    /// `None` removes the policy. Expired documents are not returned by reads
+    /// and they are removed when expired documents are purged.
+    pub fn set_collection_ttl(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+        ttl: Option<TtlPolicy>,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.set_collection_ttl(collection_name, db_file_path, ttl) {
+            Ok(()) => {
+                let content = format!(
+                    "Set TTL policy of collection '{}' in database '{}'",
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to set TTL policy of collection '{}' in database '{}': {}",
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to remove expired documents from all collections in a database.
+    /// 
+    /// Returns the number of removed documents.
+    pub fn purge_expired_documents(
+        &self,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<usize>
+    {
+        match self.db_manager.purge_expired_documents(db_file_path) {
+            Ok(removed_count) => {
+                let content = format!(
+                    "Purged {} expired documents from database '{}'",
+                    removed_count,
+                    db_file_path.display()
+                );
+                return request_success(Some(removed_count), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to purge expired documents from database '{}': {}",
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to find a collection's schema.
+    /// 
+    /// Returns the schema or `None` if the collection doesn't have one.
+    pub fn find_collection_schema(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<Option<CollectionSchema>>
+    {
+        match self.db_manager.find_collection_schema(collection_name, db_file_path) {
+            Ok(schema) => {
+                let content = format!(
+                    "Fetched schema of collection '{}' from database '{}'",
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(Some(schema), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to find schema of collection '{}' from database '{}': {}",
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to scan a collection and report its implicit schema.
+    /// 
+    /// Returns the report of each field in the collection's documents.
+    pub fn infer_collection_schema(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+    ) -> StorageRequestResult<SchemaReportDto>
+    {
+        match self.db_manager.infer_collection_schema(collection_name, db_file_path) {
+            Ok(report) => {
+                let content = format!(
+                    "Inferred schema of collection '{}' in database '{}'",
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(Some(report), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to infer schema of collection '{}' in database '{}': {}",
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to migrate a field in every document of a collection.
+    /// 
+    /// Documents that fail the migration are not changed.
+    /// 
+    /// Returns the number of migrated and failed documents.
+    pub fn migrate_field(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        migration: &FieldMigration,
+    ) -> StorageRequestResult<FieldMigrationResultDto>
+    {
+        match self.db_manager.migrate_field(db_file_path, collection_name, migration) {
+            Ok(result) => {
+                let content = format!(
+                    "Migrated collection '{}' in database '{}' ({}): {} converted, {} failed",
+                    collection_name,
+                    db_file_path.display(),
+                    migration,
+                    result.converted_count,
+                    result.failed_count
+                );
+                return request_success(Some(result), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to migrate collection '{}' in database '{}' ({}): {}",
+                    collection_name,
+                    db_file_path.display(),
+                    migration,
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
         }
     }
-}
-
 
-impl StorageApi {
-    /// Requests `DatabaseManager` to create a database to database directory.
-    pub fn create_database_to_db_dir(
+    /// Requests `DatabaseManager` to export all documents in a collection to a JSON file.
+    /// 
+    /// Returns the number of exported documents.
+    pub fn export_collection_to_json(
         &self,
-        db_name: &str,
-    ) -> StorageRequestResult<()>
+        db_file_path: &Path,
+        collection_name: &str,
+        json_file_path: &Path,
+        options: &JsonOptions,
+    ) -> StorageRequestResult<usize>
     {
-        match self.db_manager.create_database_to_db_dir(db_name) {
-            Ok(()) => {
-                let content = format!("Created database '{}' to database directory", db_name);
-                return request_success(None, &self.logger, &content);
+        match self.db_manager.export_collection_to_json(
+            db_file_path,
+            collection_name,
+            json_file_path,
+            options
+        ) {
+            Ok(document_count) => {
+                let content = format!(
+                    "Exported {} documents from collection '{}' in database '{}' to '{}'",
+                    document_count,
+                    collection_name,
+                    db_file_path.display(),
+                    json_file_path.display()
+                );
+                return request_success(Some(document_count), &self.logger, &content);
             },
             Err(err) => {
                 let content = format!(
-                    "Failed to create database '{}' to database directory: {}",
-                    db_name,
+                    "Failed to export collection '{}' in database '{}' to '{}': {}",
+                    collection_name,
+                    db_file_path.display(),
+                    json_file_path.display(),
                     &err.message
                 );
                 return request_fail(err, &self.logger, &content);
@@ -123,43 +1257,72 @@ impl StorageApi {
         }
     }
 
-    /// Requests `DatabaseManager` to create a database by file path.
-    pub fn create_database_by_file_path(
+    /// Requests `DatabaseManager` to export all documents in a database to a JSON file.
+    /// 
+    /// Returns the number of exported documents.
+    pub fn export_database_to_json(
         &self,
-        db_name: &str,
         db_file_path: &Path,
-    ) -> StorageRequestResult<()>
+        json_file_path: &Path,
+        options: &JsonOptions,
+    ) -> StorageRequestResult<usize>
     {
-        match self.db_manager.create_database_by_file_path(db_name, db_file_path) {
-            Ok(()) => {
-                let content = format!("Created database '{}'", db_file_path.display());
-                return request_success(None, &self.logger, &content);
+        match self.db_manager.export_database_to_json(db_file_path, json_file_path, options) {
+            Ok(document_count) => {
+                let content = format!(
+                    "Exported {} documents from database '{}' to '{}'",
+                    document_count,
+                    db_file_path.display(),
+                    json_file_path.display()
+                );
+                return request_success(Some(document_count), &self.logger, &content);
             },
             Err(err) => {
                 let content = format!(
-                    "Failed to create database '{}': {}",
+                    "Failed to export database '{}' to '{}': {}",
                     db_file_path.display(),
+                    json_file_path.display(),
                     &err.message
                 );
                 return request_fail(err, &self.logger, &content);
             },
         }
     }
-    
-    /// Requests `DatabaseManager` to delete a database.
-    pub fn delete_database(
+
+    /// Requests `DatabaseManager` to import documents from a JSON file to a collection.
+    /// 
+    /// Nothing is imported if a document is not valid.
+    /// 
+    /// Returns the number of imported documents.
+    pub fn import_json_to_collection(
         &self,
         db_file_path: &Path,
-    ) -> StorageRequestResult<()>
+        collection_name: &str,
+        json_file_path: &Path,
+        options: &JsonOptions,
+    ) -> StorageRequestResult<usize>
     {
-        match self.db_manager.delete_database(db_file_path) {
-            Ok(()) => {
-                let content = format!("Deleted database '{}'", db_file_path.display());
-                return request_success(None, &self.logger, &content);
+        match self.db_manager.import_json_to_collection(
+            db_file_path,
+            collection_name,
+            json_file_path,
+            options
+        ) {
+            Ok(document_count) => {
+                let content = format!(
+                    "Imported {} documents from '{}' to collection '{}' in database '{}'",
+                    document_count,
+                    json_file_path.display(),
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(Some(document_count), &self.logger, &content);
             },
             Err(err) => {
                 let content = format!(
-                    "Failed to delete database '{}': {}",
+                    "Failed to import '{}' to collection '{}' in database '{}': {}",
+                    json_file_path.display(),
+                    collection_name,
                     db_file_path.display(),
                     &err.message
                 );
@@ -168,21 +1331,33 @@ impl StorageApi {
         }
     }
 
-    /// Requests `DatabaseManager` to change the description of a database.
-    pub fn change_database_description(
+    /// Requests `DatabaseManager` to import documents from a JSON file to a database.
+    /// 
+    /// Collections that don't exist are created.
+    /// Nothing is imported if a document is not valid.
+    /// 
+    /// Returns the number of imported documents.
+    pub fn import_json_to_database(
         &self,
         db_file_path: &Path,
-        description: &str,
-    ) -> StorageRequestResult<()>
+        json_file_path: &Path,
+        options: &JsonOptions,
+    ) -> StorageRequestResult<usize>
     {
-        match self.db_manager.change_database_description(db_file_path, description) {
-            Ok(()) => {
-                let content = format!("Changed description of database '{}'", db_file_path.display());
-                return request_success(None, &self.logger, &content);
+        match self.db_manager.import_json_to_database(db_file_path, json_file_path, options) {
+            Ok(document_count) => {
+                let content = format!(
+                    "Imported {} documents from '{}' to database '{}'",
+                    document_count,
+                    json_file_path.display(),
+                    db_file_path.display()
+                );
+                return request_success(Some(document_count), &self.logger, &content);
             },
             Err(err) => {
                 let content = format!(
-                    "Failed to change description of database '{}': {}",
+                    "Failed to import '{}' to database '{}': {}",
+                    json_file_path.display(),
                     db_file_path.display(),
                     &err.message
                 );
@@ -191,27 +1366,39 @@ impl StorageApi {
         }
     }
 
-    /// Requests `DatabaseManager` to create a new collection.
-    pub fn create_collection(
+    /// Requests `DatabaseManager` to export documents in a collection to a CSV file.
+    /// 
+    /// Returns the number of exported documents.
+    pub fn export_collection_to_csv(
         &self,
-        collection_name: &str,
         db_file_path: &Path,
-    ) -> StorageRequestResult<()>
+        collection_name: &str,
+        csv_file_path: &Path,
+        options: &CsvExportOptions,
+    ) -> StorageRequestResult<usize>
     {
-        match self.db_manager.create_collection(collection_name, db_file_path) {
-            Ok(()) => {
+        match self.db_manager.export_collection_to_csv(
+            db_file_path,
+            collection_name,
+            csv_file_path,
+            options
+        ) {
+            Ok(document_count) => {
                 let content = format!(
-                    "Created collection '{}' to database '{}'",
+                    "Exported {} documents from collection '{}' in database '{}' to '{}'",
+                    document_count,
                     collection_name,
-                    db_file_path.display()
+                    db_file_path.display(),
+                    csv_file_path.display()
                 );
-                return request_success(None, &self.logger, &content);
+                return request_success(Some(document_count), &self.logger, &content);
             },
             Err(err) => {
                 let content = format!(
-                    "Failed to create collection '{}' to database '{}': {}",
+                    "Failed to export collection '{}' in database '{}' to '{}': {}",
                     collection_name,
                     db_file_path.display(),
+                    csv_file_path.display(),
                     &err.message
                 );
                 return request_fail(err, &self.logger, &content);
@@ -219,25 +1406,42 @@ impl StorageApi {
         }
     }
 
-    /// Requests `DatabaseManager` to delete a collection.
-    pub fn delete_collection(
+    /// Requests `DatabaseManager` to import documents from a CSV file to a collection.
+    /// 
+    /// Rows that are not valid are reported and the other rows are imported.
+    /// Nothing is written in a dry run.
+    /// 
+    /// Returns the number of imported documents and the rows that cannot be imported.
+    pub fn import_csv_to_collection(
         &self,
-        collection_name: &str,
         db_file_path: &Path,
-    ) -> StorageRequestResult<()>
+        collection_name: &str,
+        csv_file_path: &Path,
+        options: &CsvImportOptions,
+    ) -> StorageRequestResult<CsvImportReportDto>
     {
-        match self.db_manager.delete_collection(collection_name, db_file_path) {
-            Ok(()) => {
+        match self.db_manager.import_csv_to_collection(
+            db_file_path,
+            collection_name,
+            csv_file_path,
+            options
+        ) {
+            Ok(report) => {
                 let content = format!(
-                    "Deleted collection '{}' from database '{}'",
+                    "{} '{}' to collection '{}' in database '{}': {} imported, {} failed",
+                    if report.dry_run { "Validated import of" } else { "Imported" },
+                    csv_file_path.display(),
                     collection_name,
-                    db_file_path.display()
+                    db_file_path.display(),
+                    report.imported_count,
+                    report.errors.len()
                 );
-                return request_success(None, &self.logger, &content);
+                return request_success(Some(report), &self.logger, &content);
             },
             Err(err) => {
                 let content = format!(
-                    "Failed to delete collection '{}' from database '{}': {}",
+                    "Failed to import '{}' to collection '{}' in database '{}': {}",
+                    csv_file_path.display(),
                     collection_name,
                     db_file_path.display(),
                     &err.message
@@ -249,6 +1453,8 @@ impl StorageApi {
 
     /// Requests `DatabaseManager` to create a new document to a collection.
     /// 
+    /// The document id is generated with the collection's id strategy.
+    /// 
     /// Returns the created document.
     pub fn create_document(
         &self,
@@ -257,7 +1463,27 @@ impl StorageApi {
         data: Vec<DocumentInputDataField>,
     ) -> StorageRequestResult<DocumentDto>
     {
-        match self.db_manager.create_document(db_file_path, collection_name, data) {
+        self.create_document_with_id(db_file_path, collection_name, data, None)
+    }
+
+    /// Requests `DatabaseManager` to create a new document to a collection.
+    /// 
+    /// If the collection has a schema, the error lists every schema violation.
+    /// The document id must be given if the collection uses a client id strategy.
+    /// If `document_id` is `None`, the id is generated.
+    /// 
+    /// Expired documents are removed from the database when the document is created.
+    /// 
+    /// Returns the created document.
+    pub fn create_document_with_id(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        data: Vec<DocumentInputDataField>,
+        document_id: Option<Id>,
+    ) -> StorageRequestResult<DocumentDto>
+    {
+        match self.db_manager.create_document(db_file_path, collection_name, data, document_id) {
             Ok(created_document) => {
                 let content = format!(
                     "Created document with ID '{}' to collection '{}' in database '{}'",
@@ -283,7 +1509,7 @@ impl StorageApi {
     pub fn replace_document(
         &self,
         db_file_path: &Path,
-        document_id: &u64,
+        document_id: &Id,
         collection_name: &str,
         data: Vec<DocumentInputDataField>,
     ) -> StorageRequestResult<()>
@@ -317,7 +1543,7 @@ impl StorageApi {
     pub fn delete_document(
         &self,
         db_file_path: &Path,
-        document_id: &u64,
+        document_id: &Id,
         collection_name: &str,
     ) -> StorageRequestResult<()>
     {
@@ -375,17 +1601,202 @@ impl StorageApi {
         }
     }
 
+    /// Requests `DatabaseManager` to enable or disable a collection's history mode.
+    /// 
+    /// Collections with history mode keep the previous version of each replaced,
+    /// updated or deleted document as a revision.
+    pub fn set_collection_history(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+        enabled: bool,
+    ) -> StorageRequestResult<()>
+    {
+        match self.db_manager.set_collection_history(collection_name, db_file_path, enabled) {
+            Ok(()) => {
+                let content = format!(
+                    "{} history mode of collection '{}' in database '{}'",
+                    if enabled { "Enabled" } else { "Disabled" },
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(None, &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to set history mode of collection '{}' in database '{}': {}",
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to find the revisions of a document in a collection.
+    /// 
+    /// Revisions of deleted documents can be found as well.
+    /// Returns the revisions in the order they were made.
+    pub fn find_document_revisions(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        document_id: &Id,
+    ) -> StorageRequestResult<Vec<DocumentRevisionDto>>
+    {
+        match self.db_manager.find_document_revisions(db_file_path, collection_name, document_id) {
+            Ok(revisions) => {
+                let content = format!(
+                    "Fetched revisions of document with ID '{}' from collection '{}' in database '{}'",
+                    document_id,
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(Some(revisions), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to find revisions of document with ID '{}' from collection '{}' in database '{}': {}",
+                    document_id,
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to find a revision of a document in a collection.
+    /// 
+    /// Returns `None` if the revision doesn't exist.
+    pub fn find_document_revision(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        document_id: &Id,
+        revision: u64,
+    ) -> StorageRequestResult<Option<DocumentRevisionDto>>
+    {
+        match self.db_manager.find_document_revision(db_file_path, collection_name, document_id, revision) {
+            Ok(document_revision) => {
+                let content = format!(
+                    "Fetched revision {} of document with ID '{}' from collection '{}' in database '{}'",
+                    revision,
+                    document_id,
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(Some(document_revision), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to find revision {} of document with ID '{}' from collection '{}' in database '{}': {}",
+                    revision,
+                    document_id,
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to restore a document to a revision.
+    /// 
+    /// Deleted documents are inserted again with the same id.
+    /// Returns the restored document.
+    pub fn restore_document_revision(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        document_id: &Id,
+        revision: u64,
+    ) -> StorageRequestResult<DocumentDto>
+    {
+        match self.db_manager.restore_document_revision(db_file_path, collection_name, document_id, revision) {
+            Ok(document) => {
+                let content = format!(
+                    "Restored document with ID '{}' to revision {} in collection '{}' in database '{}'",
+                    document_id,
+                    revision,
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(Some(document), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to restore document with ID '{}' to revision {} in collection '{}' in database '{}': {}",
+                    document_id,
+                    revision,
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
+    /// Requests `DatabaseManager` to remove revisions that are older than
+    /// the given number of seconds from a collection.
+    /// 
+    /// Returns the number of removed revisions.
+    pub fn purge_document_history(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        older_than_seconds: u64,
+    ) -> StorageRequestResult<usize>
+    {
+        match self.db_manager.purge_document_history(db_file_path, collection_name, older_than_seconds) {
+            Ok(removed_count) => {
+                let content = format!(
+                    "Purged {} revisions older than {} seconds from collection '{}' in database '{}'",
+                    removed_count,
+                    older_than_seconds,
+                    collection_name,
+                    db_file_path.display()
+                );
+                return request_success(Some(removed_count), &self.logger, &content);
+            },
+            Err(err) => {
+                let content = format!(
+                    "Failed to purge revisions from collection '{}' in database '{}': {}",
+                    collection_name,
+                    db_file_path.display(),
+                    &err.message
+                );
+                return request_fail(err, &self.logger, &content);
+            },
+        }
+    }
+
     /// Requests `DatabaseManager` to find all databases from database directory.
     /// 
-    /// Returns the found databases.
+    /// Returns the found databases and the database files that could not be listed.
     pub fn find_all_databases(
         &self,
-    ) -> StorageRequestResult<Vec<DatabaseDto>>
+    ) -> StorageRequestResult<DatabaseListDto>
     {
         match self.db_manager.find_all_databases() {
-            Ok(databases) => {
-                let content = "Fetched all databases from database directory".to_string();
-                return request_success(Some(databases), &self.logger, &content);
+            Ok(database_list) => {
+                let mut content = "Fetched all databases from database directory".to_string();
+                if !database_list.problems.is_empty() {
+                    let problems: Vec<String> = database_list.problems
+                        .iter()
+                        .map(|problem| problem.to_string())
+                        .collect();
+                    content.push_str(&format!(
+                        ". Cannot list {} database files: {}",
+                        problems.len(),
+                        problems.join("; ")
+                    ));
+                }
+                return request_success(Some(database_list), &self.logger, &content);
             },
             Err(err) => {
                 let content = format!(
@@ -538,7 +1949,7 @@ impl StorageApi {
     /// Returns the found document.
     pub fn find_document_by_id(
         &self,
-        document_id: &u64,
+        document_id: &Id,
         db_file_path: &Path,
         collection_name: &str,
     ) -> StorageRequestResult<Option<DocumentDto>>
@@ -574,6 +1985,9 @@ impl StorageApi {
     /// For example, this could return all the documents that contain field
     /// `first_name` with value `John`.
     /// 
+    /// Numeric values match across data types by their numeric value,
+    /// so `Int64` 1999 matches `Int32` 1999.
+    /// 
     /// Returns the found documents.
     pub fn find_documents(
         &self,
@@ -583,7 +1997,28 @@ impl StorageApi {
         limit: Option<usize>
     ) -> StorageRequestResult<Vec<DocumentDto>>
     {
-        match self.db_manager.find_documents(db_file_path, collection_name, query, limit) {
+        let options = FindOptions {
+            limit,
+            ..Default::default()
+        };
+        return self.find_documents_with_options(db_file_path, collection_name, query, &options);
+    }
+
+    /// Requests `DatabaseManager` to find documents in a collection using query and options.
+    /// 
+    /// Set `strict_types` in options to match only document values
+    /// that have the same data type as the query values.
+    /// 
+    /// Returns the found documents.
+    pub fn find_documents_with_options(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        query: &Vec<DocumentInputDataField>,
+        options: &FindOptions,
+    ) -> StorageRequestResult<Vec<DocumentDto>>
+    {
+        match self.db_manager.find_documents(db_file_path, collection_name, query, options) {
             Ok(documents) => {
                 let content = format!(
                     "Fetched {} documents from collection '{}' in database '{}'",
@@ -0,0 +1,332 @@
+// Online database backup and restore.
+// A backup is a directory with copies of database files
+// and a manifest that lists them with their checksums.
+
+use std::{
+    error::Error,
+    ffi::OsStr,
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+use serde::{
+    Serialize,
+    Deserialize,
+};
+use sha2::{
+    Digest,
+    Sha256,
+};
+use crate::storage::{
+    error::{
+        BackupError,
+        DatabaseError,
+    },
+    file_header::FileHeader,
+    encryption,
+    journal,
+    change_stream::{
+        self,
+        ChangeOperation,
+    },
+    deserialize_database,
+    write_file_atomically,
+    DB_FILE_EXTENSION,
+};
+
+/// File name of the backup manifest in a backup directory.
+pub const BACKUP_MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Current backup format version.
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Manifest of a backup.
+/// 
+/// The manifest is written after all database files have been copied,
+/// so a backup directory without a manifest is not complete.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+
+    /// Version of the engine that created the backup.
+    pub engine_version: String,
+
+    /// Time when the backup was created in RFC 3339 format.
+    pub created_at: String,
+
+    pub databases: Vec<BackupEntry>,
+}
+
+impl BackupManifest {
+    /// Finds a database in the backup.
+    pub fn database(&self, db_name: &str) -> Option<&BackupEntry> {
+        self.databases.iter().find(|entry| entry.name == db_name)
+    }
+}
+
+/// Database file in a backup.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupEntry {
+    /// Name of the database.
+    pub name: String,
+
+    /// File name of the database file in the backup directory.
+    pub file_name: String,
+
+    /// Size of the database file in bytes.
+    pub size: u64,
+
+    /// SHA-256 checksum of the database file as a hex string.
+    pub checksum: String,
+}
+
+/// Backs up a database file to a backup directory.
+/// 
+/// The database can be used while it is backed up.
+/// The backup directory is created if it doesn't exist.
+pub fn backup_database_file(
+    file_path: &Path,
+    backup_dir_path: &Path,
+) -> Result<BackupManifest, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    create_backup(&[file_path.to_path_buf()], backup_dir_path)
+}
+
+/// Backs up all database files in a directory to a backup directory.
+/// 
+/// The databases can be used while they are backed up.
+/// The backup directory is created if it doesn't exist.
+pub fn backup_database_dir(
+    dir_path: &Path,
+    backup_dir_path: &Path,
+) -> Result<BackupManifest, Box<dyn Error>>
+{
+    let mut file_paths = Vec::new();
+
+    for entry in fs::read_dir(dir_path)? {
+        let path = entry?.path();
+
+        if path.is_file() && path.extension() == Some(OsStr::new(DB_FILE_EXTENSION)) {
+            file_paths.push(path);
+        }
+    }
+    file_paths.sort();
+
+    create_backup(&file_paths, backup_dir_path)
+}
+
+/// Verifies that a backup is complete and its database files are not corrupted.
+/// 
+/// Returns the backup manifest.
+pub fn verify_backup(backup_dir_path: &Path) -> Result<BackupManifest, Box<dyn Error>> {
+    let manifest = read_backup_manifest(backup_dir_path)?;
+
+    for entry in manifest.databases.iter() {
+        read_backup_entry(backup_dir_path, entry)?;
+    }
+
+    Ok(manifest)
+}
+
+/// Restores a database from a backup to a directory.
+/// 
+/// The backed up database file is verified before it replaces
+/// the database file in the directory. The file is replaced atomically.
+/// 
+/// Returns the file path of the restored database.
+pub fn restore_database_file(
+    backup_dir_path: &Path,
+    db_name: &str,
+    dir_path: &Path,
+) -> Result<PathBuf, Box<dyn Error>>
+{
+    let manifest = read_backup_manifest(backup_dir_path)?;
+    let entry = match manifest.database(db_name) {
+        Some(entry) => entry,
+        None => return Err(Box::new(BackupError::DatabaseNotFound(db_name.to_string()))),
+    };
+    let buf = read_backup_entry(backup_dir_path, entry)?;
+
+    let file_path = dir_path.join(&entry.file_name);
+    replace_database_file(&buf, &file_path)?;
+
+    Ok(file_path)
+}
+
+/// Restores all databases from a backup to a directory.
+/// 
+/// The whole backup is verified before any database file is replaced.
+/// Each file is replaced atomically. Databases in the directory
+/// that are not in the backup are not changed.
+/// 
+/// Returns the file paths of the restored databases.
+pub fn restore_database_dir(
+    backup_dir_path: &Path,
+    dir_path: &Path,
+) -> Result<Vec<PathBuf>, Box<dyn Error>>
+{
+    let manifest = read_backup_manifest(backup_dir_path)?;
+    let mut bufs = Vec::new();
+    for entry in manifest.databases.iter() {
+        bufs.push(read_backup_entry(backup_dir_path, entry)?);
+    }
+
+    let mut file_paths = Vec::new();
+    for (entry, buf) in manifest.databases.iter().zip(bufs) {
+        let file_path = dir_path.join(&entry.file_name);
+        replace_database_file(&buf, &file_path)?;
+        file_paths.push(file_path);
+    }
+
+    Ok(file_paths)
+}
+
+/// Copies database files to a backup directory and writes the manifest.
+fn create_backup(
+    file_paths: &[PathBuf],
+    backup_dir_path: &Path,
+) -> Result<BackupManifest, Box<dyn Error>>
+{
+    let manifest_path = backup_dir_path.join(BACKUP_MANIFEST_FILE_NAME);
+    if manifest_path.is_file() {
+        return Err(Box::new(BackupError::Exists));
+    }
+    fs::create_dir_all(backup_dir_path)?;
+
+    let mut databases = Vec::new();
+    for file_path in file_paths {
+        let (name, file_name) = match (file_path.file_stem(), file_path.file_name()) {
+            (Some(name), Some(file_name)) => (
+                name.to_string_lossy().to_string(),
+                file_name.to_string_lossy().to_string(),
+            ),
+            _ => return Err(Box::new(DatabaseError::NotFound)),
+        };
+        let buf = snapshot_database_file(file_path)?;
+        fs::write(backup_dir_path.join(&file_name), &buf)?;
+
+        databases.push(BackupEntry {
+            name,
+            file_name,
+            size: buf.len() as u64,
+            checksum: checksum(&buf),
+        });
+    }
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        engine_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        databases,
+    };
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+    Ok(manifest)
+}
+
+/// Reads and validates the manifest of a backup.
+fn read_backup_manifest(backup_dir_path: &Path) -> Result<BackupManifest, BackupError> {
+    let manifest_path = backup_dir_path.join(BACKUP_MANIFEST_FILE_NAME);
+    let json = match fs::read_to_string(manifest_path) {
+        Ok(json) => json,
+        Err(_) => return Err(BackupError::ManifestNotFound),
+    };
+    let manifest: BackupManifest = match serde_json::from_str(&json) {
+        Ok(manifest) => manifest,
+        Err(e) => return Err(BackupError::InvalidManifest(e.to_string())),
+    };
+
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(BackupError::UnsupportedFormatVersion(manifest.format_version));
+    }
+    for entry in manifest.databases.iter() {
+        // File names must not point outside the backup directory
+        if entry.file_name != format!("{}.{}", entry.name, DB_FILE_EXTENSION)
+            || Path::new(&entry.file_name).file_name() != Some(OsStr::new(&entry.file_name))
+        {
+            return Err(BackupError::InvalidManifest(
+                format!("Invalid file name '{}'", entry.file_name)
+            ));
+        }
+    }
+
+    Ok(manifest)
+}
+
+/// Reads a database file from a backup and verifies it.
+fn read_backup_entry(
+    backup_dir_path: &Path,
+    entry: &BackupEntry,
+) -> Result<Vec<u8>, Box<dyn Error>>
+{
+    let buf = match fs::read(backup_dir_path.join(&entry.file_name)) {
+        Ok(buf) => buf,
+        Err(_) => return Err(Box::new(BackupError::FileMissing(entry.file_name.clone()))),
+    };
+    if buf.len() as u64 != entry.size || checksum(&buf) != entry.checksum {
+        return Err(Box::new(BackupError::ChecksumMismatch(entry.file_name.clone())));
+    }
+    validate_database_buf(&buf)?;
+
+    Ok(buf)
+}
+
+/// Reads a database file to back it up.
+/// 
+/// Database files are replaced atomically when they are written,
+/// so the file can be read while the database is used.
+/// A corrupted file is not backed up.
+fn snapshot_database_file(file_path: &Path) -> Result<Vec<u8>, Box<dyn Error>> {
+    let buf = fs::read(file_path)?;
+    validate_database_buf(&buf)?;
+
+    Ok(buf)
+}
+
+/// Checks that a buffer is a complete database file.
+/// 
+/// Encrypted databases may be locked, so only their checksum is verified.
+fn validate_database_buf(buf: &[u8]) -> io::Result<()> {
+    match FileHeader::read(buf)? {
+        Some((header, payload)) => header.verify(payload),
+        None => deserialize_database(buf).map(|_| ()),
+    }
+}
+
+/// Replaces a database file atomically.
+/// 
+/// The buffer is written to a temporary file that is renamed over the database file.
+fn replace_database_file(buf: &[u8], file_path: &Path) -> io::Result<()> {
+    let mut changes = Vec::new();
+    if file_path.is_file() {
+        journal::record_write(buf, file_path)?;
+        // Encrypted backups cannot be compared to the database file
+        if !encryption::is_encrypted(buf) {
+            changes = change_stream::collect_changes(buf, file_path, ChangeOperation::Replace)?;
+        }
+    }
+
+    write_file_atomically(buf, file_path)?;
+    change_stream::publish_changes(file_path, changes);
+
+    // The unlocked key of the replaced file may not match the restored file
+    if !encryption::is_encrypted(buf)
+        || encryption::decrypt_database_buf(buf.to_vec(), file_path).is_err()
+    {
+        encryption::take_key(file_path);
+    }
+
+    Ok(())
+}
+
+/// Calculates SHA-256 checksum of a buffer as a hex string.
+fn checksum(buf: &[u8]) -> String {
+    Sha256::digest(buf)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
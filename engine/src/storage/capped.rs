@@ -0,0 +1,164 @@
+// Capped collections.
+// Capped collections have a max document count and/or a max size in bytes.
+// The oldest documents are evicted when an inserted or replaced document exceeds a limit.
+
+use std::fmt;
+use prost::Message;
+use crate::storage::{
+    error::{
+        CollectionError,
+        DocumentError,
+    },
+    pb,
+    pb::document::Id,
+};
+
+// Implements methods for protobuf type
+impl pb::CappedLimits {
+    /// Creates new capped collection limits. 0 means no limit.
+    pub fn new(max_documents: u64, max_size_bytes: u64) -> Self {
+        Self {
+            max_documents,
+            max_size_bytes,
+        }
+    }
+
+    /// Validates capped collection limits by checking its field values.
+    pub fn validate_errors(&self) -> Result<(), CollectionError> {
+        if self.max_documents == 0 && self.max_size_bytes == 0 {
+            return Err(CollectionError::EmptyCappedLimits);
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for pb::CappedLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.max_documents, self.max_size_bytes) {
+            (0, max_size_bytes) => write!(f, "Capped at {} bytes", max_size_bytes),
+            (max_documents, 0) => write!(f, "Capped at {} documents", max_documents),
+            (max_documents, max_size_bytes) => {
+                write!(f, "Capped at {} documents and {} bytes", max_documents, max_size_bytes)
+            },
+        }
+    }
+}
+
+impl pb::Collection {
+    /// Gets the size of the documents in this collection in bytes.
+    pub fn documents_size(&self) -> u64 {
+        self.documents
+            .iter()
+            .map(|document| document.encoded_len() as u64)
+            .sum()
+    }
+
+    /// Checks that a document fits in this collection if it is capped.
+    /// 
+    /// A document that is larger than the max size cannot be inserted,
+    /// because evicting all other documents would not make room for it.
+    pub fn check_capped_document(&self, document: &pb::Document) -> Result<(), DocumentError> {
+        if let Some(capped) = &self.capped {
+            if capped.max_size_bytes > 0 && document.encoded_len() as u64 > capped.max_size_bytes {
+                return Err(DocumentError::ExceedsCappedSize);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evicts the oldest documents until this collection is within its capped limits.
+    /// 
+    /// Documents are stored in insertion order, so the oldest documents are first.
+    /// 
+    /// Returns the evicted documents, so they can be kept as tombstones.
+    pub fn evict_capped_documents(&mut self) -> Vec<pb::Document> {
+        self.evict_oldest_documents(None)
+    }
+
+    /// Evicts the oldest documents until this collection is within its capped limits,
+    /// but never the document with the given id. This is used when a document is replaced.
+    /// 
+    /// Returns the evicted documents, so they can be kept as tombstones.
+    pub fn evict_capped_documents_except(&mut self, document_id: &Id) -> Vec<pb::Document> {
+        self.evict_oldest_documents(Some(document_id))
+    }
+
+    fn evict_oldest_documents(&mut self, kept_document_id: Option<&Id>) -> Vec<pb::Document> {
+        let capped = match &self.capped {
+            Some(capped) => capped,
+            None => return Vec::new(),
+        };
+
+        let mut evicted = vec![false; self.documents.len()];
+        let mut document_count = self.documents.len() as u64;
+        let mut size = self.documents_size();
+        for (index, document) in self.documents.iter().enumerate() {
+            let over_max_documents = capped.max_documents > 0 && document_count > capped.max_documents;
+            let over_max_size = capped.max_size_bytes > 0 && size > capped.max_size_bytes;
+            if !over_max_documents && !over_max_size {
+                break;
+            }
+            if kept_document_id.is_some() && document.id() == kept_document_id {
+                continue;
+            }
+
+            evicted[index] = true;
+            document_count -= 1;
+            size -= document.encoded_len() as u64;
+        }
+
+        let documents = std::mem::take(&mut self.documents);
+        let (evicted_documents, documents): (Vec<_>, Vec<_>) = documents
+            .into_iter()
+            .zip(evicted)
+            .partition(|(_, evicted)| *evicted);
+        self.documents = documents.into_iter().map(|(document, _)| document).collect();
+
+        evicted_documents.into_iter().map(|(document, _)| document).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::pb::document::data_type::DataType;
+
+    fn event(id: u64, text: &str) -> pb::Document {
+        pb::Document::test_document(id, "message", DataType::Text(text.to_string()))
+    }
+
+    fn ids(collection: &pb::Collection) -> Vec<Option<Id>> {
+        collection.documents.iter().map(|document| document.id.clone()).collect()
+    }
+
+    #[test]
+    fn test_evict_capped_documents() {
+        let mut collection = pb::Collection::from("events");
+        collection.documents = (1..=4).map(|id| event(id, "event")).collect();
+        assert!(collection.evict_capped_documents().is_empty());
+
+        collection.capped = Some(pb::CappedLimits::new(3, 0));
+        assert_eq!(collection.evict_capped_documents().len(), 1);
+        assert_eq!(ids(&collection), vec![Some(Id::Integer(2)), Some(Id::Integer(3)), Some(Id::Integer(4))]);
+
+        // Max size fits the last two documents
+        let max_size_bytes = collection.documents[1].encoded_len() as u64 * 2;
+        collection.capped = Some(pb::CappedLimits::new(0, max_size_bytes));
+        assert_eq!(collection.evict_capped_documents().len(), 1);
+        assert_eq!(ids(&collection), vec![Some(Id::Integer(3)), Some(Id::Integer(4))]);
+
+        let large_document = event(5, &"event".repeat(10));
+        assert!(matches!(
+            collection.check_capped_document(&large_document),
+            Err(DocumentError::ExceedsCappedSize)
+        ));
+        assert!(collection.check_capped_document(&event(5, "event")).is_ok());
+
+        // Replaced document is not evicted even if it is the oldest
+        collection.documents[0] = event(3, &"event".repeat(2));
+        assert_eq!(collection.evict_capped_documents_except(&Id::Integer(3)).len(), 1);
+        assert_eq!(ids(&collection), vec![Some(Id::Integer(3))]);
+    }
+}
@@ -0,0 +1,427 @@
+// Change streams for document mutations.
+// Each write to a watched database is compared to the database file before it
+// is written, and the changed documents are sent to subscribers in the same process.
+
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    error::Error,
+    fmt,
+    fs,
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::{
+        mpsc::{
+            self,
+            Receiver,
+            Sender,
+        },
+        Arc,
+        Mutex,
+        MutexGuard,
+        OnceLock,
+        Weak,
+    },
+    time::Duration,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use crate::storage::{
+    pb,
+    pb::document::Id,
+    error::DatabaseError,
+    document::DocumentDto,
+    diff::{
+        self,
+        DocumentChange,
+    },
+    deserialize_database,
+    read_database_file,
+};
+
+/// Number of latest change events that are kept for resuming change streams.
+pub const CHANGE_HISTORY_LEN: usize = 1000;
+
+/// Operation that changed a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOperation {
+    /// Document was inserted.
+    Insert,
+
+    /// Document was replaced with a new one.
+    Replace,
+
+    /// Document was changed by an operation on the whole collection, such as a field migration.
+    Update,
+
+    /// Document was deleted.
+    Delete,
+}
+
+impl fmt::Display for ChangeOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ChangeOperation::Insert => "Insert",
+                ChangeOperation::Replace => "Replace",
+                ChangeOperation::Update => "Update",
+                ChangeOperation::Delete => "Delete",
+            }
+        )
+    }
+}
+
+/// Change to a document in a watched database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeEvent {
+    /// Sequence number of the event in the database. The first event has sequence number 1.
+    pub sequence: u64,
+
+    /// Time when the change was written.
+    pub timestamp: DateTime<Utc>,
+
+    /// Name of the collection that has the document.
+    pub collection: String,
+
+    /// Id of the changed document.
+    pub document_id: Id,
+
+    /// Operation that changed the document.
+    pub operation: ChangeOperation,
+
+    /// Document before the change.
+    /// `None` for inserts or if the change stream doesn't include documents.
+    pub before: Option<DocumentDto>,
+
+    /// Document after the change.
+    /// `None` for deletes or if the change stream doesn't include documents.
+    pub after: Option<DocumentDto>,
+}
+
+/// Options for watching a database.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangeStreamOptions {
+    /// Receives only the changes in this collection. `None` receives the changes in all collections.
+    pub collection: Option<String>,
+
+    /// Whether events have the documents before and after the change.
+    pub include_documents: bool,
+
+    /// Receives the kept events after this sequence number first.
+    /// 
+    /// `None` receives only the changes that are written after the change stream is created.
+    pub resume_after: Option<u64>,
+}
+
+/// Stream of change events of a watched database.
+/// 
+/// The change stream ends when it is dropped.
+pub struct ChangeStream {
+    receiver: Receiver<ChangeEvent>,
+    _handle: Arc<()>,
+}
+
+impl ChangeStream {
+    /// Waits for the next change event.
+    pub fn recv(&self) -> Option<ChangeEvent> {
+        self.receiver.recv().ok()
+    }
+
+    /// Waits for the next change event until the timeout.
+    /// 
+    /// Returns `None` if no change was written before the timeout.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<ChangeEvent> {
+        self.receiver.recv_timeout(timeout).ok()
+    }
+
+    /// Gets the next change event without waiting.
+    /// 
+    /// Returns `None` if there are no received events.
+    pub fn try_recv(&self) -> Option<ChangeEvent> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Gets all received change events without waiting.
+    pub fn try_iter(&self) -> impl Iterator<Item = ChangeEvent> + '_ {
+        self.receiver.try_iter()
+    }
+}
+
+/// Subscriber of a watched database.
+struct Subscriber {
+    sender: Sender<ChangeEvent>,
+    handle: Weak<()>,
+    collection: Option<String>,
+    include_documents: bool,
+}
+
+impl Subscriber {
+    /// Gets the event as this subscriber receives it.
+    /// 
+    /// Returns `None` if the event is in another collection.
+    fn filter(&self, event: &ChangeEvent) -> Option<ChangeEvent> {
+        if let Some(collection) = &self.collection {
+            if *collection != event.collection {
+                return None;
+            }
+        }
+
+        let mut event = event.clone();
+        if !self.include_documents {
+            event.before = None;
+            event.after = None;
+        }
+
+        Some(event)
+    }
+
+    /// Checks if the change stream of this subscriber has not been dropped.
+    fn is_live(&self) -> bool {
+        self.handle.strong_count() > 0
+    }
+}
+
+/// Change events and subscribers of a watched database.
+#[derive(Default)]
+struct DatabaseChanges {
+    last_sequence: u64,
+    history: VecDeque<ChangeEvent>,
+    subscribers: Vec<Subscriber>,
+}
+
+/// Watches a database for changes to its documents.
+/// 
+/// The database is watched while it has change streams that have not been dropped.
+/// Sequence numbers start from 1 when the database is watched again
+/// or the process restarts, and only the latest events are kept for resuming.
+/// 
+/// Returns a change stream that receives the events.
+pub fn watch_database(
+    db_file_path: &Path,
+    options: &ChangeStreamOptions,
+) -> Result<ChangeStream, Box<dyn Error>>
+{
+    if !db_file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let mut registry = registry();
+    let changes = registry.entry(registry_path(db_file_path)).or_default();
+    let (sender, receiver) = mpsc::channel();
+    let handle = Arc::new(());
+    let subscriber = Subscriber {
+        sender,
+        handle: Arc::downgrade(&handle),
+        collection: options.collection.clone(),
+        include_documents: options.include_documents,
+    };
+
+    if let Some(resume_after) = options.resume_after {
+        let first_sequence = changes.history
+            .front()
+            .map(|event| event.sequence)
+            .unwrap_or(changes.last_sequence + 1);
+        if resume_after > changes.last_sequence || resume_after + 1 < first_sequence {
+            return Err(Box::new(DatabaseError::ChangeStreamResumePointNotFound));
+        }
+
+        for event in changes.history.iter().filter(|event| event.sequence > resume_after) {
+            if let Some(event) = subscriber.filter(event) {
+                subscriber.sender.send(event)?;
+            }
+        }
+    }
+    changes.subscribers.push(subscriber);
+
+    Ok(ChangeStream { receiver, _handle: handle })
+}
+
+/// Gets the changes of a database write if the database is watched.
+/// 
+/// This must be called before the database file is written,
+/// because the changes are found by comparing the buffer to the database file.
+/// Documents that are changed but not inserted or deleted get the given operation.
+/// 
+/// If all change streams of the database have been dropped, the database is no longer watched
+/// and its kept events are removed.
+pub(crate) fn collect_changes(
+    buf: &[u8],
+    db_file_path: &Path,
+    changed_operation: ChangeOperation,
+) -> io::Result<Vec<ChangeEvent>>
+{
+    {
+        let mut registry = registry();
+        let path = registry_path(db_file_path);
+        let changes = match registry.get_mut(&path) {
+            Some(changes) => changes,
+            None => return Ok(Vec::new()),
+        };
+
+        changes.subscribers.retain(Subscriber::is_live);
+        if changes.subscribers.is_empty() {
+            registry.remove(&path);
+            return Ok(Vec::new());
+        }
+    }
+
+    let old_database = deserialize_database(&read_database_file(db_file_path)?)?;
+    let new_database = deserialize_database(buf)?;
+
+    Ok(diff_documents(&old_database, &new_database, changed_operation))
+}
+
+/// Sends the changes of a database write to the change streams of the database.
+/// 
+/// This must be called after the database file is written.
+pub(crate) fn publish_changes(db_file_path: &Path, events: Vec<ChangeEvent>) {
+    if events.is_empty() {
+        return;
+    }
+
+    let mut registry = registry();
+    let changes = match registry.get_mut(&registry_path(db_file_path)) {
+        Some(changes) => changes,
+        None => return,
+    };
+    let timestamp = Utc::now();
+
+    for mut event in events {
+        changes.last_sequence += 1;
+        event.sequence = changes.last_sequence;
+        event.timestamp = timestamp;
+
+        // Change streams that are dropped are removed
+        changes.subscribers.retain(|subscriber| match subscriber.filter(&event) {
+            Some(event) => subscriber.sender.send(event).is_ok(),
+            None => true,
+        });
+
+        changes.history.push_back(event);
+        if changes.history.len() > CHANGE_HISTORY_LEN {
+            changes.history.pop_front();
+        }
+    }
+}
+
+/// Gets the change events of watched database files.
+/// 
+/// Events are stored by canonical file path while the database has change streams.
+fn registry() -> MutexGuard<'static, HashMap<PathBuf, DatabaseChanges>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<PathBuf, DatabaseChanges>>> = OnceLock::new();
+
+    REGISTRY
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+fn registry_path(file_path: &Path) -> PathBuf {
+    fs::canonicalize(file_path).unwrap_or_else(|_| PathBuf::from(file_path))
+}
+
+/// Gets the document changes from the old database to the new database.
+/// 
+/// Documents in deleted collections are deleted too.
+fn diff_documents(
+    old: &pb::Database,
+    new: &pb::Database,
+    changed_operation: ChangeOperation,
+) -> Vec<ChangeEvent>
+{
+    let mut events = Vec::new();
+    let mut push = |collection: &str, operation, before: Option<&pb::Document>, after: Option<&pb::Document>| {
+        let document_id = before.or(after).and_then(|document| document.id.clone()).unwrap_or_default();
+
+        events.push(ChangeEvent {
+            sequence: 0,
+            timestamp: DateTime::<Utc>::default(),
+            collection: collection.to_string(),
+            document_id,
+            operation,
+            before: before.map(DocumentDto::from),
+            after: after.map(DocumentDto::from),
+        });
+    };
+
+    for new_collection in new.collections.iter() {
+        let old_documents = old.collections
+            .iter()
+            .find(|collection| collection.name == new_collection.name)
+            .map(|old_collection| old_collection.documents.as_slice())
+            .unwrap_or_default();
+
+        for change in diff::diff_documents(old_documents, &new_collection.documents) {
+            match change {
+                DocumentChange::Insert(document) => {
+                    push(&new_collection.name, ChangeOperation::Insert, None, Some(document));
+                },
+                DocumentChange::Replace { before, after } => {
+                    push(&new_collection.name, changed_operation, Some(before), Some(after));
+                },
+                DocumentChange::Delete(document) => {
+                    push(&new_collection.name, ChangeOperation::Delete, Some(document), None);
+                },
+            }
+        }
+    }
+
+    for old_collection in old.collections.iter() {
+        if !new.collections.iter().any(|collection| collection.name == old_collection.name) {
+            for document in old_collection.documents.iter() {
+                push(&old_collection.name, ChangeOperation::Delete, Some(document), None);
+            }
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::pb::document::data_type::DataType;
+
+    fn person(id: u64, name: &str) -> pb::Document {
+        pb::Document::test_document(id, "name", DataType::Text(name.to_string()))
+    }
+
+    #[test]
+    fn test_diff_documents() {
+        let mut old = pb::Database::from("test");
+        let mut people = pb::Collection::from("people");
+        people.documents = vec![person(1, "John"), person(2, "Jane"), person(3, "Mike")];
+        let mut items = pb::Collection::from("items");
+        items.documents = vec![person(1, "Item")];
+        old.collections = vec![people.clone(), items];
+
+        let mut new = pb::Database::from("test");
+        people.documents = vec![person(1, "John"), person(2, "Janet"), person(4, "Anna")];
+        new.collections = vec![people];
+
+        let events = diff_documents(&old, &new, ChangeOperation::Replace);
+        let changes: Vec<(&str, Id, ChangeOperation)> = events
+            .iter()
+            .map(|event| (event.collection.as_str(), event.document_id.clone(), event.operation))
+            .collect();
+        assert_eq!(changes, vec![
+            ("people", Id::Integer(2), ChangeOperation::Replace),
+            ("people", Id::Integer(4), ChangeOperation::Insert),
+            ("people", Id::Integer(3), ChangeOperation::Delete),
+            ("items", Id::Integer(1), ChangeOperation::Delete),
+        ]);
+
+        assert!(events[0].before.is_some() && events[0].after.is_some());
+        assert!(events[1].before.is_none() && events[1].after.is_some());
+        assert!(events[2].before.is_some() && events[2].after.is_none());
+    }
+}
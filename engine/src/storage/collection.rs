@@ -2,16 +2,24 @@ use std::{
     fs,
     path::Path,
     error::Error,
+    fmt::{self, Display},
+    str::FromStr,
 };
+use uuid::Uuid;
 use crate::{
     storage::{
         error::{
             DatabaseError,
             CollectionError,
+            DocumentError,
         },
         pb,
+        pb::IdStrategy,
+        pb::document::Id,
+        schema::SchemaReportDto,
         serialize_database,
         deserialize_database,
+        read_database_file,
         write_database_to_file,
     },
     util::has_whitespaces,
@@ -35,6 +43,69 @@ impl pb::Collection {
         &self.id_count
     }
 
+    /// Generates an id for a new document using the collection's id strategy.
+    /// 
+    /// Returns `None` if the id strategy requires the client to give the id.
+    pub fn next_document_id(&mut self) -> Option<Id> {
+        match self.id_strategy() {
+            IdStrategy::AutoIncrement => {
+                self.id_count += 1;
+                Some(Id::Integer(self.id_count))
+            },
+            IdStrategy::UuidV4 => Some(Id::Text(Uuid::new_v4().to_string())),
+            IdStrategy::UuidV7 => Some(Id::Text(Uuid::now_v7().to_string())),
+            IdStrategy::ClientInteger | IdStrategy::ClientText => None,
+        }
+    }
+
+    /// Gets the id for a document that is inserted to the collection.
+    /// 
+    /// The id is generated if `document_id` is `None`.
+    /// Otherwise it is checked against the collection's id strategy
+    /// and the ids of existing documents.
+    /// 
+    /// Returns any errors that may occur during the process.
+    pub fn new_document_id(&mut self, document_id: Option<Id>) -> Result<Id, DocumentError> {
+        let document_id = match document_id {
+            Some(document_id) => document_id,
+            None => match self.next_document_id() {
+                Some(document_id) => return Ok(document_id),
+                None => return Err(DocumentError::IdRequired),
+            },
+        };
+
+        match (self.id_strategy(), &document_id) {
+            (IdStrategy::ClientInteger, Id::Integer(id)) => {
+                // Id 0 means the document has no id
+                if *id == 0 {
+                    return Err(DocumentError::InvalidId);
+                }
+            },
+            (IdStrategy::ClientText, Id::Text(id)) => {
+                if id.is_empty() || has_whitespaces(id) {
+                    return Err(DocumentError::InvalidId);
+                }
+            },
+            (IdStrategy::ClientInteger | IdStrategy::ClientText, _) => {
+                return Err(DocumentError::IdTypeMismatch);
+            },
+            _ => return Err(DocumentError::IdNotAllowed),
+        }
+
+        if self.documents.iter().any(|document| document.id() == Some(&document_id)) {
+            return Err(DocumentError::IdExists);
+        }
+
+        // Keep id_count at the largest integer id
+        if let Id::Integer(id) = document_id {
+            if id > self.id_count {
+                self.id_count = id;
+            }
+        }
+
+        Ok(document_id)
+    }
+
     /// Validates collection by checking its field values.
     /// 
     /// Returns any errors that may occur during the process.
@@ -56,16 +127,76 @@ impl From<&str> for pb::Collection {
             name: String::from(name),
             documents: Vec::new(),
             id_count: 0,
+            id_strategy: IdStrategy::AutoIncrement as i32,
+            schema: None,
+            ttl: None,
+            capped: None,
+            history: false,
+            revisions: Vec::new(),
+        }
+    }
+}
+
+impl Display for IdStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                IdStrategy::AutoIncrement => "AutoIncrement",
+                IdStrategy::UuidV4 => "UuidV4",
+                IdStrategy::UuidV7 => "UuidV7",
+                IdStrategy::ClientInteger => "ClientInteger",
+                IdStrategy::ClientText => "ClientText",
+            }
+        )
+    }
+}
+
+impl FromStr for IdStrategy {
+    type Err = CollectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "AutoIncrement" => Ok(IdStrategy::AutoIncrement),
+            "UuidV4" => Ok(IdStrategy::UuidV4),
+            "UuidV7" => Ok(IdStrategy::UuidV7),
+            "ClientInteger" => Ok(IdStrategy::ClientInteger),
+            "ClientText" => Ok(IdStrategy::ClientText),
+            _ => Err(CollectionError::UnknownIdStrategy),
         }
     }
 }
 
+/// Options for creating a collection.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollectionOptions {
+    /// Strategy used to give ids to new documents.
+    pub id_strategy: IdStrategy,
+
+    /// Schema that new documents must follow. `None` allows any documents.
+    pub schema: Option<pb::CollectionSchema>,
+
+    /// Time-to-live policy that expires documents. `None` keeps documents until they are deleted.
+    pub ttl: Option<pb::TtlPolicy>,
+
+    /// Limits that make the collection capped. `None` doesn't limit the collection.
+    pub capped: Option<pb::CappedLimits>,
+
+    /// Whether previous versions of replaced, updated and deleted documents are kept.
+    pub history: bool,
+}
+
 /// Collection data transfer object (DTO).
 /// 
 /// Exposes collection data that clients can use.
 #[derive(Debug, PartialEq)]
 pub struct CollectionDto {
     name: String,
+    id_strategy: IdStrategy,
+    ttl: Option<pb::TtlPolicy>,
+    capped: Option<pb::CappedLimits>,
+    history: bool,
 }
 
 impl CollectionDto {
@@ -73,10 +204,46 @@ impl CollectionDto {
         &self.name
     }
 
+    /// Gets the strategy used to give ids to documents in the collection.
+    pub fn id_strategy(&self) -> IdStrategy {
+        self.id_strategy
+    }
+
+    /// Gets the time-to-live policy of the collection.
+    pub fn ttl(&self) -> Option<&pb::TtlPolicy> {
+        self.ttl.as_ref()
+    }
+
+    /// Gets the limits of the collection if it is capped.
+    pub fn capped(&self) -> Option<&pb::CappedLimits> {
+        self.capped.as_ref()
+    }
+
+    /// Checks if the collection keeps previous versions of documents.
+    pub fn history(&self) -> bool {
+        self.history
+    }
+
     /// Creates a new instance of `CollectionDto`.
     pub fn new(name: &str) -> Self {
         Self {
             name: String::from(name),
+            id_strategy: IdStrategy::default(),
+            ttl: None,
+            capped: None,
+            history: false,
+        }
+    }
+}
+
+impl From<&pb::Collection> for CollectionDto {
+    fn from(collection: &pb::Collection) -> Self {
+        Self {
+            name: collection.name.clone(),
+            id_strategy: collection.id_strategy(),
+            ttl: collection.ttl.clone(),
+            capped: collection.capped.clone(),
+            history: collection.history,
         }
     }
 }
@@ -89,13 +256,14 @@ impl CollectionDto {
 pub fn create_collection_to_database(
     collection_name: &str,
     file_path: &Path,
+    options: &CollectionOptions,
 ) -> Result<(), Box<dyn Error>>
 {
     if !file_path.is_file() {
         return Err(Box::new(DatabaseError::NotFound));
     }
 
-    let mut database = deserialize_database(&fs::read(file_path)?)?;
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
     if let Err(e) = database.validate_errors() {
         return Err(Box::new(e));
     }
@@ -107,10 +275,30 @@ pub fn create_collection_to_database(
         }
     }
 
-    let collection = pb::Collection::from(collection_name.trim());
+    let mut collection = pb::Collection::from(collection_name.trim());
+    collection.set_id_strategy(options.id_strategy);
+    collection.schema = options.schema.clone();
+    collection.ttl = options.ttl.clone();
+    collection.capped = options.capped.clone();
+    collection.history = options.history;
     if let Err(e) = collection.validate_errors() {
         return Err(Box::new(e));
     }
+    if let Some(schema) = &collection.schema {
+        if let Err(e) = schema.validate_errors() {
+            return Err(Box::new(e));
+        }
+    }
+    if let Some(ttl) = &collection.ttl {
+        if let Err(e) = ttl.validate_errors() {
+            return Err(Box::new(e));
+        }
+    }
+    if let Some(capped) = &collection.capped {
+        if let Err(e) = capped.validate_errors() {
+            return Err(Box::new(e));
+        }
+    }
 
     database.collections_mut().push(collection);
     let buf = serialize_database(&database)?;
@@ -133,7 +321,7 @@ pub fn delete_collection_from_database(
         return Err(Box::new(DatabaseError::NotFound));
     }
 
-    let mut database = deserialize_database(&fs::read(file_path)?)?;
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
     if let Err(e) = database.validate_errors() {
         return Err(Box::new(e));
     }
@@ -166,6 +354,208 @@ pub fn delete_collection_from_database(
     }
 }
 
+/// Renames a collection in a database.
+/// 
+/// Renaming a collection to its own name does nothing.
+/// 
+/// Writes the modified database to the database file.
+pub fn rename_collection_in_database(
+    collection_name: &str,
+    new_collection_name: &str,
+    file_path: &Path
+) -> Result<(), Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    if let Err(e) = database.validate_errors() {
+        return Err(Box::new(e));
+    }
+
+    if collection_name == new_collection_name {
+        if !database.collections().iter().any(|collection| collection.name() == collection_name) {
+            return Err(Box::new(CollectionError::NotFound));
+        }
+        return Ok(());
+    }
+    if database.collections().iter().any(|collection| collection.name() == new_collection_name) {
+        return Err(Box::new(CollectionError::Exists));
+    }
+
+    let collection = match database
+        .collections_mut()
+        .iter_mut()
+        .find(|collection| collection.name() == collection_name)
+    {
+        Some(collection) => collection,
+        None => return Err(Box::new(CollectionError::NotFound)),
+    };
+    collection.name = new_collection_name.to_string();
+    if let Err(e) = collection.validate_errors() {
+        return Err(Box::new(e));
+    }
+
+    let buf = serialize_database(&database)?;
+
+    match write_database_to_file(&buf, file_path) {
+        Ok(()) => return Ok(()),
+        Err(e) => return Err(e.into()),
+    }
+}
+
+/// Copies a collection to a database with a new name.
+/// 
+/// The copy has the same documents, document ids, id strategy and schema.
+/// The target database can be the same database.
+/// 
+/// Writes the modified database to the target database file.
+pub fn copy_collection_to_database(
+    collection_name: &str,
+    file_path: &Path,
+    new_collection_name: &str,
+    target_file_path: &Path
+) -> Result<(), Box<dyn Error>>
+{
+    if !file_path.is_file() || !target_file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let database = deserialize_database(&read_database_file(file_path)?)?;
+    if let Err(e) = database.validate_errors() {
+        return Err(Box::new(e));
+    }
+
+    let mut collection = match database
+        .collections()
+        .iter()
+        .find(|collection| collection.name() == collection_name)
+    {
+        Some(collection) => collection.clone(),
+        None => return Err(Box::new(CollectionError::NotFound)),
+    };
+    collection.name = new_collection_name.to_string();
+    if let Err(e) = collection.validate_errors() {
+        return Err(Box::new(e));
+    }
+
+    let mut target_database = if fs::canonicalize(file_path)? == fs::canonicalize(target_file_path)? {
+        database
+    } else {
+        deserialize_database(&read_database_file(target_file_path)?)?
+    };
+    if let Err(e) = target_database.validate_errors() {
+        return Err(Box::new(e));
+    }
+
+    if target_database.collections().iter().any(|collection| collection.name() == new_collection_name) {
+        return Err(Box::new(CollectionError::Exists));
+    }
+
+    target_database.collections_mut().push(collection);
+    let buf = serialize_database(&target_database)?;
+
+    match write_database_to_file(&buf, target_file_path) {
+        Ok(()) => return Ok(()),
+        Err(e) => return Err(e.into()),
+    }
+}
+
+/// Sets a collection's schema. `None` removes the schema.
+/// 
+/// The schema is enforced when documents are created or replaced.
+/// Existing documents are not validated.
+/// 
+/// Writes the modified database to the database file.
+pub fn set_collection_schema_in_database(
+    collection_name: &str,
+    file_path: &Path,
+    schema: Option<pb::CollectionSchema>,
+) -> Result<(), Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    if let Err(e) = database.validate_errors() {
+        return Err(Box::new(e));
+    }
+
+    if let Some(schema) = &schema {
+        if let Err(e) = schema.validate_errors() {
+            return Err(Box::new(e));
+        }
+    }
+
+    for collection in database.collections_mut() {
+        if collection.name() == collection_name {
+            collection.schema = schema;
+            let buf = serialize_database(&database)?;
+
+            match write_database_to_file(&buf, file_path) {
+                Ok(()) => return Ok(()),
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    Err(Box::new(CollectionError::NotFound))
+}
+
+/// Finds a collection's schema.
+/// 
+/// Returns the schema or `None` if the collection doesn't have one.
+pub fn find_collection_schema_in_database(
+    collection_name: &str,
+    file_path: &Path,
+) -> Result<Option<pb::CollectionSchema>, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let database = deserialize_database(&read_database_file(file_path)?)?;
+    if let Err(e) = database.validate_errors() {
+        return Err(Box::new(e));
+    }
+
+    for collection in database.collections.into_iter() {
+        if collection.name() == collection_name {
+            return Ok(collection.schema);
+        }
+    }
+
+    Err(Box::new(CollectionError::NotFound))
+}
+
+/// Scans a collection's documents and reports its implicit schema.
+/// 
+/// Returns the report of each field in the documents.
+pub fn infer_collection_schema_in_database(
+    collection_name: &str,
+    file_path: &Path,
+) -> Result<SchemaReportDto, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let database = deserialize_database(&read_database_file(file_path)?)?;
+    if let Err(e) = database.validate_errors() {
+        return Err(Box::new(e));
+    }
+
+    for collection in database.collections() {
+        if collection.name() == collection_name {
+            return Ok(SchemaReportDto::from_documents(collection.documents()));
+        }
+    }
+
+    Err(Box::new(CollectionError::NotFound))
+}
+
 /// Finds all collections in a database.
 /// 
 /// Returns the found collections.
@@ -177,7 +567,7 @@ pub fn find_all_collections_in_database(
         return Err(Box::new(DatabaseError::NotFound));
     }
 
-    let database = deserialize_database(&fs::read(file_path)?)?;
+    let database = deserialize_database(&read_database_file(file_path)?)?;
     if let Err(e) = database.validate_errors() {
         return Err(Box::new(e));
     }
@@ -192,7 +582,7 @@ pub fn find_all_collections_in_database(
             continue;
         }
 
-        let collection_dto = CollectionDto::new(collection.name());
+        let collection_dto = CollectionDto::from(collection);
         collections.push(collection_dto);
     }
     
@@ -211,7 +601,7 @@ pub fn find_collection_in_database(
         return Err(Box::new(DatabaseError::NotFound));
     }
 
-    let database = deserialize_database(&fs::read(file_path)?)?;
+    let database = deserialize_database(&read_database_file(file_path)?)?;
     if let Err(e) = database.validate_errors() {
         return Err(Box::new(e));
     }
@@ -222,7 +612,7 @@ pub fn find_collection_in_database(
                 return Err(Box::new(e));
             }
 
-            let collection_dto = CollectionDto::new(collection_name);
+            let collection_dto = CollectionDto::from(collection);
             return Ok(Some(collection_dto));
         }
     }
@@ -262,7 +652,11 @@ mod tests {
         let mut file = File::create(&file_path).unwrap();
 
         assert!(file.write_all(&db_buf).is_ok());
-        assert!(create_collection_to_database(collection_name, &file_path).is_ok());
+        assert!(create_collection_to_database(
+            collection_name,
+            &file_path,
+            &CollectionOptions::default()
+        ).is_ok());
         assert_eq!(fs::read(&file_path).unwrap(), expected_db_buf);
 
         drop(file);
@@ -292,6 +686,29 @@ mod tests {
         dir.close().unwrap();
     }
     
+    #[test]
+    fn test_rename_collection_in_database() {
+        let mut db = Database::from("test");
+        db.collections_mut().push(Collection::from("renamed_collection"));
+        let expected_db_buf = serialize_database(&db).unwrap();
+
+        db.collections_mut()[0].name = String::from("test_collection");
+        let db_buf = serialize_database(&db).unwrap();
+
+        let dir = tempdir().unwrap();
+        let file_path = dir
+            .path()
+            .join(&format!("{}.{}", db.name(), DB_FILE_EXTENSION));
+        let mut file = File::create(&file_path).unwrap();
+
+        assert!(file.write_all(&db_buf).is_ok());
+        assert!(rename_collection_in_database("test_collection", "renamed_collection", &file_path).is_ok());
+        assert_eq!(fs::read(&file_path).unwrap(), expected_db_buf);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
     #[test]
     fn test_find_all_collections_in_database() {
         let mut db = Database::from("test");
@@ -335,4 +752,27 @@ mod tests {
         drop(file);
         dir.close().unwrap();
     }
+
+    #[test]
+    fn test_new_document_id() {
+        let mut collection = Collection::from("test_collection");
+        assert_eq!(collection.new_document_id(None).unwrap(), Id::Integer(1));
+        assert!(collection.new_document_id(Some(Id::Integer(5))).is_err());
+
+        collection.set_id_strategy(IdStrategy::ClientInteger);
+        assert_eq!(collection.new_document_id(Some(Id::Integer(5))).unwrap(), Id::Integer(5));
+        assert_eq!(collection.id_count(), &5);
+        assert!(collection.new_document_id(None).is_err());
+        assert!(collection.new_document_id(Some(Id::Integer(0))).is_err());
+        assert!(collection.new_document_id(Some(Id::from("5"))).is_err());
+
+        collection.documents_mut().push(pb::Document {
+            id: Some(Id::Integer(5)),
+            data: Default::default(),
+        });
+        assert!(collection.new_document_id(Some(Id::Integer(5))).is_err());
+
+        collection.set_id_strategy(IdStrategy::UuidV4);
+        assert!(matches!(collection.new_document_id(None).unwrap(), Id::Text(_)));
+    }
 }
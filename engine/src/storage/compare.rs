@@ -0,0 +1,259 @@
+// Document value comparison.
+// Queries, sorting and aggregations use these to compare document field values.
+
+use std::{
+    cmp::Ordering,
+    mem,
+};
+use rust_decimal::{
+    Decimal,
+    prelude::ToPrimitive,
+};
+use crate::storage::{
+    pb::document::data_type::DataType,
+    document::{
+        decode_decimal128,
+        decode_int128,
+        decode_uint128,
+    },
+};
+
+/// Integer value promoted to 128 bits.
+/// 
+/// All integer data types can be represented with this without loss.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Integer {
+    Signed(i128),
+    Unsigned(u128),
+}
+
+impl Integer {
+    /// Promotes an integer document value to 128 bits.
+    /// 
+    /// Returns `None` if the value is not an integer.
+    fn from_value(value: &DataType) -> Option<Integer> {
+        match value {
+            DataType::Int32(value) => Some(Integer::Signed(i128::from(*value))),
+            DataType::Int64(value) => Some(Integer::Signed(i128::from(*value))),
+            DataType::Uint32(value) => Some(Integer::Unsigned(u128::from(*value))),
+            DataType::Uint64(value) => Some(Integer::Unsigned(u128::from(*value))),
+            DataType::Int128(value) => Some(Integer::Signed(decode_int128(value)?)),
+            DataType::Uint128(value) => Some(Integer::Unsigned(decode_uint128(value)?)),
+            _ => None,
+        }
+    }
+
+    fn cmp(&self, other: &Integer) -> Ordering {
+        match (*self, *other) {
+            (Integer::Signed(a), Integer::Signed(b)) => a.cmp(&b),
+            (Integer::Unsigned(a), Integer::Unsigned(b)) => a.cmp(&b),
+            (Integer::Signed(a), Integer::Unsigned(b)) => {
+                // Negative values are always less than unsigned values
+                if a < 0 {
+                    Ordering::Less
+                } else {
+                    (a as u128).cmp(&b)
+                }
+            },
+            (Integer::Unsigned(_), Integer::Signed(_)) => other.cmp(self).reverse(),
+        }
+    }
+}
+
+/// Numeric document value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Number {
+    Integer(Integer),
+    Exact(Decimal),
+    Float(f64),
+}
+
+impl Number {
+    /// Gets the numeric value of a document value.
+    /// 
+    /// Returns `None` if the value is not a number.
+    fn from_value(value: &DataType) -> Option<Number> {
+        if let Some(integer) = Integer::from_value(value) {
+            return Some(Number::Integer(integer));
+        }
+
+        match value {
+            DataType::Decimal(value) => Some(Number::Float(*value)),
+            DataType::Decimal128(value) => Some(Number::Exact(decode_decimal128(value)?)),
+            _ => None,
+        }
+    }
+
+    /// Converts the number to an exact decimal number.
+    /// 
+    /// Floating point numbers are converted using their shortest
+    /// decimal representation, so `Decimal` 0.1 becomes exactly 0.1.
+    /// 
+    /// Returns `None` if the number is outside `Decimal128` range or precision.
+    fn to_decimal(self) -> Option<Decimal> {
+        match self {
+            Number::Integer(Integer::Signed(value)) => {
+                Decimal::try_from_i128_with_scale(value, 0).ok()
+            },
+            Number::Integer(Integer::Unsigned(value)) => {
+                Decimal::try_from_i128_with_scale(i128::try_from(value).ok()?, 0).ok()
+            },
+            Number::Exact(value) => Some(value),
+            Number::Float(value) => {
+                if !value.is_finite() {
+                    return None;
+                }
+                Decimal::from_str_exact(&value.to_string()).ok()
+            },
+        }
+    }
+
+    /// Converts the number to a 64-bit floating point number.
+    fn to_f64(self) -> Option<f64> {
+        match self {
+            Number::Integer(Integer::Signed(value)) => Some(value as f64),
+            Number::Integer(Integer::Unsigned(value)) => Some(value as f64),
+            Number::Exact(value) => value.to_f64(),
+            Number::Float(value) => Some(value),
+        }
+    }
+
+    fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Integer(a), Number::Integer(b)) => Some(a.cmp(b)),
+            (Number::Float(a), Number::Float(b)) => a.partial_cmp(b),
+            _ => match (self.to_decimal(), other.to_decimal()) {
+                (Some(a), Some(b)) => Some(a.cmp(&b)),
+                // Fall back to floating point if a value doesn't fit in a decimal
+                _ => self.to_f64()?.partial_cmp(&other.to_f64()?),
+            },
+        }
+    }
+}
+
+/// Compares two document values.
+///
+/// Values are compared by their numeric or textual value,
+/// not by their storage representation.
+/// For example, `Decimal128` values 1.1 and 1.10 are equal.
+/// 
+/// Numbers of different data types are promoted before comparing:
+/// - Integers are promoted to 128 bits, so `Int32` 5 is equal to `UInt64` 5.
+/// - If either value is `Decimal` or `Decimal128`, both are promoted to
+///   an exact decimal number, so `Int32` 3 is equal to `Decimal` 3.0.
+/// - Values that don't fit in an exact decimal number
+///   are compared as 64-bit floating point numbers.
+///
+/// Returns `None` if the values cannot be compared.
+pub fn compare_values(a: &DataType, b: &DataType) -> Option<Ordering> {
+    if let (Some(a), Some(b)) = (Number::from_value(a), Number::from_value(b)) {
+        return a.partial_cmp(&b);
+    }
+
+    match (a, b) {
+        (DataType::Bool(a), DataType::Bool(b)) => Some(a.cmp(b)),
+        (DataType::Text(a), DataType::Text(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// Compares two document values only if they have the same data type.
+/// 
+/// Returns `None` if the data types are different or the values cannot be compared.
+pub fn compare_values_strict(a: &DataType, b: &DataType) -> Option<Ordering> {
+    if mem::discriminant(a) != mem::discriminant(b) {
+        return None;
+    }
+
+    compare_values(a, b)
+}
+
+/// Checks if two document values are equal.
+/// 
+/// If `strict_types` is true, values with different data types are never equal.
+pub fn values_equal(a: &DataType, b: &DataType, strict_types: bool) -> bool {
+    let ordering = if strict_types {
+        compare_values_strict(a, b)
+    } else {
+        compare_values(a, b)
+    };
+
+    ordering == Some(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::document::{
+        encode_decimal128,
+        encode_int128,
+        encode_uint128,
+    };
+
+    fn decimal128(value: &str) -> DataType {
+        DataType::Decimal128(encode_decimal128(&Decimal::from_str_exact(value).unwrap()))
+    }
+
+    #[test]
+    fn test_compare_decimal128_values() {
+        let sum = Decimal::from_str_exact("0.1").unwrap() + Decimal::from_str_exact("0.2").unwrap();
+        let sum = DataType::Decimal128(encode_decimal128(&sum));
+
+        assert!(values_equal(&sum, &decimal128("0.3"), false));
+        assert!(values_equal(&decimal128("1.1"), &decimal128("1.10"), true));
+        assert_eq!(compare_values(&decimal128("-2.5"), &decimal128("1")), Some(Ordering::Less));
+        assert_eq!(compare_values(&decimal128("19.99"), &decimal128("9.99")), Some(Ordering::Greater));
+    }
+
+    #[test]
+    fn test_compare_integer_widths() {
+        assert!(values_equal(&DataType::Int32(1999), &DataType::Int64(1999), false));
+        assert!(values_equal(&DataType::Uint32(7), &DataType::Int128(encode_int128(7)), false));
+        assert_eq!(
+            compare_values(&DataType::Int64(-1), &DataType::Uint64(0)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_values(&DataType::Uint128(encode_uint128(u128::MAX)), &DataType::Int128(encode_int128(i128::MAX))),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_values(&DataType::Uint64(u64::MAX), &DataType::Int64(i64::MAX)),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn test_compare_numbers_across_types() {
+        assert!(values_equal(&DataType::Int32(3), &DataType::Decimal(3.0), false));
+        assert!(values_equal(&DataType::Int64(42), &decimal128("42.00"), false));
+        assert!(values_equal(&DataType::Decimal(0.1), &decimal128("0.1"), false));
+        assert!(!values_equal(&DataType::Decimal(0.1 + 0.2), &decimal128("0.3"), false));
+        assert_eq!(
+            compare_values(&DataType::Int32(2), &DataType::Decimal(2.5)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_values(&DataType::Uint128(encode_uint128(u128::MAX)), &decimal128("1.5")),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_values(&DataType::Decimal(f64::INFINITY), &DataType::Int64(i64::MAX)),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn test_compare_values_strict() {
+        assert!(!values_equal(&DataType::Int32(1999), &DataType::Int64(1999), true));
+        assert!(values_equal(&DataType::Int64(1999), &DataType::Int64(1999), true));
+        assert_eq!(compare_values_strict(&DataType::Int32(3), &DataType::Decimal(3.0)), None);
+    }
+
+    #[test]
+    fn test_compare_different_types() {
+        assert_eq!(compare_values(&DataType::Int32(1), &DataType::Text("1".to_string())), None);
+        assert_eq!(compare_values(&DataType::Bool(true), &DataType::Int32(1)), None);
+        assert!(!values_equal(&DataType::Decimal(f64::NAN), &DataType::Decimal(f64::NAN), false));
+    }
+}
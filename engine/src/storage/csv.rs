@@ -0,0 +1,372 @@
+// CSV export and import for collections.
+// Values are written with the same text format that is used for input data,
+// so exported values can be imported again with their data types.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    path::Path,
+};
+use csv::{
+    ReaderBuilder,
+    StringRecord,
+    Writer,
+};
+use indexmap::{
+    IndexMap,
+    IndexSet,
+};
+use crate::{
+    DocumentInputDataField,
+    storage::{
+        error::{
+            DatabaseError,
+            CollectionError,
+            CsvError,
+        },
+        pb,
+        pb::document::Id,
+        pb::document::DataType,
+        pb::IdStrategy,
+        document::{
+            DATA_TYPE_NAMES,
+            insert_imported_document,
+            value_to_string,
+        },
+        serialize_database,
+        deserialize_database,
+        read_database_file,
+        write_database_to_file,
+    },
+};
+
+/// Name of the document id column.
+pub const CSV_ID_COLUMN: &str = "_id";
+
+/// Data type of columns that have no data type in the import options.
+pub const CSV_DEFAULT_DATA_TYPE: &str = "Text";
+
+/// Options for CSV export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvExportOptions {
+    /// Fields to export in column order. Column `_id` is the document id.
+    /// 
+    /// Empty exports the document id and all fields in the order they are first found.
+    pub fields: Vec<String>,
+
+    /// Whether the first row has the field names.
+    pub header: bool,
+}
+
+impl Default for CsvExportOptions {
+    fn default() -> Self {
+        Self {
+            fields: Vec::new(),
+            header: true,
+        }
+    }
+}
+
+/// Column of an imported CSV file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvColumn {
+    /// Field name of the column. Column `_id` is the document id.
+    pub name: String,
+
+    /// Data type name of the column values, e.g. `Int32`.
+    pub data_type: String,
+}
+
+impl CsvColumn {
+    /// Creates a new CSV column.
+    pub fn new(name: &str, data_type: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+        }
+    }
+}
+
+/// Options for CSV import.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvImportOptions {
+    /// Data types of the columns.
+    /// 
+    /// If the file has a header row, columns are matched by name
+    /// and columns that are not listed are imported as `Text`.
+    /// Otherwise the columns are given in file order.
+    pub columns: Vec<CsvColumn>,
+
+    /// Whether the first row has the field names.
+    pub header: bool,
+
+    /// Validates the rows without writing the database.
+    pub dry_run: bool,
+}
+
+impl Default for CsvImportOptions {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            header: true,
+            dry_run: false,
+        }
+    }
+}
+
+/// Row in a CSV file that cannot be imported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRowError {
+    /// Line number of the row in the CSV file.
+    pub row: u64,
+
+    /// Reason why the row cannot be imported.
+    pub message: String,
+}
+
+impl fmt::Display for CsvRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Row {}: {}", self.row, self.message)
+    }
+}
+
+/// Result of a CSV import.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsvImportReportDto {
+    /// Number of imported documents.
+    /// In a dry run, the number of documents that would be imported.
+    pub imported_count: usize,
+
+    /// Rows that cannot be imported.
+    pub errors: Vec<CsvRowError>,
+
+    /// Whether the import was a dry run and nothing was written.
+    pub dry_run: bool,
+}
+
+/// Exports documents in a collection to a CSV file.
+/// 
+/// Values are written in the same format as input data.
+/// Fields that a document doesn't have are empty.
+/// The file is created or overwritten.
+/// 
+/// Returns the number of exported documents.
+pub fn export_collection_to_csv_file(
+    file_path: &Path,
+    collection_name: &str,
+    csv_file_path: &Path,
+    options: &CsvExportOptions,
+) -> Result<usize, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    let database = deserialize_database(&read_database_file(file_path)?)?;
+
+    let collection = match database
+        .collections
+        .iter()
+        .find(|collection| collection.name == collection_name)
+    {
+        Some(collection) => collection,
+        None => return Err(Box::new(CollectionError::NotFound)),
+    };
+    let fields = if options.fields.is_empty() {
+        collection_fields(collection)
+    } else {
+        options.fields.clone()
+    };
+
+    let mut writer = Writer::from_path(csv_file_path)?;
+    if options.header {
+        writer.write_record(&fields)?;
+    }
+    for document in collection.documents.iter() {
+        let data: HashMap<&str, &DataType> = document.fields().collect();
+        let record = fields.iter().map(|field| {
+            if field == CSV_ID_COLUMN {
+                return document.id.clone().unwrap_or_default().to_string();
+            }
+            data.get(field.as_str())
+                .and_then(|value| value.data_type.as_ref())
+                .and_then(value_to_string)
+                .unwrap_or_default()
+        });
+        writer.write_record(record)?;
+    }
+    writer.flush()?;
+
+    Ok(collection.documents.len())
+}
+
+/// Imports documents from a CSV file to a collection.
+/// 
+/// Each row is a document. Values are parsed to the data types of their columns
+/// and empty values are skipped. Document ids in column `_id` are kept if the collection
+/// has a client id strategy. Otherwise new ids are generated.
+/// 
+/// Rows that are not valid are reported and the other rows are imported.
+/// In a dry run, the rows are validated but the database is not written.
+pub fn import_csv_file_to_collection(
+    file_path: &Path,
+    collection_name: &str,
+    csv_file_path: &Path,
+    options: &CsvImportOptions,
+) -> Result<CsvImportReportDto, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    for column in options.columns.iter() {
+        if !DATA_TYPE_NAMES.contains(&column.data_type.as_str()) {
+            return Err(Box::new(CsvError::UnknownDataType(column.data_type.clone())));
+        }
+    }
+
+    let mut reader = ReaderBuilder::new()
+        .has_headers(options.header)
+        .flexible(true)
+        .from_path(csv_file_path)?;
+    let columns = if options.header {
+        let header = reader.headers().map_err(|e| CsvError::Parse(e.to_string()))?;
+        for column in options.columns.iter() {
+            if !header.iter().any(|name| name == column.name) {
+                return Err(Box::new(CsvError::ColumnNotFound(column.name.clone())));
+            }
+        }
+        header
+            .iter()
+            .map(|name| match options.columns.iter().find(|column| column.name == name) {
+                Some(column) => column.clone(),
+                None => CsvColumn::new(name, CSV_DEFAULT_DATA_TYPE),
+            })
+            .collect()
+    } else if options.columns.is_empty() {
+        return Err(Box::new(CsvError::ColumnsRequired));
+    } else {
+        options.columns.clone()
+    };
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    let collection = match database
+        .collections
+        .iter_mut()
+        .find(|collection| collection.name == collection_name)
+    {
+        Some(collection) => collection,
+        None => return Err(Box::new(CollectionError::NotFound)),
+    };
+
+    let mut report = CsvImportReportDto {
+        dry_run: options.dry_run,
+        ..Default::default()
+    };
+    for record in reader.records() {
+        let record = record.map_err(|e| CsvError::Parse(e.to_string()))?;
+        let row = record.position().map(|position| position.line()).unwrap_or_default();
+
+        match import_row(collection, &columns, &record) {
+            Ok(()) => report.imported_count += 1,
+            Err(message) => report.errors.push(CsvRowError { row, message }),
+        }
+    }
+
+    if !options.dry_run && report.imported_count > 0 {
+        let buf = serialize_database(&database)?;
+        write_database_to_file(&buf, file_path)?;
+    }
+
+    Ok(report)
+}
+
+/// Gets the document id and all field names in a collection in the order they are first found.
+fn collection_fields(collection: &pb::Collection) -> Vec<String> {
+    let mut fields = IndexSet::new();
+    fields.insert(CSV_ID_COLUMN.to_string());
+
+    for document in collection.documents.iter() {
+        for field in document.data.iter() {
+            fields.insert(field.key.clone());
+        }
+    }
+
+    fields.into_iter().collect()
+}
+
+/// Parses a CSV row and inserts it to a collection as a document.
+/// 
+/// Returns the reason if the row is not valid.
+fn import_row(
+    collection: &mut pb::Collection,
+    columns: &[CsvColumn],
+    record: &StringRecord,
+) -> Result<(), String>
+{
+    if record.len() > columns.len() {
+        return Err(format!("Row has {} values but there are {} columns", record.len(), columns.len()));
+    }
+
+    let mut document_id = None;
+    let mut data = IndexMap::new();
+    for (column, value) in columns.iter().zip(record.iter()) {
+        if value.is_empty() {
+            continue;
+        }
+        if column.name == CSV_ID_COLUMN {
+            document_id = match collection.id_strategy() {
+                IdStrategy::ClientInteger => match value.parse::<u64>() {
+                    Ok(id) => Some(Id::Integer(id)),
+                    Err(_) => return Err(format!("Document id '{}' is not an integer", value)),
+                },
+                IdStrategy::ClientText => Some(Id::Text(value.to_string())),
+                // Generated ids are not kept
+                _ => None,
+            };
+            continue;
+        }
+
+        let input = DocumentInputDataField::new(&column.name, &column.data_type, value);
+        match input.parse_to_document_data_type(value, &column.data_type) {
+            Ok(value) => data.insert(column.name.clone(), value),
+            Err(e) => return Err(format!(
+                "Field '{}': Data type '{}' is not valid: {}",
+                column.name, column.data_type, e
+            )),
+        };
+    }
+
+    insert_imported_document(collection, document_id, data).map_err(|e| e.to_string())
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_row() {
+        let mut collection = pb::Collection::from("test");
+        let columns = vec![
+            CsvColumn::new(CSV_ID_COLUMN, CSV_DEFAULT_DATA_TYPE),
+            CsvColumn::new("name", "Text"),
+            CsvColumn::new("age", "Int32"),
+        ];
+
+        let record = StringRecord::from(vec!["5", "John", "42"]);
+        assert!(import_row(&mut collection, &columns, &record).is_ok());
+        assert_eq!(collection.documents[0].id, Some(Id::Integer(1)));
+        assert_eq!(collection.documents[0].data().len(), 2);
+
+        let record = StringRecord::from(vec!["", "Jane", ""]);
+        assert!(import_row(&mut collection, &columns, &record).is_ok());
+        assert_eq!(collection.documents[1].data().len(), 1);
+
+        let record = StringRecord::from(vec!["", "Jane", "old"]);
+        assert!(import_row(&mut collection, &columns, &record).is_err());
+        let record = StringRecord::from(vec!["", "Jane", "42", "extra"]);
+        assert!(import_row(&mut collection, &columns, &record).is_err());
+        assert_eq!(collection.documents.len(), 2);
+    }
+}
@@ -6,14 +6,23 @@ use std::{
         PathBuf,
     },
     error::Error,
-    fmt::Display,
+    fmt::{self, Display},
+    str::FromStr,
 };
 use crate::{
     storage::{
         error::DatabaseError,
         pb,
+        pb::Compression,
+        encryption::{
+            self,
+            Cipher,
+            EncryptionOptions,
+        },
+        journal,
         serialize_database,
         deserialize_database,
+        read_database_file,
         write_database_to_file,
         DB_FILE_EXTENSION,
     },
@@ -59,6 +68,7 @@ impl From<&str> for pb::Database {
             name: String::from(name),
             description: String::new(),
             collections: Vec::new(),
+            compression: Compression::None as i32,
         }
     }
 }
@@ -69,10 +79,48 @@ impl From<(&str, &str)> for pb::Database {
             name: String::from(name),
             description: String::from(description),
             collections: Vec::new(),
+            compression: Compression::None as i32,
         }
     }
 }
 
+impl Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Compression::None => "None",
+                Compression::Zstd => "Zstd",
+                Compression::Lz4 => "Lz4",
+            }
+        )
+    }
+}
+
+impl FromStr for Compression {
+    type Err = DatabaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "None" => Ok(Compression::None),
+            "Zstd" => Ok(Compression::Zstd),
+            "Lz4" => Ok(Compression::Lz4),
+            _ => Err(DatabaseError::UnknownCompression),
+        }
+    }
+}
+
+/// Options for creating a database.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DatabaseOptions {
+    /// Compression of the database file.
+    pub compression: Compression,
+
+    /// Encryption of the database file. The database file is not encrypted if this is `None`.
+    pub encryption: Option<EncryptionOptions>,
+}
+
 /// Database data transfer object (DTO).
 /// 
 /// Exposes database data that clients can use.
@@ -84,6 +132,10 @@ pub struct DatabaseDto {
     description: String,
     size: u64,
     file_path: PathBuf,
+    compression: Compression,
+    encryption: Option<Cipher>,
+    locked: bool,
+    journal: bool,
 }
 
 impl DatabaseDto {
@@ -103,12 +155,35 @@ impl DatabaseDto {
         &self.file_path
     }
 
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    /// Gets the cipher of the database file if it is encrypted.
+    pub fn encryption(&self) -> Option<Cipher> {
+        self.encryption
+    }
+
+    /// Checks if the database is encrypted and has not been unlocked.
+    /// 
+    /// Only the name, size, file path and encryption of locked databases are known.
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Checks if the database has a journal for point-in-time recovery.
+    pub fn journal(&self) -> bool {
+        self.journal
+    }
+
     /// Creates a new instance of `DatabaseDto`.
     pub fn new(
         name: String,
         description: String,
         size: u64,
-        file_path: PathBuf
+        file_path: PathBuf,
+        compression: Compression,
+        encryption: Option<Cipher>,
     ) -> Self
     {
         Self {
@@ -116,6 +191,30 @@ impl DatabaseDto {
             description,
             size,
             file_path,
+            compression,
+            encryption,
+            locked: false,
+            journal: false,
+        }
+    }
+
+    /// Creates a new instance of `DatabaseDto` for a locked database.
+    pub fn new_locked(
+        name: String,
+        size: u64,
+        file_path: PathBuf,
+        encryption: Option<Cipher>,
+    ) -> Self
+    {
+        Self {
+            name,
+            description: String::new(),
+            size,
+            file_path,
+            compression: Compression::None,
+            encryption,
+            locked: true,
+            journal: false,
         }
     }
 }
@@ -128,31 +227,120 @@ impl Display for DatabaseDto {
   Name:        {}
   Size:        {} bytes
   Description: {}
-  File path:   {}",
+  File path:   {}
+  Compression: {}
+  Encryption:  {}
+  Journal:     {}",
             self.name(),
             self.size(),
             self.description(),
             self.file_path().display(),
+            self.compression(),
+            match (self.encryption(), self.locked()) {
+                (Some(cipher), true) => format!("{} (locked)", cipher),
+                (Some(cipher), false) => cipher.to_string(),
+                (None, _) => String::from("None"),
+            },
+            if self.journal() { "Enabled" } else { "Disabled" },
         )
     }
 }
 
+/// Why a database file could not be listed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DatabaseFileProblemKind {
+    /// The file cannot be decoded to a database.
+    Decode,
+
+    /// The database name is not valid.
+    InvalidName,
+
+    /// The file cannot be read because of missing permissions.
+    PermissionDenied,
+
+    /// The file cannot be read.
+    Read,
+}
+
+impl Display for DatabaseFileProblemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseFileProblemKind::Decode => write!(f, "Decode error"),
+            DatabaseFileProblemKind::InvalidName => write!(f, "Invalid name"),
+            DatabaseFileProblemKind::PermissionDenied => write!(f, "Permission denied"),
+            DatabaseFileProblemKind::Read => write!(f, "Read error"),
+        }
+    }
+}
+
+impl From<&io::Error> for DatabaseFileProblemKind {
+    fn from(err: &io::Error) -> Self {
+        if err.kind() == io::ErrorKind::PermissionDenied {
+            return DatabaseFileProblemKind::PermissionDenied;
+        }
+        // Errors from the operating system happen when reading the file.
+        // Other errors happen when decoding its content.
+        if err.raw_os_error().is_some() {
+            return DatabaseFileProblemKind::Read;
+        }
+
+        DatabaseFileProblemKind::Decode
+    }
+}
+
+/// Database file that could not be listed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseFileProblem {
+    pub file_path: PathBuf,
+    pub kind: DatabaseFileProblemKind,
+    pub message: String,
+}
+
+impl Display for DatabaseFileProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.file_path.display(), self.kind, self.message)
+    }
+}
+
+/// Databases found in a directory.
+/// 
+/// Database files that could not be listed are reported as problems.
+#[derive(Debug, Default, PartialEq)]
+pub struct DatabaseListDto {
+    pub databases: Vec<DatabaseDto>,
+    pub problems: Vec<DatabaseFileProblem>,
+}
+
 /// Creates a database file and writes initial data to it.
+/// 
+/// The database file is compressed if the options have a compression
+/// and encrypted if the options have an encryption.
+/// Encrypted databases are unlocked after they are created.
 pub fn create_database_file(
     db_name: &str,
-    file_path: &Path
+    file_path: &Path,
+    options: &DatabaseOptions,
 ) -> Result<(), Box<dyn Error>>
 {
     if file_path.is_file() {
         return Err(Box::new(DatabaseError::Exists))
     }
 
-    let database = pb::Database::from(db_name);
+    let mut database = pb::Database::from(db_name);
+    database.set_compression(options.compression);
     if let Err(e) = database.validate_errors() {
         return Err(Box::new(e));
     }
 
+    let key = match &options.encryption {
+        Some(encryption_options) => Some(encryption::generate_key(encryption_options)?),
+        None => None,
+    };
+
     fs::File::create(file_path)?;
+    if let Some(key) = key {
+        encryption::insert_key(file_path, key);
+    }
     let buf = serialize_database(&database)?;
 
     match write_database_to_file(&buf, file_path) {
@@ -172,8 +360,10 @@ pub fn delete_database_file(
         return Err(Box::new(DatabaseError::NotFound));
     }
 
-    deserialize_database(&fs::read(file_path)?)?;
+    deserialize_database(&read_database_file(file_path)?)?;
+    encryption::take_key(file_path);
     fs::remove_file(file_path)?;
+    journal::delete_journal(file_path)?;
     
     Ok(())
 }
@@ -190,7 +380,7 @@ pub fn change_database_description(
         return Err(Box::new(DatabaseError::NotFound));
     }
 
-    let mut database = deserialize_database(&fs::read(file_path)?)?;
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
     database.description = description.to_string();
     if let Err(e) = database.validate_errors() {
         return Err(Box::new(e));
@@ -204,14 +394,198 @@ pub fn change_database_description(
     }
 }
 
+/// Gets the file path of a database with the given name
+/// in the same directory as `file_path`.
+pub(crate) fn sibling_database_file_path(db_name: &str, file_path: &Path) -> PathBuf {
+    file_path.with_file_name(format!("{}.{}", db_name, DB_FILE_EXTENSION))
+}
+
+/// Renames a database.
+/// 
+/// The database file is renamed too, so that its file name
+/// and the database name stay the same.
+/// The renamed file is in the same directory as the original file.
+/// If the file cannot be renamed, the original database name is restored.
+/// 
+/// Returns the file path of the renamed database.
+pub fn rename_database_file(
+    new_db_name: &str,
+    file_path: &Path
+) -> Result<PathBuf, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let original_buf = read_database_file(file_path)?;
+    let mut database = deserialize_database(&original_buf)?;
+    database.name = new_db_name.to_string();
+    if let Err(e) = database.validate_errors() {
+        return Err(Box::new(e));
+    }
+
+    let new_file_path = sibling_database_file_path(new_db_name, file_path);
+    if new_file_path != file_path && new_file_path.is_file() {
+        return Err(Box::new(DatabaseError::Exists));
+    }
+
+    let buf = serialize_database(&database)?;
+    write_database_to_file(&buf, file_path)?;
+    let key = encryption::take_key(file_path);
+    let result = fs::rename(file_path, &new_file_path);
+    if let Some(key) = key {
+        match result {
+            Ok(()) => encryption::insert_key(&new_file_path, key),
+            Err(_) => encryption::insert_key(file_path, key),
+        }
+    }
+    if let Err(e) = result {
+        // Roll back the name so it matches the file name again
+        write_database_to_file(&original_buf, file_path)?;
+        return Err(Box::new(e));
+    }
+    journal::rename_journal(file_path, &new_file_path)?;
+
+    Ok(new_file_path)
+}
+
+/// Clones a database to a new database file.
+/// 
+/// The clone has all collections and documents of the original database.
+/// It is created to the same directory as the original database.
+/// 
+/// Returns the file path of the cloned database.
+pub fn clone_database_file(
+    new_db_name: &str,
+    file_path: &Path
+) -> Result<PathBuf, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    database.name = new_db_name.to_string();
+    if let Err(e) = database.validate_errors() {
+        return Err(Box::new(e));
+    }
+
+    let new_file_path = sibling_database_file_path(new_db_name, file_path);
+    if new_file_path.is_file() {
+        return Err(Box::new(DatabaseError::Exists));
+    }
+
+    fs::File::create(&new_file_path)?;
+    if let Some(key) = encryption::get_key(file_path) {
+        encryption::insert_key(&new_file_path, key);
+    }
+    let buf = serialize_database(&database)?;
+
+    match write_database_to_file(&buf, &new_file_path) {
+        Ok(()) => return Ok(new_file_path),
+        Err(e) => return Err(e.into()),
+    }
+}
+
+/// Encrypts a database file that is not encrypted.
+/// 
+/// The database is unlocked after it is encrypted.
+pub fn encrypt_database_file(
+    file_path: &Path,
+    options: &EncryptionOptions,
+) -> Result<(), Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    if encryption::is_encrypted(&fs::read(file_path)?) {
+        return Err(Box::new(DatabaseError::AlreadyEncrypted));
+    }
+    if journal::is_journal_enabled(file_path) {
+        return Err(Box::new(DatabaseError::JournalNotSupported));
+    }
+
+    set_database_file_key(file_path, options)
+}
+
+/// Rotates the key of an encrypted database file.
+/// 
+/// The database must be unlocked with its current key.
+/// The database is rewritten with the new key and stays unlocked.
+pub fn rotate_database_file_key(
+    file_path: &Path,
+    options: &EncryptionOptions,
+) -> Result<(), Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    if !encryption::is_encrypted(&fs::read(file_path)?) {
+        return Err(Box::new(DatabaseError::NotEncrypted));
+    }
+
+    set_database_file_key(file_path, options)
+}
+
+/// Decrypts an encrypted database file.
+/// 
+/// The database must be unlocked with its key.
+pub fn decrypt_database_file(
+    file_path: &Path,
+) -> Result<(), Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    if !encryption::is_encrypted(&fs::read(file_path)?) {
+        return Err(Box::new(DatabaseError::NotEncrypted));
+    }
+
+    let database = deserialize_database(&read_database_file(file_path)?)?;
+    let buf = serialize_database(&database)?;
+    let key = encryption::take_key(file_path);
+
+    if let Err(e) = write_database_to_file(&buf, file_path) {
+        if let Some(key) = key {
+            encryption::insert_key(file_path, key);
+        }
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+/// Writes a database file with a new key.
+fn set_database_file_key(
+    file_path: &Path,
+    options: &EncryptionOptions,
+) -> Result<(), Box<dyn Error>>
+{
+    let database = deserialize_database(&read_database_file(file_path)?)?;
+    let buf = serialize_database(&database)?;
+    let new_key = encryption::generate_key(options)?;
+    let old_key = encryption::take_key(file_path);
+    encryption::insert_key(file_path, new_key);
+
+    if let Err(e) = write_database_to_file(&buf, file_path) {
+        encryption::take_key(file_path);
+        if let Some(key) = old_key {
+            encryption::insert_key(file_path, key);
+        }
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
 /// Finds all databases in a directory.
 /// 
 /// Returns the found databases.
 pub fn find_all_databases(
     dir_path: &Path
-) -> io::Result<Vec<DatabaseDto>>
+) -> io::Result<DatabaseListDto>
 {
-    let mut databases = Vec::new();
+    let mut database_list = DatabaseListDto::default();
 
     for entry in fs::read_dir(dir_path)? {
         let entry = entry?;
@@ -220,34 +594,26 @@ pub fn find_all_databases(
         if path.is_file() {
             if let Some(file_extension) = path.extension() {
                 if file_extension == DB_FILE_EXTENSION {
-                    let buf = fs::read(&path)?;
-                    let database = match deserialize_database(&buf) {
-                        Ok(database) => database,
-                        Err(e) => {
-                            return Err(io::Error::new(
-                                io::ErrorKind::Other,
-                                format!("Error parsing database: {} ({:?})", e, entry.file_name())
-                            ));
+                    let (kind, message) = match try_read_database_dto(&path) {
+                        Ok(Ok(database_dto)) => {
+                            database_list.databases.push(database_dto);
+                            continue;
                         },
+                        Ok(Err(e)) => (DatabaseFileProblemKind::InvalidName, e.to_string()),
+                        Err(e) => (DatabaseFileProblemKind::from(&e), e.to_string()),
                     };
-                    if let Err(_) = database.validate_errors() {
-                        continue;
-                    }
-
-                    let database_dto = DatabaseDto::new(
-                        database.name,
-                        database.description,
-                        entry.metadata()?.len(),
-                        path
-                    );
-                    
-                    databases.push(database_dto);
+
+                    database_list.problems.push(DatabaseFileProblem {
+                        file_path: path,
+                        kind,
+                        message,
+                    });
                 }
             }
         }
     }
 
-    Ok(databases)
+    Ok(database_list)
 }
 
 /// Finds a database in a directory.
@@ -264,20 +630,12 @@ pub fn find_database(
 
         if path.is_file() {
             if entry.file_name() == format!("{db_name}.{DB_FILE_EXTENSION}").as_str() {
-                let database = deserialize_database(&fs::read(&path)?)?;
-                if let Err(_) = database.validate_errors() {
-                    return Ok(None);
-                }
-
-                if database.name() == db_name {
-                    let database_dto = DatabaseDto::new(
-                        database.name,
-                        database.description,
-                        entry.metadata()?.len(),
-                        path
-                    );
-
-                    return Ok(Some(database_dto));
+                match read_database_dto(&path)? {
+                    Some(database_dto) if database_dto.name() == db_name => {
+                        return Ok(Some(database_dto));
+                    },
+                    Some(_) => {},
+                    None => return Ok(None),
                 }
             }
         }
@@ -297,30 +655,64 @@ pub fn find_database_by_file_path(
         return Ok(None);
     }
 
-    let database = deserialize_database(&fs::read(file_path)?)?;
-    if let Err(_) = database.validate_errors() {
-        return Ok(None)
+    read_database_dto(file_path)
+}
+
+/// Reads a database file to a database DTO.
+/// 
+/// Locked databases are returned without reading their data.
+/// The name of a locked database is its file name.
+/// Returns `None` if the database is not valid.
+fn read_database_dto(file_path: &Path) -> io::Result<Option<DatabaseDto>> {
+    Ok(try_read_database_dto(file_path)?.ok())
+}
+
+/// Reads a database file to a database DTO.
+/// 
+/// Returns the validation error if the database is not valid.
+fn try_read_database_dto(file_path: &Path) -> io::Result<Result<DatabaseDto, DatabaseError>> {
+    let buf = fs::read(file_path)?;
+    let size = file_path.metadata()?.len();
+    let encryption = encryption::encrypted_cipher(&buf);
+
+    if encryption::is_locked(&buf, file_path) {
+        let name = match file_path.file_stem() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => return Ok(Err(DatabaseError::EmptyName)),
+        };
+        return Ok(Ok(DatabaseDto::new_locked(name, size, PathBuf::from(file_path), encryption)));
+    }
+
+    let database = deserialize_database(&encryption::decrypt_database_buf(buf, file_path)?)?;
+    if let Err(e) = database.validate_errors() {
+        return Ok(Err(e));
     }
 
-    let database_dto = DatabaseDto::new(
+    let compression = database.compression();
+    let mut database_dto = DatabaseDto::new(
         database.name,
         database.description,
-        file_path.metadata()?.len(),
+        size,
         PathBuf::from(file_path),
+        compression,
+        encryption,
     );
+    database_dto.journal = journal::is_journal_enabled(file_path);
 
-    Ok(Some(database_dto))
+    Ok(Ok(database_dto))
 }
 
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::tempdir;
     use fs::File;
     use std::io::Write;
-    use crate::storage::pb::Database;
+    use crate::storage::pb::{
+        Database,
+        Collection,
+    };
 
     #[test]
     fn test_create_database_file() {
@@ -332,7 +724,7 @@ mod tests {
             .path()
             .join(&format!("{}.{}", db.name(), DB_FILE_EXTENSION));
 
-        assert!(create_database_file(db.name(), &file_path).is_ok());
+        assert!(create_database_file(db.name(), &file_path, &DatabaseOptions::default()).is_ok());
         assert_eq!(fs::read(&file_path).unwrap(), expected_db_buf);
 
         dir.close().unwrap();
@@ -392,7 +784,7 @@ mod tests {
         let mut file = File::create(&file_path).unwrap();
 
         assert!(file.write_all(&db_buf).is_ok());
-        let databases = find_all_databases(dir.path()).unwrap();
+        let databases = find_all_databases(dir.path()).unwrap().databases;
         assert_eq!(databases.get(0).unwrap().name(), db.name());
         assert!(databases.len() == 1);
 
@@ -439,4 +831,51 @@ mod tests {
         drop(file);
         dir.close().unwrap();
     }
+
+    #[test]
+    fn test_rename_database_file() {
+        let db = Database::from("test");
+        let db_buf = serialize_database(&db).unwrap();
+        let expected_db_buf = serialize_database(&Database::from("renamed")).unwrap();
+
+        let dir = tempdir().unwrap();
+        let file_path = dir
+            .path()
+            .join(&format!("{}.{}", db.name(), DB_FILE_EXTENSION));
+        let mut file = File::create(&file_path).unwrap();
+
+        assert!(file.write_all(&db_buf).is_ok());
+        let new_file_path = rename_database_file("renamed", &file_path).unwrap();
+        assert_eq!(new_file_path, dir.path().join(&format!("renamed.{}", DB_FILE_EXTENSION)));
+        assert!(!file_path.try_exists().unwrap());
+        assert_eq!(fs::read(&new_file_path).unwrap(), expected_db_buf);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_clone_database_file() {
+        let mut db = Database::from("test");
+        db.collections_mut().push(Collection::from("people"));
+        let db_buf = serialize_database(&db).unwrap();
+
+        let dir = tempdir().unwrap();
+        let file_path = dir
+            .path()
+            .join(&format!("{}.{}", db.name(), DB_FILE_EXTENSION));
+        let mut file = File::create(&file_path).unwrap();
+
+        assert!(file.write_all(&db_buf).is_ok());
+        let new_file_path = clone_database_file("cloned", &file_path).unwrap();
+        assert_eq!(fs::read(&file_path).unwrap(), db_buf);
+
+        let cloned_db = deserialize_database(&fs::read(&new_file_path).unwrap()).unwrap();
+        assert_eq!(cloned_db.name(), "cloned");
+        assert_eq!(cloned_db.collections(), db.collections());
+        assert!(clone_database_file("cloned", &file_path).is_err());
+
+        drop(file);
+        dir.close().unwrap();
+    }
 }
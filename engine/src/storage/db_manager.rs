@@ -7,6 +7,7 @@ use std::{
         PathBuf,
     }, hash::Hash,
 };
+use indexmap::IndexMap;
 use crate::{
     logging::*,
     DocumentInputDataField,
@@ -18,9 +19,83 @@ use crate::{
         },
         pb::document::DataType,
         pb::document::data_type,
+        pb::document::Id,
+        pb::CollectionSchema,
+        schema::SchemaReportDto,
+        encryption::{
+            EncryptionKey,
+            EncryptionOptions,
+            unlock_database_file,
+            lock_database_file,
+            create_key_file,
+        },
+        backup::{
+            BackupManifest,
+            backup_database_file,
+            backup_database_dir,
+            verify_backup,
+            restore_database_file,
+            restore_database_dir,
+        },
+        dump::{
+            DumpReportDto,
+            dump_database_dir,
+            restore_dump_to_dir,
+        },
+        journal::{
+            JournalEntryDto,
+            JournalTarget,
+            enable_journal,
+            disable_journal,
+            find_journal_entries,
+            restore_database_from_journal,
+        },
+        change_stream::{
+            ChangeStream,
+            ChangeStreamOptions,
+            watch_database,
+        },
+        integrity::{
+            IntegrityReportDto,
+            RepairReportDto,
+            check_database_file,
+            repair_database_file,
+        },
+        json::{
+            JsonOptions,
+            export_collection_to_json_file,
+            export_database_to_json_file,
+            import_json_file_to_collection,
+            import_json_file_to_database,
+        },
+        csv::{
+            CsvExportOptions,
+            CsvImportOptions,
+            CsvImportReportDto,
+            export_collection_to_csv_file,
+            import_csv_file_to_collection,
+        },
+        migration::{
+            FieldMigration,
+            FieldMigrationResultDto,
+            migrate_field_in_collection,
+        },
         database::*,
         collection::*,
         document::*,
+        ttl::{
+            set_collection_ttl_in_database,
+            purge_expired_documents_in_database,
+        },
+        pb::TtlPolicy,
+        history::{
+            DocumentRevisionDto,
+            set_collection_history_in_database,
+            find_document_revisions_in_collection,
+            find_document_revision_in_collection,
+            restore_document_revision_in_collection,
+            purge_document_history_in_collection,
+        },
         create_db_dir_if_not_exists,
         DB_FILE_EXTENSION,
     },
@@ -34,13 +109,20 @@ use super::error::DocumentError;
 #[derive(Debug)]
 pub struct DatabaseManager {
     db_dir_path: PathBuf,
+    db_options: DatabaseOptions,
 }
 
 impl DatabaseManager {
     /// Builds database manager.
     pub fn build(db_dir_path: &Path) -> Self {
+        Self::build_with_options(db_dir_path, &DatabaseOptions::default())
+    }
+
+    /// Builds database manager with default options for new databases.
+    pub fn build_with_options(db_dir_path: &Path, db_options: &DatabaseOptions) -> Self {
         Self {
             db_dir_path: PathBuf::from(db_dir_path),
+            db_options: db_options.clone(),
         }
     }
 }
@@ -51,6 +133,11 @@ impl DatabaseManager {
         &self.db_dir_path
     }
 
+    /// Gets the default options of new databases.
+    pub fn db_options(&self) -> &DatabaseOptions {
+        &self.db_options
+    }
+
     /// Gets database file path.
     fn db_file_path(&self, db_name: &str) -> PathBuf {
         PathBuf::from(&self.db_dir_path()
@@ -63,6 +150,7 @@ impl DatabaseManager {
     pub fn create_database_to_db_dir(
         &self,
         db_name: &str,
+        options: &DatabaseOptions,
     ) -> Result<(), DatabaseOperationError>
     {
         if let Err(err) = create_db_dir_if_not_exists(&self.db_dir_path()) {
@@ -74,10 +162,491 @@ impl DatabaseManager {
 
         if let Err(err) = create_database_file(
             db_name,
-            &self.db_file_path(db_name)
+            &self.db_file_path(db_name),
+            options,
+        ) {
+            return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::CreateDatabase,
+                err.to_string()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Creates a new database by file path.
+    pub fn create_database_by_file_path(
+        &self,
+        db_name: &str,
+        db_file_path: &Path,
+        options: &DatabaseOptions,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        if let Err(err) = create_database_file(
+            db_name,
+            db_file_path,
+            options,
+        ) {
+            return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::CreateDatabase,
+                err.to_string()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Deletes a database.
+    pub fn delete_database(
+        &self,
+        db_file_path: &Path,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        if let Err(err) = delete_database_file(db_file_path) {
+            return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::DeleteDatabase,
+                err.to_string()
+            ));
+        }
+        
+        Ok(())
+    }
+
+    /// Changes description of a database.
+    pub fn change_database_description(
+        &self,
+        db_file_path: &Path,
+        description: &str,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        if let Err(err) = change_database_description(
+            description,
+            db_file_path
+        ) {
+            return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ModifyDatabase,
+                err.to_string()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Renames a database and its database file.
+    /// 
+    /// Returns the file path of the renamed database.
+    pub fn rename_database(
+        &self,
+        db_file_path: &Path,
+        new_db_name: &str,
+    ) -> Result<PathBuf, DatabaseOperationError>
+    {
+        match rename_database_file(new_db_name, db_file_path) {
+            Ok(file_path) => return Ok(file_path),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ModifyDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Clones a database to a new database in the same directory.
+    /// 
+    /// Returns the file path of the cloned database.
+    pub fn clone_database(
+        &self,
+        db_file_path: &Path,
+        new_db_name: &str,
+    ) -> Result<PathBuf, DatabaseOperationError>
+    {
+        match clone_database_file(new_db_name, db_file_path) {
+            Ok(file_path) => return Ok(file_path),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::CreateDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Checks the integrity of a database.
+    /// 
+    /// Returns a report of all problems found in the database file.
+    pub fn check_database(
+        &self,
+        db_file_path: &Path,
+    ) -> Result<IntegrityReportDto, DatabaseOperationError>
+    {
+        match check_database_file(db_file_path) {
+            Ok(report) => return Ok(report),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::CheckDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Repairs a database by salvaging it to a new database in the same directory.
+    /// 
+    /// Returns the file path of the repaired database and the problems that were found.
+    pub fn repair_database(
+        &self,
+        db_file_path: &Path,
+        new_db_name: &str,
+    ) -> Result<RepairReportDto, DatabaseOperationError>
+    {
+        match repair_database_file(new_db_name, db_file_path) {
+            Ok(report) => return Ok(report),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::CreateDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Backs up a database to a backup directory.
+    /// 
+    /// Returns the backup manifest.
+    pub fn backup_database(
+        &self,
+        db_file_path: &Path,
+        backup_dir_path: &Path,
+    ) -> Result<BackupManifest, DatabaseOperationError>
+    {
+        match backup_database_file(db_file_path, backup_dir_path) {
+            Ok(manifest) => return Ok(manifest),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::BackupDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Backs up all databases in database directory to a backup directory.
+    /// 
+    /// Returns the backup manifest.
+    pub fn backup_all_databases(
+        &self,
+        backup_dir_path: &Path,
+    ) -> Result<BackupManifest, DatabaseOperationError>
+    {
+        if let Err(err) = create_db_dir_if_not_exists(self.db_dir_path()) {
+            return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::BackupDatabase,
+                format!("Failed to create database directory: {}", err)
+            ));
+        }
+
+        match backup_database_dir(self.db_dir_path(), backup_dir_path) {
+            Ok(manifest) => return Ok(manifest),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::BackupDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Verifies a backup.
+    /// 
+    /// Returns the backup manifest.
+    pub fn verify_backup(
+        &self,
+        backup_dir_path: &Path,
+    ) -> Result<BackupManifest, DatabaseOperationError>
+    {
+        match verify_backup(backup_dir_path) {
+            Ok(manifest) => return Ok(manifest),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::RestoreDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Restores a database from a backup to database directory.
+    /// 
+    /// Returns the file path of the restored database.
+    pub fn restore_database(
+        &self,
+        backup_dir_path: &Path,
+        db_name: &str,
+    ) -> Result<PathBuf, DatabaseOperationError>
+    {
+        if let Err(err) = create_db_dir_if_not_exists(self.db_dir_path()) {
+            return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::RestoreDatabase,
+                format!("Failed to create database directory: {}", err)
+            ));
+        }
+
+        match restore_database_file(backup_dir_path, db_name, self.db_dir_path()) {
+            Ok(file_path) => return Ok(file_path),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::RestoreDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Restores all databases from a backup to database directory.
+    /// 
+    /// Returns the file paths of the restored databases.
+    pub fn restore_all_databases(
+        &self,
+        backup_dir_path: &Path,
+    ) -> Result<Vec<PathBuf>, DatabaseOperationError>
+    {
+        if let Err(err) = create_db_dir_if_not_exists(self.db_dir_path()) {
+            return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::RestoreDatabase,
+                format!("Failed to create database directory: {}", err)
+            ));
+        }
+
+        match restore_database_dir(backup_dir_path, self.db_dir_path()) {
+            Ok(file_paths) => return Ok(file_paths),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::RestoreDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Dumps all databases in database directory to a dump file.
+    /// 
+    /// Returns the dumped databases and the number of collections and documents.
+    pub fn dump_all_databases(
+        &self,
+        dump_file_path: &Path,
+    ) -> Result<DumpReportDto, DatabaseOperationError>
+    {
+        if let Err(err) = create_db_dir_if_not_exists(self.db_dir_path()) {
+            return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::BackupDatabase,
+                format!("Failed to create database directory: {}", err)
+            ));
+        }
+
+        match dump_database_dir(self.db_dir_path(), dump_file_path) {
+            Ok(report) => return Ok(report),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::BackupDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Restores all databases in a dump file to database directory.
+    /// 
+    /// Restored databases use the default compression of new databases.
+    /// 
+    /// Returns the restored databases and the number of collections and documents.
+    pub fn restore_dump(
+        &self,
+        dump_file_path: &Path,
+    ) -> Result<DumpReportDto, DatabaseOperationError>
+    {
+        match restore_dump_to_dir(
+            dump_file_path,
+            self.db_dir_path(),
+            self.db_options().compression
+        ) {
+            Ok(report) => return Ok(report),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::RestoreDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Enables the journal of a database.
+    pub fn enable_journal(
+        &self,
+        db_file_path: &Path,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        match enable_journal(db_file_path) {
+            Ok(()) => return Ok(()),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::Journal,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Disables the journal of a database.
+    pub fn disable_journal(
+        &self,
+        db_file_path: &Path,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        match disable_journal(db_file_path) {
+            Ok(()) => return Ok(()),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::Journal,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Finds all entries in the journal of a database.
+    pub fn find_journal_entries(
+        &self,
+        db_file_path: &Path,
+    ) -> Result<Vec<JournalEntryDto>, DatabaseOperationError>
+    {
+        match find_journal_entries(db_file_path) {
+            Ok(entries) => return Ok(entries),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::Journal,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Restores a database to a point in its journal.
+    /// 
+    /// Returns the sequence number of the last applied journal entry.
+    pub fn restore_database_from_journal(
+        &self,
+        db_file_path: &Path,
+        target: JournalTarget,
+    ) -> Result<u64, DatabaseOperationError>
+    {
+        match restore_database_from_journal(db_file_path, target) {
+            Ok(sequence) => return Ok(sequence),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::RestoreDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Watches a database for changes to its documents.
+    /// 
+    /// Returns a change stream that receives the change events.
+    pub fn watch_database(
+        &self,
+        db_file_path: &Path,
+        options: &ChangeStreamOptions,
+    ) -> Result<ChangeStream, DatabaseOperationError>
+    {
+        match watch_database(db_file_path, options) {
+            Ok(change_stream) => return Ok(change_stream),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::WatchDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Unlocks an encrypted database with its key.
+    pub fn unlock_database(
+        &self,
+        db_file_path: &Path,
+        key: &EncryptionKey,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        match unlock_database_file(db_file_path, key) {
+            Ok(()) => return Ok(()),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::UnlockDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Locks an encrypted database by removing its key from memory.
+    pub fn lock_database(
+        &self,
+        db_file_path: &Path,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        match lock_database_file(db_file_path) {
+            Ok(()) => return Ok(()),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ModifyDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Encrypts a database that is not encrypted.
+    pub fn encrypt_database(
+        &self,
+        db_file_path: &Path,
+        options: &EncryptionOptions,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        match encrypt_database_file(db_file_path, options) {
+            Ok(()) => return Ok(()),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ModifyDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Rotates the key of an unlocked encrypted database.
+    pub fn rotate_database_key(
+        &self,
+        db_file_path: &Path,
+        options: &EncryptionOptions,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        match rotate_database_file_key(db_file_path, options) {
+            Ok(()) => return Ok(()),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ModifyDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Decrypts an unlocked encrypted database.
+    pub fn decrypt_database(
+        &self,
+        db_file_path: &Path,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        match decrypt_database_file(db_file_path) {
+            Ok(()) => return Ok(()),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ModifyDatabase,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Creates a key file with a random key.
+    pub fn create_key_file(
+        &self,
+        file_path: &Path,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        match create_key_file(file_path) {
+            Ok(()) => return Ok(()),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::CreateKeyFile,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Creates a new collection to a database.
+    pub fn create_collection(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+        options: &CollectionOptions,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        if let Err(err) = create_collection_to_database(
+            collection_name,
+            db_file_path,
+            options
         ) {
             return Err(DatabaseOperationError::new(
-                DatabaseOperationErrorKind::CreateDatabase,
+                DatabaseOperationErrorKind::CreateCollection,
                 err.to_string()
             ));
         }
@@ -85,19 +654,19 @@ impl DatabaseManager {
         Ok(())
     }
 
-    /// Creates a new database by file path.
-    pub fn create_database_by_file_path(
+    /// Deletes a collection from a database.
+    pub fn delete_collection(
         &self,
-        db_name: &str,
+        collection_name: &str,
         db_file_path: &Path,
     ) -> Result<(), DatabaseOperationError>
     {
-        if let Err(err) = create_database_file(
-            db_name,
+        if let Err(err) = delete_collection_from_database(
+            collection_name,
             db_file_path
         ) {
             return Err(DatabaseOperationError::new(
-                DatabaseOperationErrorKind::CreateDatabase,
+                DatabaseOperationErrorKind::DeleteCollection,
                 err.to_string()
             ));
         }
@@ -105,35 +674,47 @@ impl DatabaseManager {
         Ok(())
     }
 
-    /// Deletes a database.
-    pub fn delete_database(
+    /// Renames a collection in a database.
+    pub fn rename_collection(
         &self,
+        collection_name: &str,
+        new_collection_name: &str,
         db_file_path: &Path,
     ) -> Result<(), DatabaseOperationError>
     {
-        if let Err(err) = delete_database_file(db_file_path) {
+        if let Err(err) = rename_collection_in_database(
+            collection_name,
+            new_collection_name,
+            db_file_path
+        ) {
             return Err(DatabaseOperationError::new(
-                DatabaseOperationErrorKind::DeleteDatabase,
+                DatabaseOperationErrorKind::ModifyCollection,
                 err.to_string()
             ));
         }
-        
+
         Ok(())
     }
 
-    /// Changes description of a database.
-    pub fn change_database_description(
+    /// Copies a collection to a database with a new name.
+    /// 
+    /// The target database can be the same database.
+    pub fn copy_collection(
         &self,
+        collection_name: &str,
         db_file_path: &Path,
-        description: &str,
+        new_collection_name: &str,
+        target_db_file_path: &Path,
     ) -> Result<(), DatabaseOperationError>
     {
-        if let Err(err) = change_database_description(
-            description,
-            db_file_path
+        if let Err(err) = copy_collection_to_database(
+            collection_name,
+            db_file_path,
+            new_collection_name,
+            target_db_file_path
         ) {
             return Err(DatabaseOperationError::new(
-                DatabaseOperationErrorKind::ModifyDatabase,
+                DatabaseOperationErrorKind::CreateCollection,
                 err.to_string()
             ));
         }
@@ -141,19 +722,21 @@ impl DatabaseManager {
         Ok(())
     }
 
-    /// Creates a new collection to a database.
-    pub fn create_collection(
+    /// Sets a collection's schema. `None` removes the schema.
+    pub fn set_collection_schema(
         &self,
         collection_name: &str,
         db_file_path: &Path,
+        schema: Option<CollectionSchema>,
     ) -> Result<(), DatabaseOperationError>
     {
-        if let Err(err) = create_collection_to_database(
+        if let Err(err) = set_collection_schema_in_database(
             collection_name,
-            db_file_path
+            db_file_path,
+            schema
         ) {
             return Err(DatabaseOperationError::new(
-                DatabaseOperationErrorKind::CreateCollection,
+                DatabaseOperationErrorKind::ModifyCollection,
                 err.to_string()
             ));
         }
@@ -161,19 +744,17 @@ impl DatabaseManager {
         Ok(())
     }
 
-    /// Deletes a collection from a database.
-    pub fn delete_collection(
+    /// Sets a collection's time-to-live policy. `None` removes the policy.
+    pub fn set_collection_ttl(
         &self,
         collection_name: &str,
         db_file_path: &Path,
+        ttl: Option<TtlPolicy>,
     ) -> Result<(), DatabaseOperationError>
     {
-        if let Err(err) = delete_collection_from_database(
-            collection_name,
-            db_file_path
-        ) {
+        if let Err(err) = set_collection_ttl_in_database(collection_name, db_file_path, ttl) {
             return Err(DatabaseOperationError::new(
-                DatabaseOperationErrorKind::DeleteCollection,
+                DatabaseOperationErrorKind::ModifyCollection,
                 err.to_string()
             ));
         }
@@ -181,9 +762,226 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Removes expired documents from all collections in a database.
+    /// 
+    /// Returns the number of removed documents.
+    pub fn purge_expired_documents(
+        &self,
+        db_file_path: &Path,
+    ) -> Result<usize, DatabaseOperationError>
+    {
+        match purge_expired_documents_in_database(db_file_path) {
+            Ok(removed_count) => return Ok(removed_count),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::DeleteDocument,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Finds a collection's schema.
+    pub fn find_collection_schema(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+    ) -> Result<Option<CollectionSchema>, DatabaseOperationError>
+    {
+        match find_collection_schema_in_database(
+            collection_name,
+            db_file_path
+        ) {
+            Ok(schema) => return Ok(schema),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::FindCollectionOne,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Scans a collection and reports its implicit schema.
+    pub fn infer_collection_schema(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+    ) -> Result<SchemaReportDto, DatabaseOperationError>
+    {
+        match infer_collection_schema_in_database(
+            collection_name,
+            db_file_path
+        ) {
+            Ok(report) => return Ok(report),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::FindCollectionOne,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Migrates a field in every document of a collection.
+    /// 
+    /// Returns the number of migrated and failed documents.
+    pub fn migrate_field(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        migration: &FieldMigration,
+    ) -> Result<FieldMigrationResultDto, DatabaseOperationError>
+    {
+        match migrate_field_in_collection(
+            db_file_path,
+            collection_name,
+            migration
+        ) {
+            Ok(result) => return Ok(result),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ModifyCollection,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Exports all documents in a collection to a JSON file.
+    /// 
+    /// Returns the number of exported documents.
+    pub fn export_collection_to_json(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        json_file_path: &Path,
+        options: &JsonOptions,
+    ) -> Result<usize, DatabaseOperationError>
+    {
+        match export_collection_to_json_file(
+            db_file_path,
+            collection_name,
+            json_file_path,
+            options
+        ) {
+            Ok(document_count) => return Ok(document_count),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ExportData,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Exports all documents in a database to a JSON file.
+    /// 
+    /// Returns the number of exported documents.
+    pub fn export_database_to_json(
+        &self,
+        db_file_path: &Path,
+        json_file_path: &Path,
+        options: &JsonOptions,
+    ) -> Result<usize, DatabaseOperationError>
+    {
+        match export_database_to_json_file(db_file_path, json_file_path, options) {
+            Ok(document_count) => return Ok(document_count),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ExportData,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Imports documents from a JSON file to a collection.
+    /// 
+    /// Returns the number of imported documents.
+    pub fn import_json_to_collection(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        json_file_path: &Path,
+        options: &JsonOptions,
+    ) -> Result<usize, DatabaseOperationError>
+    {
+        match import_json_file_to_collection(
+            db_file_path,
+            collection_name,
+            json_file_path,
+            options
+        ) {
+            Ok(document_count) => return Ok(document_count),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ImportData,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Imports documents from a JSON file to a database.
+    /// 
+    /// Returns the number of imported documents.
+    pub fn import_json_to_database(
+        &self,
+        db_file_path: &Path,
+        json_file_path: &Path,
+        options: &JsonOptions,
+    ) -> Result<usize, DatabaseOperationError>
+    {
+        match import_json_file_to_database(db_file_path, json_file_path, options) {
+            Ok(document_count) => return Ok(document_count),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ImportData,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Exports documents in a collection to a CSV file.
+    /// 
+    /// Returns the number of exported documents.
+    pub fn export_collection_to_csv(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        csv_file_path: &Path,
+        options: &CsvExportOptions,
+    ) -> Result<usize, DatabaseOperationError>
+    {
+        match export_collection_to_csv_file(
+            db_file_path,
+            collection_name,
+            csv_file_path,
+            options
+        ) {
+            Ok(document_count) => return Ok(document_count),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ExportData,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Imports documents from a CSV file to a collection.
+    /// 
+    /// Returns the number of imported documents and the rows that cannot be imported.
+    pub fn import_csv_to_collection(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        csv_file_path: &Path,
+        options: &CsvImportOptions,
+    ) -> Result<CsvImportReportDto, DatabaseOperationError>
+    {
+        match import_csv_file_to_collection(
+            db_file_path,
+            collection_name,
+            csv_file_path,
+            options
+        ) {
+            Ok(report) => return Ok(report),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ImportData,
+                err.to_string()
+            )),
+        }
+    }
+
     /// Creates a new document to a collection.
     /// 
     /// Validates input data and parses it into correct document data types.
+    /// The document id is generated if `document_id` is `None`.
     /// 
     /// Returns the created document.
     pub fn create_document(
@@ -191,9 +989,10 @@ impl DatabaseManager {
         db_file_path: &Path,
         collection_name: &str,
         input_data: Vec<DocumentInputDataField>,
+        document_id: Option<Id>,
     ) -> Result<DocumentDto, DatabaseOperationError>
     {
-        let mut document_data: HashMap<String, DataType> = HashMap::new();
+        let mut document_data: IndexMap<String, DataType> = IndexMap::new();
 
         // Validate input data
         for data_field in input_data {
@@ -226,12 +1025,13 @@ impl DatabaseManager {
         let created_document = match create_document_to_collection(
             db_file_path,
             collection_name,
-            document_data
+            document_data,
+            document_id
         ) {
             Ok(created_document) => created_document,
-            Err(err) => return Err(DatabaseOperationError::new(
+            Err(err) => return Err(DatabaseOperationError::from_storage_error(
                 DatabaseOperationErrorKind::CreateDocument,
-                err.to_string()
+                err
             )),
         };
 
@@ -244,12 +1044,12 @@ impl DatabaseManager {
     pub fn replace_document(
         &self,
         db_file_path: &Path,
-        document_id: &u64,
+        document_id: &Id,
         collection_name: &str,
         input_data: Vec<DocumentInputDataField>,
     ) -> Result<(), DatabaseOperationError>
     {
-        let mut document_data: HashMap<String, DataType> = HashMap::new();
+        let mut document_data: IndexMap<String, DataType> = IndexMap::new();
 
         // Validate input data
         for data_field in input_data {
@@ -285,9 +1085,9 @@ impl DatabaseManager {
             collection_name,
             document_data
         ) {
-            return Err(DatabaseOperationError::new(
+            return Err(DatabaseOperationError::from_storage_error(
                 DatabaseOperationErrorKind::ReplaceDocument,
-                err.to_string()
+                err
             ));
         }
 
@@ -298,7 +1098,7 @@ impl DatabaseManager {
     pub fn delete_document(
         &self,
         db_file_path: &Path,
-        document_id: &u64,
+        document_id: &Id,
         collection_name: &str,
     ) -> Result<(), DatabaseOperationError>
     {
@@ -337,10 +1137,104 @@ impl DatabaseManager {
         };
     }
 
+    /// Enables or disables a collection's history mode.
+    pub fn set_collection_history(
+        &self,
+        collection_name: &str,
+        db_file_path: &Path,
+        enabled: bool,
+    ) -> Result<(), DatabaseOperationError>
+    {
+        if let Err(err) = set_collection_history_in_database(collection_name, db_file_path, enabled) {
+            return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ModifyCollection,
+                err.to_string()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Finds the revisions of a document in a collection.
+    pub fn find_document_revisions(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        document_id: &Id,
+    ) -> Result<Vec<DocumentRevisionDto>, DatabaseOperationError>
+    {
+        match find_document_revisions_in_collection(db_file_path, collection_name, document_id) {
+            Ok(revisions) => return Ok(revisions),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::FindDocumentMany,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Finds a revision of a document in a collection by revision number.
+    pub fn find_document_revision(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        document_id: &Id,
+        revision: u64,
+    ) -> Result<Option<DocumentRevisionDto>, DatabaseOperationError>
+    {
+        match find_document_revision_in_collection(db_file_path, collection_name, document_id, revision) {
+            Ok(revision) => return Ok(revision),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::FindDocumentOne,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Restores a document to a revision.
+    /// 
+    /// Returns the restored document.
+    pub fn restore_document_revision(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        document_id: &Id,
+        revision: u64,
+    ) -> Result<DocumentDto, DatabaseOperationError>
+    {
+        match restore_document_revision_in_collection(db_file_path, collection_name, document_id, revision) {
+            Ok(document) => return Ok(document),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::ReplaceDocument,
+                err.to_string()
+            )),
+        }
+    }
+
+    /// Removes revisions that are older than the given number of seconds from a collection.
+    /// 
+    /// Returns the number of removed revisions.
+    pub fn purge_document_history(
+        &self,
+        db_file_path: &Path,
+        collection_name: &str,
+        older_than_seconds: u64,
+    ) -> Result<usize, DatabaseOperationError>
+    {
+        match purge_document_history_in_collection(db_file_path, collection_name, older_than_seconds) {
+            Ok(removed_count) => return Ok(removed_count),
+            Err(err) => return Err(DatabaseOperationError::new(
+                DatabaseOperationErrorKind::DeleteDocument,
+                err.to_string()
+            )),
+        }
+    }
+
     /// Finds all databases from database directory.
+    /// 
+    /// Database files that cannot be listed are reported as problems.
     pub fn find_all_databases(
         &self,
-    ) -> Result<Vec<DatabaseDto>, DatabaseOperationError>
+    ) -> Result<DatabaseListDto, DatabaseOperationError>
     {
         if let Err(err) = create_db_dir_if_not_exists(&self.db_dir_path()) {
             return Err(DatabaseOperationError::new(
@@ -453,7 +1347,7 @@ impl DatabaseManager {
     /// Finds a document in a collection by document id.
     pub fn find_document_by_id(
         &self,
-        document_id: &u64,
+        document_id: &Id,
         db_file_path: &Path,
         collection_name: &str,
     ) -> Result<Option<DocumentDto>, DatabaseOperationError>
@@ -477,7 +1371,7 @@ impl DatabaseManager {
         db_file_path: &Path,
         collection_name: &str,
         query: &Vec<DocumentInputDataField>,
-        limit: Option<usize>,
+        options: &FindOptions,
     ) -> Result<Vec<DocumentDto>, DatabaseOperationError>
     {
         let mut transformed_query: HashMap<String, data_type::DataType> = HashMap::new();
@@ -507,7 +1401,8 @@ impl DatabaseManager {
             db_file_path,
             collection_name,
             &transformed_query,
-            limit,
+            options.limit,
+            options.strict_types,
         ) {
             Ok(documents) => return Ok(documents),
             Err(err) => return Err(DatabaseOperationError::new(
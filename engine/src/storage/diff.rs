@@ -0,0 +1,62 @@
+// Document diffs between two versions of a collection.
+// Used by the journal and change streams to find the documents that a write changed.
+
+use std::collections::HashMap;
+use crate::storage::pb;
+
+/// Change to a document between two versions of a collection.
+#[derive(Debug, PartialEq)]
+pub(crate) enum DocumentChange<'a> {
+    /// Document is only in the new version.
+    Insert(&'a pb::Document),
+
+    /// Document with the same id is different in the new version.
+    Replace {
+        before: &'a pb::Document,
+        after: &'a pb::Document,
+    },
+
+    /// Document is only in the old version.
+    Delete(&'a pb::Document),
+}
+
+/// Gets a key for a document id that can be used in maps and sets.
+fn document_key(document: &pb::Document) -> String {
+    format!("{:?}", document.id)
+}
+
+/// Gets the changes from the old documents to the new documents.
+/// 
+/// Inserts and replaces are in the order of the new documents,
+/// followed by deletes in the order of the old documents.
+pub(crate) fn diff_documents<'a>(
+    old: &'a [pb::Document],
+    new: &'a [pb::Document],
+) -> Vec<DocumentChange<'a>>
+{
+    let mut old_documents: HashMap<String, &pb::Document> = old
+        .iter()
+        .map(|document| (document_key(document), document))
+        .collect();
+    let mut changes = Vec::new();
+
+    for document in new.iter() {
+        match old_documents.remove(&document_key(document)) {
+            Some(old_document) if old_document == document => {},
+            Some(old_document) => changes.push(DocumentChange::Replace {
+                before: old_document,
+                after: document,
+            }),
+            None => changes.push(DocumentChange::Insert(document)),
+        }
+    }
+
+    // Documents that are left were deleted
+    for document in old.iter() {
+        if old_documents.contains_key(&document_key(document)) {
+            changes.push(DocumentChange::Delete(document));
+        }
+    }
+
+    changes
+}
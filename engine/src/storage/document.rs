@@ -1,10 +1,11 @@
 use std::{
-    fs,
     path::Path,
     error::Error,
     collections::HashMap,
     fmt::{self, Display},
 };
+use indexmap::IndexMap;
+use rust_decimal::Decimal;
 use crate::{
     storage::{
         error::{
@@ -15,32 +16,68 @@ use crate::{
         pb,
         pb::document::DataType,
         pb::document::data_type,
+        pb::document::Id,
+        pb::document::Field,
+        pb::IdStrategy,
         serialize_database,
         deserialize_database,
+        read_database_file,
         write_database_to_file,
+        compare::values_equal,
+        ttl,
     },
     util::has_whitespaces
 };
 
+/// Value of a document field that has no value.
+static EMPTY_VALUE: DataType = DataType { data_type: None };
+
 // Implements methods for protobuf type
 impl pb::Document {
-    pub fn id(&self) -> &u64 {
-        &self.id
+    pub fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
     }
 
-    pub fn data(&self) -> &HashMap<String, DataType> {
-        &self.data
+    /// Gets the document data. Fields are in insertion order.
+    pub fn data(&self) -> IndexMap<String, DataType> {
+        self.data
+            .iter()
+            .map(|field| (field.key.clone(), field.value.clone().unwrap_or_default()))
+            .collect()
+    }
+
+    /// Iterates over the document fields without copying them. Fields are in insertion order.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &DataType)> {
+        self.data
+            .iter()
+            .map(|field| (field.key.as_str(), field.value.as_ref().unwrap_or(&EMPTY_VALUE)))
+    }
+
+    /// Takes the document data. Fields are in insertion order.
+    pub fn into_data(self) -> IndexMap<String, DataType> {
+        self.data
+            .into_iter()
+            .map(|field| (field.key, field.value.unwrap_or_default()))
+            .collect()
+    }
+
+    /// Sets the document data. Keeps the field order.
+    pub fn set_data(&mut self, data: IndexMap<String, DataType>) {
+        self.data = data
+            .into_iter()
+            .map(|(key, value)| Field { key, value: Some(value) })
+            .collect();
     }
 
     /// Creates a new document.
     /// 
-    /// Increases the collection's `id_count` by 1
+    /// The document id is generated with the collection's id strategy.
+    /// With the default strategy, increases the collection's `id_count` by 1
     /// so each document gets a unique id in the collection.
     pub fn new(collection: &mut pb::Collection) -> Self {
-        collection.id_count += 1;
         Self {
-            id: collection.id_count,
-            data: HashMap::new(),
+            id: collection.next_document_id(),
+            data: Vec::new(),
         }
     }
 
@@ -48,11 +85,11 @@ impl pb::Document {
     /// 
     /// Returns any errors that may occur during the process.
     pub fn validate_errors(&self) -> Result<(), DocumentError> {
-        for (key, _value) in self.data.iter() {
-            if key.is_empty() {
+        for field in self.data.iter() {
+            if field.key.is_empty() {
                 return Err(DocumentError::EmptyFieldName);
             }
-            if has_whitespaces(key) {
+            if has_whitespaces(&field.key) {
                 return Err(DocumentError::FieldNameHasWhitespace);
             }
         }
@@ -61,26 +98,75 @@ impl pb::Document {
     }
 }
 
+#[cfg(test)]
+impl pb::Document {
+    /// Creates a document with an integer id and one field for unit tests.
+    pub(crate) fn test_document(id: u64, key: &str, value: data_type::DataType) -> Self {
+        Self {
+            id: Some(Id::Integer(id)),
+            data: vec![Field {
+                key: key.to_string(),
+                value: Some(DataType { data_type: Some(value) }),
+            }],
+        }
+    }
+}
+
+impl Default for Id {
+    /// Integer id 0 is used for documents that have no id.
+    fn default() -> Self {
+        Id::Integer(0)
+    }
+}
+
+impl Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Id::Integer(id) => write!(f, "{}", id),
+            Id::Text(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+impl From<u64> for Id {
+    fn from(id: u64) -> Self {
+        Id::Integer(id)
+    }
+}
+
+impl From<&str> for Id {
+    fn from(id: &str) -> Self {
+        Id::Text(String::from(id))
+    }
+}
+
+impl From<String> for Id {
+    fn from(id: String) -> Self {
+        Id::Text(id)
+    }
+}
+
 /// Document data transfer object (DTO).
 /// 
 /// Exposes document data that clients can use.
-#[derive(Debug)]
+/// Fields are in insertion order.
+#[derive(Debug, Clone, PartialEq)]
 pub struct DocumentDto {
-    pub id: u64,
-    pub data: HashMap<String, DataType>,
+    pub id: Id,
+    pub data: IndexMap<String, DataType>,
 }
 
 impl DocumentDto {
-    pub fn id(&self) -> &u64 {
+    pub fn id(&self) -> &Id {
         &self.id
     }
 
-    pub fn data(&self) -> &HashMap<String, DataType> {
+    pub fn data(&self) -> &IndexMap<String, DataType> {
         &self.data
     }
 
     /// Creates a new instance of `DocumentDto`.
-    pub fn new(id: u64, data: &HashMap<String, DataType>) -> Self {
+    pub fn new(id: Id, data: &IndexMap<String, DataType>) -> Self {
         Self {
             id,
             data: data.to_owned(),
@@ -88,18 +174,26 @@ impl DocumentDto {
     }
 }
 
+impl From<&pb::Document> for DocumentDto {
+    fn from(document: &pb::Document) -> Self {
+        Self {
+            id: document.id.clone().unwrap_or_default(),
+            data: document
+                .fields()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+}
+
 impl Display for DocumentDto {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut data = Vec::new();
         for (key, value) in self.data().iter() {
             // Get data type and value
             let (data_type, field_value) = match &value.data_type {
-                Some(data_type::DataType::Int32(value)) => ("Int32", value.to_string()),
-                Some(data_type::DataType::Int64(value)) => ("Int64", value.to_string()),
-                Some(data_type::DataType::Decimal(value)) => ("Decimal", value.to_string()),
-                Some(data_type::DataType::Bool(value)) => ("Bool", value.to_string()),
-                Some(data_type::DataType::Text(value)) => ("Text", format!("\"{}\"", value)),
-                _ => ("Invalid document data type", "Invalid value".to_string()),
+                Some(value) => (data_type_name(value), format_value(value)),
+                None => ("Invalid document data type", "Invalid value".to_string()),
             };
     
             data.push(format!("  [{}] \"{}\": {}", data_type, key, field_value));
@@ -114,6 +208,110 @@ impl Display for DocumentDto {
     }
 }
 
+/// Options for finding documents using query.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FindOptions {
+    /// Maximum number of documents to return. `None` returns all matching documents.
+    pub limit: Option<usize>,
+
+    /// Whether query values match only document values with the same data type.
+    pub strict_types: bool,
+}
+
+/// Names of all document data types.
+pub const DATA_TYPE_NAMES: [&str; 10] = [
+    "Int32",
+    "Int64",
+    "Decimal",
+    "Bool",
+    "Text",
+    "Decimal128",
+    "UInt32",
+    "UInt64",
+    "Int128",
+    "UInt128",
+];
+
+/// Gets the name of a document value's data type.
+pub fn data_type_name(value: &data_type::DataType) -> &'static str {
+    match value {
+        data_type::DataType::Int32(_) => "Int32",
+        data_type::DataType::Int64(_) => "Int64",
+        data_type::DataType::Decimal(_) => "Decimal",
+        data_type::DataType::Bool(_) => "Bool",
+        data_type::DataType::Text(_) => "Text",
+        data_type::DataType::Decimal128(_) => "Decimal128",
+        data_type::DataType::Uint32(_) => "UInt32",
+        data_type::DataType::Uint64(_) => "UInt64",
+        data_type::DataType::Int128(_) => "Int128",
+        data_type::DataType::Uint128(_) => "UInt128",
+    }
+}
+
+/// Formats a document value for display. Text values are quoted.
+pub fn format_value(value: &data_type::DataType) -> String {
+    match value {
+        data_type::DataType::Text(value) => format!("\"{}\"", value),
+        _ => value_to_string(value).unwrap_or("Invalid value".to_string()),
+    }
+}
+
+/// Converts a document value to a string that can be parsed
+/// back into a document value. Text values are not quoted.
+/// 
+/// Returns `None` if the value is not valid in its storage format.
+pub fn value_to_string(value: &data_type::DataType) -> Option<String> {
+    match value {
+        data_type::DataType::Int32(value) => Some(value.to_string()),
+        data_type::DataType::Int64(value) => Some(value.to_string()),
+        data_type::DataType::Decimal(value) => Some(value.to_string()),
+        data_type::DataType::Bool(value) => Some(value.to_string()),
+        data_type::DataType::Text(value) => Some(value.clone()),
+        data_type::DataType::Decimal128(value) => Some(decode_decimal128(value)?.to_string()),
+        data_type::DataType::Uint32(value) => Some(value.to_string()),
+        data_type::DataType::Uint64(value) => Some(value.to_string()),
+        data_type::DataType::Int128(value) => Some(decode_int128(value)?.to_string()),
+        data_type::DataType::Uint128(value) => Some(decode_uint128(value)?.to_string()),
+    }
+}
+
+/// Encodes an exact decimal number to `Decimal128` storage format.
+pub fn encode_decimal128(value: &Decimal) -> Vec<u8> {
+    value.serialize().to_vec()
+}
+
+/// Decodes an exact decimal number from `Decimal128` storage format.
+/// 
+/// Returns `None` if the buffer is not a valid `Decimal128` value.
+pub fn decode_decimal128(buf: &[u8]) -> Option<Decimal> {
+    let bytes: [u8; 16] = buf.try_into().ok()?;
+    Some(Decimal::deserialize(bytes))
+}
+
+/// Encodes a 128-bit signed integer to `Int128` storage format.
+pub fn encode_int128(value: i128) -> Vec<u8> {
+    value.to_le_bytes().to_vec()
+}
+
+/// Decodes a 128-bit signed integer from `Int128` storage format.
+/// 
+/// Returns `None` if the buffer is not a valid `Int128` value.
+pub fn decode_int128(buf: &[u8]) -> Option<i128> {
+    Some(i128::from_le_bytes(buf.try_into().ok()?))
+}
+
+/// Encodes a 128-bit unsigned integer to `UInt128` storage format.
+pub fn encode_uint128(value: u128) -> Vec<u8> {
+    value.to_le_bytes().to_vec()
+}
+
+/// Decodes a 128-bit unsigned integer from `UInt128` storage format.
+/// 
+/// Returns `None` if the buffer is not a valid `UInt128` value.
+pub fn decode_uint128(buf: &[u8]) -> Option<u128> {
+    Some(u128::from_le_bytes(buf.try_into().ok()?))
+}
+
 /* Disabled for now. Currently defined in generated protocol buffers code.
 /// Data type for document fields
 #[derive(Debug, PartialEq, Clone)]
@@ -160,20 +358,27 @@ impl fmt::Display for DataType {
 
 /// Creates a document to a collection.
 /// 
+/// The document id is generated with the collection's id strategy
+/// if `document_id` is `None`. Collections with a client id strategy
+/// require the id to be given. The oldest documents are evicted
+/// if the collection is capped. Expired documents are removed
+/// from the database if a collection has a TTL policy.
+/// 
 /// Writes the modified database to the database file.
 /// 
 /// Returns the created document.
 pub fn create_document_to_collection(
     file_path: &Path,
     collection_name: &str, 
-    data: HashMap<String, DataType>,
+    data: IndexMap<String, DataType>,
+    document_id: Option<Id>,
 ) -> Result<DocumentDto, Box<dyn Error>>
 {
     if !file_path.is_file() {
         return Err(Box::new(DatabaseError::NotFound));
     }
 
-    let mut database = deserialize_database(&fs::read(file_path)?)?;
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
     let mut collection_index = None;
 
     // Find collection index
@@ -192,17 +397,40 @@ pub fn create_document_to_collection(
             .collections_mut()
             .get_mut(collection_index)
         {
-            let mut document = pb::Document::new(collection);
-            document.data = data;
+            let document_id = match collection.new_document_id(document_id) {
+                Ok(document_id) => document_id,
+                Err(e) => return Err(Box::new(e)),
+            };
+            let mut document = pb::Document {
+                id: Some(document_id.clone()),
+                data: Vec::new(),
+            };
+            document.set_data(data.clone());
             if let Err(e) = document.validate_errors() {
                 return Err(Box::new(e));
             }
+            if let Some(schema) = &collection.schema {
+                if let Err(e) = schema.validate_document(&data) {
+                    return Err(Box::new(e));
+                }
+            }
+            if let Err(e) = collection.check_capped_document(&document) {
+                return Err(Box::new(e));
+            }
             let document_dto = DocumentDto::new(
-                document.id,
-                &document.data
+                document_id,
+                &data
             );
 
             collection.documents_mut().push(document);
+            let evicted_documents = collection.evict_capped_documents();
+            collection.record_revisions(evicted_documents, true);
+            if database.collections.iter().any(|collection| collection.ttl.is_some()) {
+                let now = ttl::now();
+                for collection in database.collections.iter_mut() {
+                    collection.remove_expired_documents(now);
+                }
+            }
             let buf = serialize_database(&database)?;
 
             match write_database_to_file(&buf, file_path) {
@@ -215,32 +443,90 @@ pub fn create_document_to_collection(
     Err(Box::new(CollectionError::NotFound))
 }
 
+/// Inserts imported document data to a collection.
+/// 
+/// The oldest documents are evicted if the collection is capped.
+/// The document id is kept only if the collection has a client id strategy.
+/// Otherwise a new id is generated, because the imported id could conflict
+/// with existing documents. The document is validated against the collection's schema.
+/// 
+/// Doesn't write the database to the database file.
+pub(crate) fn insert_imported_document(
+    collection: &mut pb::Collection,
+    document_id: Option<Id>,
+    data: IndexMap<String, DataType>,
+) -> Result<(), Box<dyn Error>>
+{
+    let document_id = match collection.id_strategy() {
+        IdStrategy::ClientInteger | IdStrategy::ClientText => document_id,
+        _ => None,
+    };
+    let document_id = collection.new_document_id(document_id)?;
+
+    if let Some(schema) = &collection.schema {
+        schema.validate_document(&data)?;
+    }
+    let mut document = pb::Document {
+        id: Some(document_id),
+        data: Vec::new(),
+    };
+    document.set_data(data);
+    document.validate_errors()?;
+    collection.check_capped_document(&document)?;
+
+    collection.documents.push(document);
+    let evicted_documents = collection.evict_capped_documents();
+    collection.record_revisions(evicted_documents, true);
+
+    Ok(())
+}
+
 /// Replaces a document's data. Keeps the document id.
 /// 
+/// The previous version is kept as a revision if the collection has history mode.
+/// If the collection is capped, the oldest other documents are evicted
+/// when the replaced document makes the collection exceed its limits.
+/// 
 /// Writes the modified database to the database file.
 pub fn replace_document_in_collection(
     file_path: &Path,
-    document_id: &u64,
+    document_id: &Id,
     collection_name: &str,
-    data: HashMap<String, DataType>,
+    data: IndexMap<String, DataType>,
 ) -> Result<(), Box<dyn Error>>
 {
     if !file_path.is_file() {
         return Err(Box::new(DatabaseError::NotFound));
     }
-    let mut database = deserialize_database(&fs::read(file_path)?)?;
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
 
     for collection in database.collections_mut() {
         if collection.name() == collection_name {
-            if let Some(document) = collection
-                .documents_mut()
-                .iter_mut()
-                .find(|document| document.id() == document_id)
+            if let Some(index) = collection
+                .documents
+                .iter()
+                .position(|document| document.id() == Some(document_id))
             {
-                document.data = data;
+                if let Some(schema) = &collection.schema {
+                    if let Err(e) = schema.validate_document(&data) {
+                        return Err(Box::new(e));
+                    }
+                }
+                let mut document = pb::Document {
+                    id: Some(document_id.clone()),
+                    data: Vec::new(),
+                };
+                document.set_data(data);
                 if let Err(e) = document.validate_errors() {
                     return Err(Box::new(e));
                 }
+                if let Err(e) = collection.check_capped_document(&document) {
+                    return Err(Box::new(e));
+                }
+                let previous = std::mem::replace(&mut collection.documents[index], document);
+                collection.record_revision(previous, false);
+                let evicted_documents = collection.evict_capped_documents_except(document_id);
+                collection.record_revisions(evicted_documents, true);
                 let buf = serialize_database(&database)?;
 
                 match write_database_to_file(&buf, file_path) {
@@ -258,28 +544,32 @@ pub fn replace_document_in_collection(
 
 /// Deletes a document from a collection by document id.
 /// 
+/// The deleted document is kept as a tombstone if the collection has history mode.
+/// 
 /// Writes the modified database to the database file.
 pub fn delete_document_from_collection(
     file_path: &Path,
-    document_id: &u64,
+    document_id: &Id,
     collection_name: &str,
 ) -> Result<(), Box<dyn Error>>
 {
     if !file_path.is_file() {
         return Err(Box::new(DatabaseError::NotFound));
     }
-    let mut database = deserialize_database(&fs::read(file_path)?)?;
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
 
     for collection in database.collections_mut() {
         if collection.name() == collection_name {
-            if let Some(_document) = collection
+            if let Some(document) = collection
                 .documents()
                 .iter()
-                .find(|document| document.id() == document_id)
+                .find(|document| document.id() == Some(document_id))
             {
+                let deleted_document = document.clone();
                 collection
                     .documents_mut()
-                    .retain(|document| document.id() != document_id);
+                    .retain(|document| document.id() != Some(document_id));
+                collection.record_revision(deleted_document, true);
                 let buf = serialize_database(&database)?;
 
                 match write_database_to_file(&buf, file_path) {
@@ -297,6 +587,8 @@ pub fn delete_document_from_collection(
 
 /// Deletes all documents from a collection.
 /// 
+/// The deleted documents are kept as tombstones if the collection has history mode.
+/// 
 /// Writes the modified database to the database file.
 /// 
 /// Returns the number of deleted documents.
@@ -308,13 +600,15 @@ pub fn delete_all_documents_from_collection(
     if !file_path.is_file() {
         return Err(Box::new(DatabaseError::NotFound));
     }
-    let mut database = deserialize_database(&fs::read(file_path)?)?;
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
 
     for collection in database.collections_mut() {
         if collection.name() == collection_name {
-            let deleted_count = collection.documents_mut()
+            let deleted_documents: Vec<pb::Document> = collection.documents_mut()
                 .drain(..)
-                .count();
+                .collect();
+            let deleted_count = deleted_documents.len();
+            collection.record_revisions(deleted_documents, true);
             let buf = serialize_database(&database)?;
 
             match write_database_to_file(&buf, file_path) {
@@ -341,26 +635,27 @@ pub fn find_all_documents_in_collection(
     if !file_path.is_file() {
         return Err(Box::new(DatabaseError::NotFound));
     }
-    let database = deserialize_database(&fs::read(file_path)?)?;
+    let database = deserialize_database(&read_database_file(file_path)?)?;
     let mut documents = Vec::new();
 
-    for collection in database.collections.into_iter() {
+    for mut collection in database.collections.into_iter() {
         if collection.name() == collection_name {
+            collection.remove_expired_documents(ttl::now());
             if let Some(limit) = limit {
                 for document in collection.documents.into_iter() {
                     if documents.len() >= limit {
                         return Ok(documents)
                     }
                     documents.push(DocumentDto {
-                        id: document.id,
-                        data: document.data,
+                        id: document.id.clone().unwrap_or_default(),
+                        data: document.into_data(),
                     });
                 }
             } else {
                 for document in collection.documents.into_iter() {
                     documents.push(DocumentDto {
-                        id: document.id,
-                        data: document.data,
+                        id: document.id.clone().unwrap_or_default(),
+                        data: document.into_data(),
                     });
                 }
             }
@@ -376,22 +671,23 @@ pub fn find_all_documents_in_collection(
 /// Returns the found document.
 pub fn find_document_in_collection_by_id(
     file_path: &Path,
-    document_id: &u64,
+    document_id: &Id,
     collection_name: &str,
 ) -> Result<Option<DocumentDto>, Box<dyn Error>>
 {
     if !file_path.is_file() {
         return Err(Box::new(DatabaseError::NotFound));
     }
-    let database = deserialize_database(&fs::read(file_path)?)?;
+    let database = deserialize_database(&read_database_file(file_path)?)?;
 
-    for collection in database.collections.into_iter() {
+    for mut collection in database.collections.into_iter() {
         if collection.name() == collection_name {
+            collection.remove_expired_documents(ttl::now());
             for document in collection.documents.into_iter() {
-                if document.id() == document_id {
+                if document.id() == Some(document_id) {
                     let document_dto = DocumentDto {
-                        id: document.id,
-                        data: document.data,
+                        id: document.id.clone().unwrap_or_default(),
+                        data: document.into_data(),
                     };
     
                     return Ok(Some(document_dto));
@@ -409,6 +705,8 @@ pub fn find_document_in_collection_by_id(
 /// Accepts query to find specific documents.
 /// 
 /// The query contains fields of key-values that the document must match.
+/// Numeric values of different data types match if they are numerically equal.
+/// If `strict_types` is true, the data types must also be the same.
 /// 
 /// Limit specifies the maximum number of documents to return.
 /// 
@@ -418,62 +716,33 @@ pub fn find_documents_in_collection(
     collection_name: &str,
     query: &HashMap<String, data_type::DataType>,
     limit: Option<usize>,
+    strict_types: bool,
 ) -> Result<Vec<DocumentDto>, Box<dyn Error>>
 {
     if !file_path.is_file() {
         return Err(Box::new(DatabaseError::NotFound));
     }
-    let database = deserialize_database(&fs::read(file_path)?)?;
+    let database = deserialize_database(&read_database_file(file_path)?)?;
     let mut documents = Vec::new();
 
-    for collection in database.collections.into_iter() {
+    for mut collection in database.collections.into_iter() {
         if collection.name() == collection_name {
             if query.is_empty() {
                 return Ok(documents);
             }
+            collection.remove_expired_documents(ttl::now());
 
-            if let Some(limit) = limit {
-                for document in collection.documents.into_iter() {
+            for document in collection.documents.into_iter() {
+                if let Some(limit) = limit {
                     if documents.len() >= limit {
                         return Ok(documents)
                     }
-                    let mut fields_match = 0;
-                    for (document_key, document_value) in document.data.iter() {
-                        if let Some(query_value) = query.get(document_key) {
-                            if let Some(document_value) = &document_value.data_type {
-                                if query_value == document_value {
-                                    fields_match += 1;
-                                }
-                                if fields_match == query.len() {
-                                    documents.push(DocumentDto {
-                                        id: document.id,
-                                        data: document.data,
-                                    });
-                                    break
-                                }
-                            }
-                        }
-                    }
                 }
-            } else {
-                for document in collection.documents.into_iter() {
-                    let mut fields_match = 0;
-                    for (document_key, document_value) in document.data.iter() {
-                        if let Some(query_value) = query.get(document_key) {
-                            if let Some(document_value) = &document_value.data_type {
-                                if query_value == document_value {
-                                    fields_match += 1;
-                                }
-                                if fields_match == query.len() {
-                                    documents.push(DocumentDto {
-                                        id: document.id,
-                                        data: document.data,
-                                    });
-                                    break
-                                }
-                            }
-                        }
-                    }
+                if document_matches_query(&document, query, strict_types) {
+                    documents.push(DocumentDto {
+                        id: document.id.clone().unwrap_or_default(),
+                        data: document.into_data(),
+                    });
                 }
             }
 
@@ -484,6 +753,26 @@ pub fn find_documents_in_collection(
     Err(Box::new(CollectionError::NotFound))
 }
 
+/// Checks if a document has the fields of a query with matching values.
+fn document_matches_query(
+    document: &pb::Document,
+    query: &HashMap<String, data_type::DataType>,
+    strict_types: bool,
+) -> bool
+{
+    let fields_match = document
+        .fields()
+        .filter(|(key, value)| match (query.get(*key), value.data_type.as_ref()) {
+            (Some(query_value), Some(document_value)) => {
+                values_equal(query_value, document_value, strict_types)
+            },
+            _ => false,
+        })
+        .count();
+
+    fields_match == query.len()
+}
+
 
 
 #[cfg(test)]
@@ -491,7 +780,10 @@ mod tests {
     use super::*;
     use std::io::Write;
     use tempfile::tempdir;
-    use std::fs::File;
+    use std::fs::{
+        self,
+        File,
+    };
     use crate::storage::{
         DB_FILE_EXTENSION,
         pb::{
@@ -506,7 +798,7 @@ mod tests {
     };
 
     // Inserts some data to documents that are created in tests
-    fn insert_document_test_data(data: &mut HashMap<String, DataType>) {
+    fn insert_document_test_data(data: &mut IndexMap<String, DataType>) {
         data.insert(
             String::from("first_name"),
             DataType {
@@ -535,14 +827,14 @@ mod tests {
         db.collections_mut().push(collection);
         let db_buf = serialize_database(&db).unwrap();
 
-        let mut data = HashMap::new();
+        let mut data = IndexMap::new();
         insert_document_test_data(&mut data);
         assert!(data.len() > 0);
 
         let mut document = Document::new(db.collections_mut()
             .get_mut(0)
             .unwrap());
-        document.data = data.clone();
+        document.set_data(data.clone());
         db.collections_mut()
             .get_mut(0)
             .unwrap()
@@ -560,9 +852,10 @@ mod tests {
         let created_document = create_document_to_collection(
             &file_path,
             collection_name,
-            data
+            data,
+            None,
         ).unwrap();
-        assert!(created_document.id == 1);
+        assert_eq!(created_document.id, Id::Integer(1));
         assert_eq!(fs::read(&file_path).unwrap(), expected_db_buf);
 
         drop(file);
@@ -577,14 +870,14 @@ mod tests {
         let collection = Collection::from(collection_name);
         db.collections_mut().push(collection);
 
-        let mut data = HashMap::new();
+        let mut data = IndexMap::new();
         insert_document_test_data(&mut data);
         assert!(data.len() > 0);
 
         let mut document = Document::new(db.collections_mut()
             .get_mut(0)
             .unwrap());
-        document.data = data;
+        document.set_data(data);
         db.collections_mut()
             .get_mut(0)
             .unwrap()
@@ -592,7 +885,7 @@ mod tests {
             .push(document);
         let db_buf = serialize_database(&db).unwrap();
 
-        let mut new_data = HashMap::new();
+        let mut new_data = IndexMap::new();
         new_data.insert(
             String::from("name"),
             DataType {
@@ -605,7 +898,7 @@ mod tests {
             .documents_mut()
             .get_mut(0)
             .unwrap();
-        document.data = new_data.clone();
+        document.set_data(new_data.clone());
         let expected_db_buf = serialize_database(&db).unwrap();
 
         let dir = tempdir().unwrap();
@@ -615,7 +908,12 @@ mod tests {
         let mut file = File::create(&file_path).unwrap();
 
         assert!(file.write_all(&db_buf).is_ok());
-        assert!(replace_document_in_collection(&file_path, &1, collection_name, new_data).is_ok());
+        assert!(replace_document_in_collection(
+            &file_path,
+            &Id::Integer(1),
+            collection_name,
+            new_data
+        ).is_ok());
         assert_eq!(fs::read(&file_path).unwrap(), expected_db_buf);
 
         drop(file);
@@ -629,14 +927,14 @@ mod tests {
         let collection = Collection::from(collection_name);
         db.collections_mut().push(collection);
         
-        let mut data = HashMap::new();
+        let mut data = IndexMap::new();
         insert_document_test_data(&mut data);
         assert!(data.len() > 0);
 
         let mut document = Document::new(db.collections_mut()
             .get_mut(0)
             .unwrap());
-        document.data = data;
+        document.set_data(data);
         db.collections_mut()
             .get_mut(0)
             .unwrap()
@@ -658,7 +956,11 @@ mod tests {
         let mut file = File::create(&file_path).unwrap();
 
         assert!(file.write_all(&db_buf).is_ok());
-        assert!(delete_document_from_collection(&file_path, &1, collection_name).is_ok());
+        assert!(delete_document_from_collection(
+            &file_path,
+            &Id::Integer(1),
+            collection_name
+        ).is_ok());
         assert_eq!(fs::read(&file_path).unwrap(), expected_db_buf);
 
         drop(file);
@@ -719,14 +1021,14 @@ mod tests {
         let collection = Collection::from(collection_name);
         db.collections_mut().push(collection);
 
-        let mut data = HashMap::new();
+        let mut data = IndexMap::new();
         insert_document_test_data(&mut data);
         assert!(data.len() > 0);
 
         let mut document = Document::new(db.collections_mut()
             .get_mut(0)
             .unwrap());
-        document.data = data;
+        document.set_data(data);
         db.collections_mut()
             .get_mut(0)
             .unwrap()
@@ -759,14 +1061,14 @@ mod tests {
         let collection = Collection::from(collection_name);
         db.collections_mut().push(collection);
 
-        let mut data = HashMap::new();
+        let mut data = IndexMap::new();
         insert_document_test_data(&mut data);
         assert!(data.len() > 0);
 
         let mut document = Document::new(db.collections_mut()
             .get_mut(0)
             .unwrap());
-        document.data = data;
+        document.set_data(data);
         db.collections_mut()
             .get_mut(0)
             .unwrap()
@@ -783,11 +1085,11 @@ mod tests {
         assert!(file.write_all(&db_buf).is_ok());
         let document = find_document_in_collection_by_id(
             &file_path,
-            &1,
+            &Id::Integer(1),
             collection_name
         ).unwrap();
         assert!(document.is_some());
-        assert_eq!(document.unwrap().id, 1);
+        assert_eq!(document.unwrap().id, Id::Integer(1));
 
         drop(file);
         dir.close().unwrap();
@@ -802,7 +1104,7 @@ mod tests {
         let max_documents = 4;
 
         for i in 1..=max_documents {
-            let mut data = HashMap::new();
+            let mut data = IndexMap::new();
             if i == 1 {
                 data.insert(
                     String::from("age"),
@@ -822,7 +1124,7 @@ mod tests {
             let mut document = Document::new(db.collections_mut()
                 .get_mut(0)
                 .unwrap());
-            document.data = data;
+            document.set_data(data);
 
             db.collections_mut()
                 .get_mut(0)
@@ -852,6 +1154,7 @@ mod tests {
             collection_name,
             &query,
             None,
+            false,
         ).unwrap();
         assert_ne!(documents.len(), document_count);
         assert_eq!(documents.len(), expected_document_count);
@@ -859,5 +1162,80 @@ mod tests {
         drop(file);
         dir.close().unwrap();
     }
-}
 
+    #[test]
+    fn test_document_field_order() {
+        let mut db = Database::from("test");
+        let collection_name = "test_collection";
+        db.collections_mut().push(Collection::from(collection_name));
+        let db_buf = serialize_database(&db).unwrap();
+
+        let dir = tempdir().unwrap();
+        let file_path = dir
+            .path()
+            .join(&format!("{}.{}", db.name(), DB_FILE_EXTENSION));
+        let mut file = File::create(&file_path).unwrap();
+        assert!(file.write_all(&db_buf).is_ok());
+
+        let field_names = ["zip_code", "age", "name", "city", "bio"];
+        let mut data = IndexMap::new();
+        for (index, field_name) in field_names.iter().enumerate() {
+            data.insert(
+                field_name.to_string(),
+                DataType {
+                    data_type: Some(data_type::DataType::Int32(index as i32))
+                }
+            );
+        }
+
+        let created_document = create_document_to_collection(
+            &file_path,
+            collection_name,
+            data,
+            None,
+        ).unwrap();
+        let document = find_document_in_collection_by_id(
+            &file_path,
+            created_document.id(),
+            collection_name
+        ).unwrap().unwrap();
+        let keys: Vec<&str> = document.data().keys().map(|key| key.as_str()).collect();
+        assert_eq!(keys, field_names);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_decode_document_with_map_data() {
+        // Documents used to store data in a protobuf map
+        #[derive(Clone, PartialEq, prost::Message)]
+        struct MapDocument {
+            #[prost(uint64, tag = "1")]
+            id: u64,
+            #[prost(map = "string, message", tag = "2")]
+            data: HashMap<String, DataType>,
+        }
+
+        let mut data = HashMap::new();
+        data.insert(
+            String::from("name"),
+            DataType {
+                data_type: Some(data_type::DataType::Text(String::from("John Smith")))
+            }
+        );
+        let map_document = MapDocument { id: 1, data };
+        let buf = prost::Message::encode_to_vec(&map_document);
+
+        let document = <Document as prost::Message>::decode(buf.as_slice()).unwrap();
+        assert_eq!(document.id(), Some(&Id::Integer(1)));
+        assert_eq!(
+            document.data().get("name").unwrap().data_type,
+            Some(data_type::DataType::Text(String::from("John Smith")))
+        );
+
+        let fields: Vec<(&str, &DataType)> = document.fields().collect();
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].0, "name");
+    }
+}
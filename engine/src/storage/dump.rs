@@ -0,0 +1,571 @@
+// Whole database directory dump and restore.
+// A dump is one JSON file with all databases in a directory.
+// Its format is versioned and doesn't depend on the database file format,
+// so it can be used to move databases between machines and engine versions.
+
+use std::{
+    collections::HashSet,
+    error::Error,
+    ffi::OsStr,
+    fs,
+    path::Path,
+    str::FromStr,
+};
+use serde::{
+    Serialize,
+    Deserialize,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use crate::storage::{
+    error::DumpError,
+    pb,
+    pb::document::Id,
+    pb::Compression,
+    pb::IdStrategy,
+    json::{
+        JsonMode,
+        JSON_ID_KEY,
+        document_to_json,
+        document_value,
+        typed_json_value,
+    },
+    serialize_database,
+    deserialize_database,
+    read_database_file,
+    write_database_to_file,
+    DB_FILE_EXTENSION,
+};
+
+/// Name of the dump format. Dump files have this in key `format`.
+pub const DUMP_FORMAT_NAME: &str = "database-system-dump";
+
+/// Current dump format version.
+pub const DUMP_FORMAT_VERSION: u32 = 1;
+
+/// Dump of all databases in a database directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dump {
+    /// Always `database-system-dump`.
+    pub format: String,
+
+    pub format_version: u32,
+
+    /// Version of the engine that created the dump.
+    pub engine_version: String,
+
+    /// Time when the dump was created in RFC 3339 format.
+    pub created_at: String,
+
+    pub databases: Vec<DumpDatabase>,
+}
+
+/// Database in a dump.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpDatabase {
+    pub name: String,
+
+    #[serde(default)]
+    pub description: String,
+
+    #[serde(default)]
+    pub collections: Vec<DumpCollection>,
+}
+
+/// Collection in a dump.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpCollection {
+    pub name: String,
+
+    /// Id strategy name, e.g. `AutoIncrement`.
+    pub id_strategy: String,
+
+    /// Largest generated or given integer document id.
+    pub id_count: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<DumpSchema>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<DumpTtlPolicy>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capped: Option<DumpCappedLimits>,
+
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub history: bool,
+
+    /// Previous versions of documents in the order they were made.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revisions: Vec<DumpRevision>,
+
+    /// Documents in typed JSON format with the document id in key `_id`.
+    #[serde(default)]
+    pub documents: Vec<Map<String, Value>>,
+}
+
+/// Collection schema in a dump.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpSchema {
+    pub fields: Vec<DumpFieldSchema>,
+
+    #[serde(default)]
+    pub forbid_unknown_fields: bool,
+}
+
+/// Collection time-to-live policy in a dump.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpTtlPolicy {
+    pub field: String,
+
+    #[serde(default)]
+    pub expire_after_seconds: u64,
+}
+
+/// Capped collection limits in a dump. 0 means no limit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpCappedLimits {
+    #[serde(default)]
+    pub max_documents: u64,
+
+    #[serde(default)]
+    pub max_size_bytes: u64,
+}
+
+/// Document revision in a dump.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpRevision {
+    pub revision: u64,
+
+    /// Unix timestamp in microseconds.
+    pub timestamp: i64,
+
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub deleted: bool,
+
+    /// Document in typed JSON format with the document id in key `_id`.
+    pub document: Map<String, Value>,
+}
+
+/// Field schema in a dump. Values are in typed JSON format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpFieldSchema {
+    pub name: String,
+
+    #[serde(default)]
+    pub required: bool,
+
+    #[serde(default)]
+    pub data_types: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<Value>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<Value>,
+
+    #[serde(default)]
+    pub max_length: u64,
+
+    #[serde(default)]
+    pub pattern: String,
+
+    #[serde(default)]
+    pub allowed_values: Vec<Value>,
+}
+
+/// Result of a dump or a restore.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DumpReportDto {
+    /// Names of the dumped or restored databases.
+    pub databases: Vec<String>,
+
+    pub collection_count: usize,
+
+    pub document_count: usize,
+}
+
+/// Dumps all databases in a directory to a dump file.
+/// 
+/// Encrypted databases must be unlocked. They are dumped unencrypted.
+/// 
+/// Returns the dumped databases and the number of collections and documents.
+pub fn dump_database_dir(
+    dir_path: &Path,
+    dump_file_path: &Path,
+) -> Result<DumpReportDto, Box<dyn Error>>
+{
+    if dump_file_path.exists() {
+        return Err(Box::new(DumpError::Exists));
+    }
+
+    let mut file_paths = Vec::new();
+    for entry in fs::read_dir(dir_path)? {
+        let path = entry?.path();
+
+        if path.is_file() && path.extension() == Some(OsStr::new(DB_FILE_EXTENSION)) {
+            file_paths.push(path);
+        }
+    }
+    file_paths.sort();
+
+    let mut report = DumpReportDto::default();
+    let mut databases = Vec::new();
+    for file_path in file_paths {
+        let database = deserialize_database(&read_database_file(&file_path)?)?;
+        report.databases.push(database.name.clone());
+        report.collection_count += database.collections.len();
+        report.document_count += database.collections
+            .iter()
+            .map(|collection| collection.documents.len())
+            .sum::<usize>();
+        databases.push(dump_database(&database));
+    }
+
+    let dump = Dump {
+        format: DUMP_FORMAT_NAME.to_string(),
+        format_version: DUMP_FORMAT_VERSION,
+        engine_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        databases,
+    };
+    fs::write(dump_file_path, serde_json::to_string_pretty(&dump)?)?;
+
+    Ok(report)
+}
+
+/// Restores all databases in a dump file to a directory.
+/// 
+/// Document ids and collection id counts are restored exactly.
+/// The whole dump is validated and no database in it may exist
+/// in the directory before any database file is written.
+/// Restored databases use the given compression and are not encrypted.
+/// 
+/// Returns the restored databases and the number of collections and documents.
+pub fn restore_dump_to_dir(
+    dump_file_path: &Path,
+    dir_path: &Path,
+    compression: Compression,
+) -> Result<DumpReportDto, Box<dyn Error>>
+{
+    let json = fs::read_to_string(dump_file_path)?;
+    let dump: Dump = match serde_json::from_str(&json) {
+        Ok(dump) => dump,
+        Err(e) => return Err(Box::new(DumpError::InvalidDump(e.to_string()))),
+    };
+    if dump.format != DUMP_FORMAT_NAME {
+        return Err(Box::new(DumpError::InvalidDump(format!("Unknown format '{}'", dump.format))));
+    }
+    if dump.format_version > DUMP_FORMAT_VERSION {
+        return Err(Box::new(DumpError::UnsupportedFormatVersion(dump.format_version)));
+    }
+
+    let mut report = DumpReportDto::default();
+    let mut databases = Vec::new();
+    for dump_database in dump.databases {
+        let mut database = match restore_database(dump_database) {
+            Ok(database) => database,
+            Err(message) => return Err(Box::new(DumpError::InvalidDump(message))),
+        };
+        database.set_compression(compression);
+
+        let file_path = dir_path.join(format!("{}.{}", database.name, DB_FILE_EXTENSION));
+        if file_path.exists() || databases.iter().any(|(path, _)| path == &file_path) {
+            return Err(Box::new(DumpError::DatabaseExists(database.name)));
+        }
+        databases.push((file_path, database));
+    }
+
+    fs::create_dir_all(dir_path)?;
+    for (file_path, database) in databases {
+        fs::File::create(&file_path)?;
+        let buf = serialize_database(&database)?;
+        write_database_to_file(&buf, &file_path)?;
+
+        report.databases.push(database.name.clone());
+        report.collection_count += database.collections.len();
+        report.document_count += database.collections
+            .iter()
+            .map(|collection| collection.documents.len())
+            .sum::<usize>();
+    }
+
+    Ok(report)
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Converts a database to dump format.
+fn dump_database(database: &pb::Database) -> DumpDatabase {
+    let collections = database.collections
+        .iter()
+        .map(|collection| DumpCollection {
+            name: collection.name.clone(),
+            id_strategy: collection.id_strategy().to_string(),
+            id_count: collection.id_count,
+            schema: collection.schema.as_ref().map(dump_schema),
+            ttl: collection.ttl.as_ref().map(|ttl| DumpTtlPolicy {
+                field: ttl.field.clone(),
+                expire_after_seconds: ttl.expire_after_seconds,
+            }),
+            capped: collection.capped.as_ref().map(|capped| DumpCappedLimits {
+                max_documents: capped.max_documents,
+                max_size_bytes: capped.max_size_bytes,
+            }),
+            history: collection.history,
+            revisions: collection.revisions
+                .iter()
+                .map(|revision| DumpRevision {
+                    revision: revision.revision,
+                    timestamp: revision.timestamp,
+                    deleted: revision.deleted,
+                    document: document_to_json(&revision.document.clone().unwrap_or_default(), JsonMode::Typed),
+                })
+                .collect(),
+            documents: collection.documents
+                .iter()
+                .map(|document| document_to_json(document, JsonMode::Typed))
+                .collect(),
+        })
+        .collect();
+
+    DumpDatabase {
+        name: database.name.clone(),
+        description: database.description.clone(),
+        collections,
+    }
+}
+
+/// Converts a collection schema to dump format.
+fn dump_schema(schema: &pb::CollectionSchema) -> DumpSchema {
+    let value = |value: &pb::document::DataType| value.data_type.as_ref().map(typed_json_value);
+
+    DumpSchema {
+        fields: schema.fields
+            .iter()
+            .map(|field| DumpFieldSchema {
+                name: field.name.clone(),
+                required: field.required,
+                data_types: field.data_types.clone(),
+                min: field.min.as_ref().and_then(value),
+                max: field.max.as_ref().and_then(value),
+                max_length: field.max_length,
+                pattern: field.pattern.clone(),
+                allowed_values: field.allowed_values.iter().filter_map(value).collect(),
+            })
+            .collect(),
+        forbid_unknown_fields: schema.forbid_unknown_fields,
+    }
+}
+
+/// Converts a database in dump format back to a database and validates it.
+/// 
+/// Returns the reason if the database is not valid.
+fn restore_database(dump_database: DumpDatabase) -> Result<pb::Database, String> {
+    let mut database = pb::Database::from(dump_database.name.as_str());
+    database.description = dump_database.description;
+    database.validate_errors().map_err(|e| format!("Database '{}': {}", database.name, e))?;
+
+    let mut collection_names = HashSet::new();
+    for dump_collection in dump_database.collections {
+        let label = format!("Database '{}', collection '{}'", database.name, dump_collection.name);
+        if !collection_names.insert(dump_collection.name.clone()) {
+            return Err(format!("{}: Duplicate collection name", label));
+        }
+        let collection = restore_collection(dump_collection).map_err(|e| format!("{}: {}", label, e))?;
+        database.collections.push(collection);
+    }
+
+    Ok(database)
+}
+
+/// Converts a collection in dump format back to a collection and validates it.
+fn restore_collection(dump_collection: DumpCollection) -> Result<pb::Collection, String> {
+    let mut collection = pb::Collection::from(dump_collection.name.as_str());
+    collection.validate_errors().map_err(|e| e.to_string())?;
+    let id_strategy = IdStrategy::from_str(&dump_collection.id_strategy).map_err(|e| e.to_string())?;
+    collection.set_id_strategy(id_strategy);
+    collection.id_count = dump_collection.id_count;
+    if let Some(schema) = dump_collection.schema {
+        collection.schema = Some(restore_schema(schema)?);
+    }
+    if let Some(ttl) = dump_collection.ttl {
+        let ttl = pb::TtlPolicy::new(&ttl.field, ttl.expire_after_seconds);
+        ttl.validate_errors().map_err(|e| e.to_string())?;
+        collection.ttl = Some(ttl);
+    }
+    if let Some(capped) = dump_collection.capped {
+        let capped = pb::CappedLimits::new(capped.max_documents, capped.max_size_bytes);
+        capped.validate_errors().map_err(|e| e.to_string())?;
+        collection.capped = Some(capped);
+    }
+    collection.history = dump_collection.history;
+    for (index, revision) in dump_collection.revisions.into_iter().enumerate() {
+        let document = restore_document(revision.document)
+            .map_err(|e| format!("Revision {}: {}", index + 1, e))?;
+        collection.revisions.push(pb::DocumentRevision {
+            revision: revision.revision,
+            timestamp: revision.timestamp,
+            document: Some(document),
+            deleted: revision.deleted,
+        });
+    }
+
+    let mut ids = HashSet::new();
+    for (index, object) in dump_collection.documents.into_iter().enumerate() {
+        let document = restore_document(object).map_err(|e| format!("Document {}: {}", index + 1, e))?;
+        let id = document.id.clone().unwrap_or_default();
+        if !ids.insert(format!("{:?}", id)) {
+            return Err(format!("Duplicate document id {}", id));
+        }
+        if let Id::Integer(id) = id {
+            if id > collection.id_count {
+                return Err(format!("Id count {} is lower than document id {}", collection.id_count, id));
+            }
+        }
+        collection.documents.push(document);
+    }
+
+    Ok(collection)
+}
+
+/// Converts a document in dump format back to a document and validates it.
+/// The document id is kept.
+fn restore_document(object: Map<String, Value>) -> Result<pb::Document, String> {
+    let mut document = pb::Document {
+        id: None,
+        data: Vec::new(),
+    };
+
+    for (key, value) in object {
+        if key == JSON_ID_KEY {
+            document.id = match value {
+                Value::Number(id) => id.as_u64().filter(|id| *id > 0).map(Id::Integer),
+                Value::String(id) => Some(Id::Text(id)),
+                _ => None,
+            };
+            continue;
+        }
+        if let Some(value) = document_value(value, JsonMode::Typed).map_err(|e| format!("Field '{}': {}", key, e))? {
+            document.data.push(pb::document::Field { key, value: Some(value) });
+        }
+    }
+    if document.id.is_none() {
+        return Err(String::from("Document must have a positive integer or a text id"));
+    }
+    document.validate_errors().map_err(|e| e.to_string())?;
+
+    Ok(document)
+}
+
+/// Converts a collection schema in dump format back to a collection schema.
+fn restore_schema(dump_schema: DumpSchema) -> Result<pb::CollectionSchema, String> {
+    let value = |value: Value| match document_value(value, JsonMode::Typed) {
+        Ok(Some(value)) => Ok(value),
+        Ok(None) => Err(String::from("Schema value is missing")),
+        Err(e) => Err(format!("Schema: {}", e)),
+    };
+
+    let mut schema = pb::CollectionSchema {
+        fields: Vec::new(),
+        forbid_unknown_fields: dump_schema.forbid_unknown_fields,
+    };
+    for field in dump_schema.fields {
+        schema.fields.push(pb::FieldSchema {
+            name: field.name,
+            required: field.required,
+            data_types: field.data_types,
+            min: field.min.map(value).transpose()?,
+            max: field.max.map(value).transpose()?,
+            max_length: field.max_length,
+            pattern: field.pattern,
+            allowed_values: field.allowed_values
+                .into_iter()
+                .map(value)
+                .collect::<Result<Vec<_>, _>>()?,
+        });
+    }
+
+    Ok(schema)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::pb::document::{
+        DataType,
+        data_type,
+    };
+
+    #[test]
+    fn test_dump_and_restore_database() {
+        let mut collection = pb::Collection::from("people");
+        collection.set_id_strategy(IdStrategy::ClientText);
+        collection.id_count = 7;
+        collection.documents.push(pb::Document {
+            id: Some(Id::Text(String::from("john"))),
+            data: vec![pb::document::Field {
+                key: String::from("age"),
+                value: Some(DataType { data_type: Some(data_type::DataType::Uint64(u64::MAX)) }),
+            }],
+        });
+        collection.schema = Some(pb::CollectionSchema {
+            fields: vec![pb::FieldSchema {
+                name: String::from("age"),
+                min: Some(DataType { data_type: Some(data_type::DataType::Int32(0)) }),
+                ..Default::default()
+            }],
+            forbid_unknown_fields: true,
+        });
+        collection.history = true;
+        collection.revisions.push(pb::DocumentRevision {
+            revision: 1,
+            timestamp: 1_700_000_000_000_000,
+            document: Some(pb::Document {
+                id: Some(Id::Text(String::from("jane"))),
+                data: Vec::new(),
+            }),
+            deleted: true,
+        });
+        let mut database = pb::Database::from("test");
+        database.description = String::from("Test database");
+        database.collections.push(collection);
+
+        let dump_database = dump_database(&database);
+        let json = serde_json::to_string(&dump_database).unwrap();
+        let restored = restore_database(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(restored, database);
+    }
+
+    #[test]
+    fn test_restore_invalid_database() {
+        let mut dump_database = dump_database(&pb::Database::from("test"));
+        dump_database.collections.push(DumpCollection {
+            name: String::from("people"),
+            id_strategy: IdStrategy::AutoIncrement.to_string(),
+            id_count: 1,
+            schema: None,
+            ttl: None,
+            capped: None,
+            history: false,
+            revisions: Vec::new(),
+            documents: vec![
+                serde_json::from_str(r#"{"_id": 1}"#).unwrap(),
+                serde_json::from_str(r#"{"_id": 2}"#).unwrap(),
+            ],
+        });
+
+        let err = restore_database(dump_database.clone()).unwrap_err();
+        assert!(err.contains("Id count 1 is lower than document id 2"));
+
+        dump_database.collections[0].documents[1] = serde_json::from_str(r#"{"_id": 1}"#).unwrap();
+        let err = restore_database(dump_database).unwrap_err();
+        assert!(err.contains("Duplicate document id 1"));
+    }
+}
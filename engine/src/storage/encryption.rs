@@ -0,0 +1,597 @@
+// Database file encryption
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
+    fs::{
+        self,
+        OpenOptions,
+    },
+    io::{
+        self,
+        Write,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    str::FromStr,
+    sync::{
+        Mutex,
+        MutexGuard,
+        OnceLock,
+    },
+};
+use aes_gcm::{
+    Aes256Gcm,
+    aead::{
+        Aead,
+        KeyInit,
+        OsRng,
+        Payload,
+        rand_core::RngCore,
+    },
+};
+use chacha20poly1305::ChaCha20Poly1305;
+use argon2::{
+    Argon2,
+    Algorithm,
+    Params,
+    Version,
+};
+use zeroize::Zeroizing;
+use crate::storage::{
+    error::DatabaseError,
+    file_header::{
+        FileHeader,
+        FLAG_ENCRYPTED,
+    },
+};
+
+/// Magic bytes at the start of encrypted payloads.
+const ENCRYPTED_PAYLOAD_MAGIC: [u8; 4] = [0x00, b'D', b'B', b'E'];
+
+/// Length of encryption keys and key files in bytes.
+pub const KEY_LEN: usize = 32;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Length of encrypted payload header.
+/// 
+/// The magic bytes are followed by the cipher, the key derivation,
+/// three Argon2 parameters as little-endian u32, the salt and the nonce.
+const HEADER_LEN: usize = ENCRYPTED_PAYLOAD_MAGIC.len() + 2 + 12 + SALT_LEN + NONCE_LEN;
+
+/// Key was loaded from a key file.
+const KDF_KEY_FILE: u8 = 0;
+
+/// Key was derived from a passphrase with Argon2id.
+const KDF_ARGON2ID: u8 = 1;
+
+/// Max Argon2 parameters accepted from an encrypted payload header.
+/// 
+/// The limits are four times the defaults that new keys are created with,
+/// so a modified header cannot make unlocking use excessive memory or time.
+const MAX_ARGON2_M_COST: u32 = Params::DEFAULT_M_COST * 4;
+const MAX_ARGON2_T_COST: u32 = Params::DEFAULT_T_COST * 4;
+const MAX_ARGON2_P_COST: u32 = Params::DEFAULT_P_COST * 4;
+
+/// Authenticated encryption cipher of a database file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Cipher {
+    #[default]
+    Aes256Gcm = 1,
+    ChaCha20Poly1305 = 2,
+}
+
+impl Cipher {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Cipher::Aes256Gcm),
+            2 => Some(Cipher::ChaCha20Poly1305),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Cipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cipher::Aes256Gcm => write!(f, "Aes256Gcm"),
+            Cipher::ChaCha20Poly1305 => write!(f, "ChaCha20Poly1305"),
+        }
+    }
+}
+
+impl FromStr for Cipher {
+    type Err = DatabaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Aes256Gcm" => Ok(Cipher::Aes256Gcm),
+            "ChaCha20Poly1305" => Ok(Cipher::ChaCha20Poly1305),
+            _ => Err(DatabaseError::UnknownCipher),
+        }
+    }
+}
+
+/// Key used to encrypt a database file.
+#[derive(Clone, PartialEq)]
+pub enum EncryptionKey {
+    /// Key is derived from the passphrase with Argon2id.
+    Passphrase(String),
+
+    /// Key is loaded from a key file that contains `KEY_LEN` bytes.
+    KeyFile(PathBuf),
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncryptionKey::Passphrase(_) => write!(f, "Passphrase(..)"),
+            EncryptionKey::KeyFile(path) => write!(f, "KeyFile({:?})", path),
+        }
+    }
+}
+
+/// Options for encrypting a database file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncryptionOptions {
+    /// Key of the database file.
+    pub key: EncryptionKey,
+
+    /// Cipher of the database file.
+    pub cipher: Cipher,
+}
+
+impl EncryptionOptions {
+    /// Creates encryption options with the default cipher.
+    pub fn new(key: EncryptionKey) -> Self {
+        Self {
+            key,
+            cipher: Cipher::default(),
+        }
+    }
+}
+
+/// Header of an encrypted payload.
+#[derive(Clone)]
+struct EncryptionHeader {
+    cipher: Cipher,
+    kdf: u8,
+    kdf_params: [u32; 3],
+    salt: [u8; SALT_LEN],
+}
+
+impl EncryptionHeader {
+    /// Parses the header from the start of an encrypted buffer.
+    /// 
+    /// Returns the header and the nonce.
+    fn parse(buf: &[u8]) -> io::Result<(Self, [u8; NONCE_LEN])> {
+        if buf.len() < HEADER_LEN || !buf.starts_with(&ENCRYPTED_PAYLOAD_MAGIC) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid encrypted database file header"
+            ));
+        }
+
+        let mut pos = ENCRYPTED_PAYLOAD_MAGIC.len();
+        let cipher = match Cipher::from_u8(buf[pos]) {
+            Some(cipher) => cipher,
+            None => return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                DatabaseError::UnknownCipher
+            )),
+        };
+        let kdf = buf[pos + 1];
+        pos += 2;
+
+        let mut kdf_params = [0u32; 3];
+        for param in kdf_params.iter_mut() {
+            let mut bytes = [0u8; 4];
+            bytes.copy_from_slice(&buf[pos..pos + 4]);
+            *param = u32::from_le_bytes(bytes);
+            pos += 4;
+        }
+        let [m_cost, t_cost, p_cost] = kdf_params;
+        if m_cost > MAX_ARGON2_M_COST || t_cost > MAX_ARGON2_T_COST || p_cost > MAX_ARGON2_P_COST {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid encrypted database file header: key derivation parameters are too large"
+            ));
+        }
+
+        let mut salt = [0u8; SALT_LEN];
+        salt.copy_from_slice(&buf[pos..pos + SALT_LEN]);
+        pos += SALT_LEN;
+
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce.copy_from_slice(&buf[pos..pos + NONCE_LEN]);
+
+        Ok((Self { cipher, kdf, kdf_params, salt }, nonce))
+    }
+
+    fn to_bytes(&self, nonce: &[u8; NONCE_LEN]) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(HEADER_LEN);
+        buf.extend_from_slice(&ENCRYPTED_PAYLOAD_MAGIC);
+        buf.push(self.cipher as u8);
+        buf.push(self.kdf);
+        for param in self.kdf_params {
+            buf.extend_from_slice(&param.to_le_bytes());
+        }
+        buf.extend_from_slice(&self.salt);
+        buf.extend_from_slice(nonce);
+
+        buf
+    }
+}
+
+/// Key of an unlocked database file.
+/// 
+/// The key is zeroed in memory when it is dropped.
+#[derive(Clone)]
+pub(crate) struct UnlockedKey {
+    header: EncryptionHeader,
+    key: Zeroizing<[u8; KEY_LEN]>,
+}
+
+impl UnlockedKey {
+    /// Creates a new key with a random salt.
+    fn generate(options: &EncryptionOptions) -> Result<Self, Box<dyn Error>> {
+        let mut salt = [0u8; SALT_LEN];
+        let (kdf, kdf_params) = match options.key {
+            EncryptionKey::Passphrase(_) => {
+                OsRng.fill_bytes(&mut salt);
+                let params = Params::default();
+                (KDF_ARGON2ID, [params.m_cost(), params.t_cost(), params.p_cost()])
+            },
+            EncryptionKey::KeyFile(_) => (KDF_KEY_FILE, [0; 3]),
+        };
+        let header = EncryptionHeader {
+            cipher: options.cipher,
+            kdf,
+            kdf_params,
+            salt,
+        };
+        let key = derive_key(&options.key, &header)?;
+
+        Ok(Self { header, key })
+    }
+
+    fn encrypt(&self, buf: &[u8]) -> io::Result<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let mut file_buf = self.header.to_bytes(&nonce);
+        let payload = Payload {
+            msg: buf,
+            aad: &file_buf,
+        };
+
+        let encrypted = match self.header.cipher {
+            Cipher::Aes256Gcm => Aes256Gcm::new((&*self.key).into())
+                .encrypt((&nonce).into(), payload),
+            Cipher::ChaCha20Poly1305 => ChaCha20Poly1305::new((&*self.key).into())
+                .encrypt((&nonce).into(), payload),
+        };
+        let encrypted = encrypted.map_err(|_| io::Error::other("Failed to encrypt database"))?;
+        file_buf.extend_from_slice(&encrypted);
+
+        Ok(file_buf)
+    }
+
+    fn decrypt(&self, buf: &[u8]) -> io::Result<Vec<u8>> {
+        let (_, nonce) = EncryptionHeader::parse(buf)?;
+        let payload = Payload {
+            msg: &buf[HEADER_LEN..],
+            aad: &buf[..HEADER_LEN],
+        };
+
+        let decrypted = match self.header.cipher {
+            Cipher::Aes256Gcm => Aes256Gcm::new((&*self.key).into())
+                .decrypt((&nonce).into(), payload),
+            Cipher::ChaCha20Poly1305 => ChaCha20Poly1305::new((&*self.key).into())
+                .decrypt((&nonce).into(), payload),
+        };
+
+        decrypted.map_err(|_| io::Error::new(
+            io::ErrorKind::InvalidData,
+            DatabaseError::InvalidKey
+        ))
+    }
+}
+
+/// Derives the key of a database file.
+fn derive_key(
+    key: &EncryptionKey,
+    header: &EncryptionHeader,
+) -> Result<Zeroizing<[u8; KEY_LEN]>, Box<dyn Error>>
+{
+    let mut derived_key = Zeroizing::new([0u8; KEY_LEN]);
+
+    match key {
+        EncryptionKey::Passphrase(passphrase) => {
+            if passphrase.is_empty() || header.kdf != KDF_ARGON2ID {
+                return Err(Box::new(DatabaseError::InvalidKey));
+            }
+            let [m_cost, t_cost, p_cost] = header.kdf_params;
+            let params = Params::new(m_cost, t_cost, p_cost, Some(KEY_LEN))
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+            Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                .hash_password_into(passphrase.as_bytes(), &header.salt, &mut *derived_key)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+        },
+        EncryptionKey::KeyFile(path) => {
+            if header.kdf != KDF_KEY_FILE {
+                return Err(Box::new(DatabaseError::InvalidKey));
+            }
+            let buf = Zeroizing::new(fs::read(path)?);
+            if buf.len() != KEY_LEN {
+                return Err(Box::new(DatabaseError::InvalidKeyFile));
+            }
+            derived_key.copy_from_slice(&buf);
+        },
+    }
+
+    Ok(derived_key)
+}
+
+/// Gets the keys of unlocked database files.
+/// 
+/// Keys are stored by canonical file path for the lifetime of the process.
+fn keyring() -> MutexGuard<'static, HashMap<PathBuf, UnlockedKey>> {
+    static KEYRING: OnceLock<Mutex<HashMap<PathBuf, UnlockedKey>>> = OnceLock::new();
+
+    KEYRING
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+fn keyring_path(file_path: &Path) -> PathBuf {
+    fs::canonicalize(file_path).unwrap_or_else(|_| PathBuf::from(file_path))
+}
+
+pub(crate) fn get_key(file_path: &Path) -> Option<UnlockedKey> {
+    keyring().get(&keyring_path(file_path)).cloned()
+}
+
+pub(crate) fn insert_key(file_path: &Path, key: UnlockedKey) {
+    keyring().insert(keyring_path(file_path), key);
+}
+
+pub(crate) fn take_key(file_path: &Path) -> Option<UnlockedKey> {
+    keyring().remove(&keyring_path(file_path))
+}
+
+/// Gets the encrypted payload of a database file buffer.
+/// 
+/// Returns `None` if the buffer is not encrypted.
+fn encrypted_payload(buf: &[u8]) -> Option<&[u8]> {
+    match FileHeader::read(buf) {
+        Ok(Some((header, payload))) if header.is_encrypted() => Some(payload),
+        _ => None,
+    }
+}
+
+/// Checks if a buffer is an encrypted database file.
+pub fn is_encrypted(buf: &[u8]) -> bool {
+    encrypted_payload(buf).is_some()
+}
+
+/// Gets the cipher of an encrypted database file buffer.
+pub fn encrypted_cipher(buf: &[u8]) -> Option<Cipher> {
+    match EncryptionHeader::parse(encrypted_payload(buf)?) {
+        Ok((header, _)) => Some(header.cipher),
+        Err(_) => None,
+    }
+}
+
+/// Checks if a database file buffer is encrypted and its key is not unlocked.
+pub fn is_locked(buf: &[u8], file_path: &Path) -> bool {
+    is_encrypted(buf) && get_key(file_path).is_none()
+}
+
+/// Encrypts the payload of a serialized database buffer
+/// if the database file has an unlocked key.
+pub(crate) fn encrypt_database_buf(buf: &[u8], file_path: &Path) -> io::Result<Option<Vec<u8>>> {
+    let key = match get_key(file_path) {
+        Some(key) => key,
+        None => return Ok(None),
+    };
+    let (header, payload) = match FileHeader::read(buf)? {
+        Some(header_and_payload) => header_and_payload,
+        None => return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            DatabaseError::InvalidFileHeader
+        )),
+    };
+
+    let encrypted_payload = key.encrypt(payload)?;
+    let header = FileHeader::new(header.flags | FLAG_ENCRYPTED, header.compression, &encrypted_payload);
+
+    Ok(Some(header.to_file_buf(&encrypted_payload)))
+}
+
+/// Decrypts an encrypted database file buffer using its unlocked key.
+/// 
+/// Returns a buffer that can be deserialized.
+/// Buffers that are not encrypted are returned as they are.
+pub(crate) fn decrypt_database_buf(buf: Vec<u8>, file_path: &Path) -> io::Result<Vec<u8>> {
+    if !is_encrypted(&buf) {
+        return Ok(buf);
+    }
+
+    let key = match get_key(file_path) {
+        Some(key) => key,
+        None => return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            DatabaseError::Locked
+        )),
+    };
+
+    let (header, payload) = match FileHeader::read(&buf)? {
+        Some(header_and_payload) => header_and_payload,
+        None => return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            DatabaseError::InvalidFileHeader
+        )),
+    };
+    header.verify(payload)?;
+    let decrypted_payload = key.decrypt(payload)?;
+    let header = FileHeader {
+        flags: header.flags & !FLAG_ENCRYPTED,
+        checksum: crc32fast::hash(&decrypted_payload),
+        ..header
+    };
+
+    Ok(header.to_file_buf(&decrypted_payload))
+}
+
+/// Creates a new unlocked key for a database file.
+pub(crate) fn generate_key(options: &EncryptionOptions) -> Result<UnlockedKey, Box<dyn Error>> {
+    UnlockedKey::generate(options)
+}
+
+/// Unlocks an encrypted database file with its key.
+/// 
+/// The key is kept in memory so the database can be read and written
+/// until it is locked again.
+pub fn unlock_database_file(
+    file_path: &Path,
+    key: &EncryptionKey,
+) -> Result<(), Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let buf = fs::read(file_path)?;
+    if !is_encrypted(&buf) {
+        return Err(Box::new(DatabaseError::NotEncrypted));
+    }
+
+    let payload = encrypted_payload(&buf).unwrap_or_default();
+    let (header, _) = EncryptionHeader::parse(payload)?;
+    let unlocked_key = UnlockedKey {
+        key: derive_key(key, &header)?,
+        header,
+    };
+    unlocked_key.decrypt(payload)?;
+    insert_key(file_path, unlocked_key);
+
+    Ok(())
+}
+
+/// Locks a database file by removing its key from memory.
+pub fn lock_database_file(file_path: &Path) -> Result<(), Box<dyn Error>> {
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    take_key(file_path);
+
+    Ok(())
+}
+
+/// Creates a key file with a random key.
+/// 
+/// Fails if the file already exists. On Unix the file
+/// can be read and written only by its owner.
+pub fn create_key_file(file_path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = match options.open(file_path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            return Err(Box::new(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "Key file already exists"
+            )));
+        },
+        Err(e) => return Err(Box::new(e)),
+    };
+
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    OsRng.fill_bytes(&mut *key);
+    file.write_all(&*key)?;
+    file.sync_all()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use crate::storage::pb::Compression;
+
+    #[test]
+    fn test_encrypt_and_decrypt_buf() {
+        let buf = b"database".to_vec();
+
+        for cipher in [Cipher::Aes256Gcm, Cipher::ChaCha20Poly1305] {
+            let options = EncryptionOptions {
+                key: EncryptionKey::Passphrase("secret".to_string()),
+                cipher,
+            };
+            let key = UnlockedKey::generate(&options).unwrap();
+            let encrypted = key.encrypt(&buf).unwrap();
+            let file_buf = FileHeader::new(FLAG_ENCRYPTED, Compression::None, &encrypted).to_file_buf(&encrypted);
+            assert!(is_encrypted(&file_buf));
+            assert!(!is_encrypted(&encrypted));
+            assert_eq!(encrypted_cipher(&file_buf), Some(cipher));
+            assert_eq!(key.decrypt(&encrypted).unwrap(), buf);
+
+            let mut tampered = encrypted.clone();
+            let last = tampered.len() - 1;
+            tampered[last] ^= 1;
+            assert!(key.decrypt(&tampered).is_err());
+        }
+    }
+
+    #[test]
+    fn test_derive_key_from_key_file() {
+        let dir = tempdir().unwrap();
+        let key_file_path = dir.path().join("db.key");
+        create_key_file(&key_file_path).unwrap();
+        assert_eq!(fs::read(&key_file_path).unwrap().len(), KEY_LEN);
+        assert!(create_key_file(&key_file_path).is_err());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&key_file_path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let key = EncryptionKey::KeyFile(key_file_path.clone());
+        let unlocked_key = UnlockedKey::generate(&EncryptionOptions::new(key.clone())).unwrap();
+        assert_eq!(derive_key(&key, &unlocked_key.header).unwrap(), unlocked_key.key);
+
+        let passphrase = EncryptionKey::Passphrase("secret".to_string());
+        assert!(derive_key(&passphrase, &unlocked_key.header).is_err());
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_parse_header_rejects_large_kdf_params() {
+        let options = EncryptionOptions::new(EncryptionKey::Passphrase("secret".to_string()));
+        let key = UnlockedKey::generate(&options).unwrap();
+        let encrypted = key.encrypt(b"database").unwrap();
+        assert!(EncryptionHeader::parse(&encrypted).is_ok());
+
+        // First Argon2 parameter is the memory cost
+        let mut modified = encrypted.clone();
+        let pos = ENCRYPTED_PAYLOAD_MAGIC.len() + 2;
+        modified[pos..pos + 4].copy_from_slice(&(MAX_ARGON2_M_COST + 1).to_le_bytes());
+        assert!(EncryptionHeader::parse(&modified).is_err());
+    }
+}
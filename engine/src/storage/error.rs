@@ -17,6 +17,60 @@ pub enum DatabaseError {
 
     /// Database name contains whitespace character.
     NameHasWhitespace,
+
+    /// Database file compression does not exist.
+    UnknownCompression,
+
+    /// Database file encryption cipher does not exist.
+    UnknownCipher,
+
+    /// Database is encrypted and has not been unlocked.
+    Locked,
+
+    /// Database is not encrypted.
+    NotEncrypted,
+
+    /// Database is already encrypted.
+    AlreadyEncrypted,
+
+    /// Encryption key does not match the database.
+    InvalidKey,
+
+    /// Key file does not contain a valid key.
+    InvalidKeyFile,
+
+    /// Database file header is invalid.
+    InvalidFileHeader,
+
+    /// Database file payload does not match the checksum in the file header.
+    ChecksumMismatch,
+
+    /// Database file format version is newer than the engine supports.
+    UnsupportedFormatVersion,
+
+    /// Database file header has flags that the engine doesn't know.
+    UnsupportedFileFlags,
+
+    /// Database journal is already enabled.
+    JournalEnabled,
+
+    /// Database journal is not enabled.
+    JournalNotEnabled,
+
+    /// Database journal cannot be used with encrypted databases.
+    JournalNotSupported,
+
+    /// Database journal is invalid.
+    InvalidJournal,
+
+    /// Database journal has no entry at the requested point.
+    JournalTargetNotFound,
+
+    /// Point in database journal is not a sequence number or a time.
+    InvalidJournalTarget,
+
+    /// Change stream events after the sequence number are not kept.
+    ChangeStreamResumePointNotFound,
 }
 
 impl fmt::Display for DatabaseError {
@@ -29,6 +83,24 @@ impl fmt::Display for DatabaseError {
                 DatabaseError::NotFound => "Database was not found",
                 DatabaseError::EmptyName => "Empty database name not allowed",
                 DatabaseError::NameHasWhitespace => "Whitespace not allowed in database name",
+                DatabaseError::UnknownCompression => "Database file compression does not exist",
+                DatabaseError::UnknownCipher => "Database file encryption cipher does not exist",
+                DatabaseError::Locked => "Database is encrypted. Unlock it with its key first",
+                DatabaseError::NotEncrypted => "Database is not encrypted",
+                DatabaseError::AlreadyEncrypted => "Database is already encrypted",
+                DatabaseError::InvalidKey => "Invalid encryption key",
+                DatabaseError::InvalidKeyFile => "Key file must contain exactly 32 bytes",
+                DatabaseError::InvalidFileHeader => "Database file header is invalid",
+                DatabaseError::ChecksumMismatch => "Database file checksum does not match. The file is corrupted",
+                DatabaseError::UnsupportedFormatVersion => "Database file format is not supported. It was written by a newer engine version",
+                DatabaseError::UnsupportedFileFlags => "Database file has unknown flags. It was written by a newer engine version",
+                DatabaseError::JournalEnabled => "Database journal is already enabled",
+                DatabaseError::JournalNotEnabled => "Database journal is not enabled",
+                DatabaseError::JournalNotSupported => "Database journal cannot be used with encrypted databases",
+                DatabaseError::InvalidJournal => "Database journal is invalid",
+                DatabaseError::JournalTargetNotFound => "Database journal has no entry at the given point",
+                DatabaseError::InvalidJournalTarget => "Journal point must be a sequence number or a time such as '2024-01-31 12:00:00'",
+                DatabaseError::ChangeStreamResumePointNotFound => "Change stream cannot be resumed after the given sequence number",
             }
         )
     }
@@ -55,6 +127,15 @@ pub enum CollectionError {
 
     /// Collection name contains whitespace character.
     NameHasWhitespace,
+
+    /// Document id strategy does not exist.
+    UnknownIdStrategy,
+
+    /// Time-to-live policy has no field.
+    EmptyTtlField,
+
+    /// Capped collection has no max document count or max size.
+    EmptyCappedLimits,
 }
 
 impl fmt::Display for CollectionError {
@@ -68,6 +149,9 @@ impl fmt::Display for CollectionError {
                 CollectionError::HasDocuments => "Collection has documents",
                 CollectionError::EmptyName => "Empty collection name not allowed",
                 CollectionError::NameHasWhitespace => "Whitespaces not allowed in collection name",
+                CollectionError::UnknownIdStrategy => "Document id strategy does not exist",
+                CollectionError::EmptyTtlField => "Time-to-live policy must have a field",
+                CollectionError::EmptyCappedLimits => "Capped collection must have a max document count or a max size",
             }
         )
     }
@@ -86,6 +170,30 @@ pub enum DocumentError {
 
     /// Document has a field name that contains whitespace character.
     FieldNameHasWhitespace,
+
+    /// Document id was not given but the collection's id strategy requires it.
+    IdRequired,
+
+    /// Document id was given but the collection's id strategy generates ids.
+    IdNotAllowed,
+
+    /// Document id type does not match the collection's id strategy.
+    IdTypeMismatch,
+
+    /// Document id is not valid.
+    InvalidId,
+
+    /// Document with the same id already exists.
+    IdExists,
+
+    /// Document already has a field with the same name.
+    FieldExists,
+
+    /// Document is larger than the max size of a capped collection.
+    ExceedsCappedSize,
+
+    /// Document revision was not found.
+    RevisionNotFound,
 }
 
 impl fmt::Display for DocumentError {
@@ -97,6 +205,14 @@ impl fmt::Display for DocumentError {
                 DocumentError::NotFound => "Document was not found",
                 DocumentError::EmptyFieldName => "Empty field name not allowed",
                 DocumentError::FieldNameHasWhitespace => "Whitespaces not allowed in field name",
+                DocumentError::IdRequired => "Document id is required by the collection's id strategy",
+                DocumentError::IdNotAllowed => "Document id is generated by the collection's id strategy",
+                DocumentError::IdTypeMismatch => "Document id type does not match the collection's id strategy",
+                DocumentError::InvalidId => "Invalid document id",
+                DocumentError::IdExists => "Document with the same id already exists",
+                DocumentError::FieldExists => "Field already exists",
+                DocumentError::ExceedsCappedSize => "Document is larger than the max size of the capped collection",
+                DocumentError::RevisionNotFound => "Document revision was not found",
             }
         )
     }
@@ -104,6 +220,263 @@ impl fmt::Display for DocumentError {
 
 impl Error for DocumentError {}
 
+/// Error type for collection schema definition errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaError {
+    /// Field schema has an empty field name.
+    EmptyFieldName,
+
+    /// Field is defined more than once in the schema.
+    DuplicateField(String),
+
+    /// Field schema has a data type that does not exist.
+    UnknownDataType(String, String),
+
+    /// Field schema has a pattern that is not a valid regular expression.
+    InvalidPattern(String),
+
+    /// Field schema has a min value that is greater than its max value.
+    MinGreaterThanMax(String),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::EmptyFieldName => write!(f, "Empty field name not allowed in schema"),
+            SchemaError::DuplicateField(field) => write!(f, "Field '{}' is defined more than once", field),
+            SchemaError::UnknownDataType(field, data_type) => {
+                write!(f, "Field '{}' has data type '{}' that does not exist", field, data_type)
+            },
+            SchemaError::InvalidPattern(field) => write!(f, "Field '{}' has invalid pattern", field),
+            SchemaError::MinGreaterThanMax(field) => {
+                write!(f, "Field '{}' has min value greater than max value", field)
+            },
+        }
+    }
+}
+
+impl Error for SchemaError {}
+
+/// Error type for database backup errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackupError {
+    /// Target directory already has a backup.
+    Exists,
+
+    /// Backup manifest was not found.
+    ManifestNotFound,
+
+    /// Backup manifest cannot be parsed.
+    InvalidManifest(String),
+
+    /// Backup format version is newer than the engine supports.
+    UnsupportedFormatVersion(u32),
+
+    /// Database is not in the backup.
+    DatabaseNotFound(String),
+
+    /// Backed up database file is missing.
+    FileMissing(String),
+
+    /// Backed up database file does not match its checksum.
+    ChecksumMismatch(String),
+}
+
+impl fmt::Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackupError::Exists => write!(f, "Target directory already has a backup"),
+            BackupError::ManifestNotFound => write!(f, "Backup manifest was not found"),
+            BackupError::InvalidManifest(e) => write!(f, "Backup manifest is invalid: {}", e),
+            BackupError::UnsupportedFormatVersion(version) => {
+                write!(f, "Backup format version {} is not supported", version)
+            },
+            BackupError::DatabaseNotFound(name) => write!(f, "Database '{}' is not in the backup", name),
+            BackupError::FileMissing(file_name) => write!(f, "Backup file '{}' is missing", file_name),
+            BackupError::ChecksumMismatch(file_name) => {
+                write!(f, "Backup file '{}' does not match its checksum", file_name)
+            },
+        }
+    }
+}
+
+impl Error for BackupError {}
+
+/// Error type for JSON import errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonError {
+    /// Input is not valid JSON.
+    Parse(String),
+
+    /// Input has a JSON value that cannot be imported, e.g. an array instead of an object.
+    UnexpectedValue(String),
+
+    /// JSON document cannot be imported.
+    /// Has the location of the document in the input, e.g. `line 3`, and the reason.
+    InvalidDocument {
+        location: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::Parse(e) => write!(f, "Invalid JSON: {}", e),
+            JsonError::UnexpectedValue(e) => write!(f, "Unexpected JSON value: {}", e),
+            JsonError::InvalidDocument { location, message } => {
+                write!(f, "Invalid document at {}: {}", location, message)
+            },
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+/// Error type for CSV import and export errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CsvError {
+    /// Input is not valid CSV.
+    Parse(String),
+
+    /// Column names are required when the CSV file has no header row.
+    ColumnsRequired,
+
+    /// Column is not in the header row of the CSV file.
+    ColumnNotFound(String),
+
+    /// Column has a data type that doesn't exist.
+    UnknownDataType(String),
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::Parse(e) => write!(f, "Invalid CSV: {}", e),
+            CsvError::ColumnsRequired => write!(f, "Columns must be given when CSV file has no header row"),
+            CsvError::ColumnNotFound(column) => write!(f, "Column '{}' is not in the header row", column),
+            CsvError::UnknownDataType(data_type) => write!(f, "Unknown data type '{}'", data_type),
+        }
+    }
+}
+
+impl Error for CsvError {}
+
+/// Error type for database directory dump errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DumpError {
+    /// Dump file already exists.
+    Exists,
+
+    /// Dump file is not valid.
+    InvalidDump(String),
+
+    /// Dump format version is newer than the engine supports.
+    UnsupportedFormatVersion(u32),
+
+    /// Database in the dump already exists in the target directory.
+    DatabaseExists(String),
+}
+
+impl fmt::Display for DumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DumpError::Exists => write!(f, "Dump file already exists"),
+            DumpError::InvalidDump(e) => write!(f, "Dump is invalid: {}", e),
+            DumpError::UnsupportedFormatVersion(version) => {
+                write!(f, "Dump format version {} is not supported", version)
+            },
+            DumpError::DatabaseExists(name) => write!(f, "Database '{}' already exists", name),
+        }
+    }
+}
+
+impl Error for DumpError {}
+
+/// Error type for documents that don't follow the collection's schema.
+/// 
+/// Lists every violation of the schema in the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaValidationError {
+    pub violations: Vec<SchemaViolation>,
+}
+
+/// Schema violation of a document field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// Name of the field that violates the schema.
+    pub field: String,
+
+    /// How the field violates the schema.
+    pub kind: SchemaViolationKind,
+}
+
+/// Kind of schema violation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaViolationKind {
+    /// Required field is missing.
+    MissingField,
+
+    /// Field is not in the schema and unknown fields are forbidden.
+    UnknownField,
+
+    /// Field has a data type that is not allowed.
+    /// 
+    /// Contains the allowed data types and the found data type.
+    DataType(Vec<String>, String),
+
+    /// Value is less than the min value.
+    LessThanMin,
+
+    /// Value is greater than the max value.
+    GreaterThanMax,
+
+    /// Text value is longer than the max length.
+    TooLong(u64),
+
+    /// Text value does not match the pattern.
+    PatternMismatch,
+
+    /// Value is not one of the allowed values.
+    ValueNotAllowed,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field '{}': ", self.field)?;
+        match &self.kind {
+            SchemaViolationKind::MissingField => write!(f, "required field is missing"),
+            SchemaViolationKind::UnknownField => write!(f, "field is not in the schema"),
+            SchemaViolationKind::DataType(allowed, found) => {
+                write!(f, "data type '{}' is not one of '{}'", found, allowed.join("', '"))
+            },
+            SchemaViolationKind::LessThanMin => write!(f, "value is less than min value"),
+            SchemaViolationKind::GreaterThanMax => write!(f, "value is greater than max value"),
+            SchemaViolationKind::TooLong(max_length) => {
+                write!(f, "text is longer than {} characters", max_length)
+            },
+            SchemaViolationKind::PatternMismatch => write!(f, "text does not match the pattern"),
+            SchemaViolationKind::ValueNotAllowed => write!(f, "value is not one of the allowed values"),
+        }
+    }
+}
+
+impl fmt::Display for SchemaValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let violations: Vec<String> = self.violations
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect();
+        write!(
+            f,
+            "Document does not follow the collection schema: {}",
+            violations.join("; ")
+        )
+    }
+}
+
+impl Error for SchemaValidationError {}
+
 /// Error type for document data type parse errors.
 #[derive(Debug)]
 pub enum ParseError {
@@ -113,6 +486,11 @@ pub enum ParseError {
     Decimal,
     Bool,
     Text,
+    Decimal128,
+    UInt32,
+    UInt64,
+    Int128,
+    UInt128,
 }
 
 impl fmt::Display for ParseError {
@@ -127,6 +505,11 @@ impl fmt::Display for ParseError {
                 ParseError::Decimal => "'Decimal'",
                 ParseError::Bool => "'Bool'",
                 ParseError::Text => "'Text'",
+                ParseError::Decimal128 => "'Decimal128'",
+                ParseError::UInt32 => "'UInt32'",
+                ParseError::UInt64 => "'UInt64'",
+                ParseError::Int128 => "'Int128'",
+                ParseError::UInt128 => "'UInt128'",
             }
         )
     }
@@ -142,6 +525,9 @@ pub struct DatabaseOperationError {
 
     /// Message telling the cause of error.
     pub message: String,
+
+    /// Possible schema violations if a document didn't follow the collection's schema.
+    pub validation_error: Option<SchemaValidationError>,
 }
 
 /// Kind of database operation error.
@@ -162,6 +548,33 @@ pub enum DatabaseOperationErrorKind {
     /// Failed to find many databases.
     FindDatabaseMany,
 
+    /// Failed to unlock database.
+    UnlockDatabase,
+
+    /// Failed to check database integrity.
+    CheckDatabase,
+
+    /// Failed to back up database.
+    BackupDatabase,
+
+    /// Failed to restore database.
+    RestoreDatabase,
+
+    /// Failed to enable, disable or read database journal.
+    Journal,
+
+    /// Failed to create key file.
+    CreateKeyFile,
+
+    /// Failed to watch database changes.
+    WatchDatabase,
+
+    /// Failed to export data.
+    ExportData,
+
+    /// Failed to import data.
+    ImportData,
+
     /// Failed to create collection.
     CreateCollection,
 
@@ -174,6 +587,9 @@ pub enum DatabaseOperationErrorKind {
     /// Failed to find many collections.
     FindCollectionMany,
 
+    /// Failed to modify collection.
+    ModifyCollection,
+
     /// Failed to create document.
     CreateDocument,
 
@@ -201,10 +617,20 @@ impl fmt::Display for DatabaseOperationError {
                 DatabaseOperationErrorKind::ModifyDatabase => "Failed to modify database",
                 DatabaseOperationErrorKind::FindDatabaseOne => "Failed to find database",
                 DatabaseOperationErrorKind::FindDatabaseMany => "Failed to find databases",
+                DatabaseOperationErrorKind::UnlockDatabase => "Failed to unlock database",
+                DatabaseOperationErrorKind::CheckDatabase => "Failed to check database",
+                DatabaseOperationErrorKind::BackupDatabase => "Failed to back up database",
+                DatabaseOperationErrorKind::RestoreDatabase => "Failed to restore database",
+                DatabaseOperationErrorKind::Journal => "Database journal operation failed",
+                DatabaseOperationErrorKind::ExportData => "Failed to export data",
+                DatabaseOperationErrorKind::ImportData => "Failed to import data",
+                DatabaseOperationErrorKind::CreateKeyFile => "Failed to create key file",
+                DatabaseOperationErrorKind::WatchDatabase => "Failed to watch database",
                 DatabaseOperationErrorKind::CreateCollection => "Failed to create collection",
                 DatabaseOperationErrorKind::DeleteCollection => "Failed to delete collection",
                 DatabaseOperationErrorKind::FindCollectionOne => "Failed to find collection",
                 DatabaseOperationErrorKind::FindCollectionMany => "Failed to find collections",
+                DatabaseOperationErrorKind::ModifyCollection => "Failed to modify collection",
                 DatabaseOperationErrorKind::CreateDocument => "Failed to create document",
                 DatabaseOperationErrorKind::DeleteDocument => "Failed to delete document",
                 DatabaseOperationErrorKind::ReplaceDocument => "Failed to replace document",
@@ -221,7 +647,19 @@ impl DatabaseOperationError {
     pub fn new(kind: DatabaseOperationErrorKind, message: String) -> Self {
         Self {
             kind,
-            message
+            message,
+            validation_error: None,
+        }
+    }
+
+    /// Creates a new `DatabaseOperationError` from a storage operation error.
+    /// 
+    /// Keeps the schema violations if the error is `SchemaValidationError`.
+    pub fn from_storage_error(kind: DatabaseOperationErrorKind, err: Box<dyn Error>) -> Self {
+        Self {
+            kind,
+            message: err.to_string(),
+            validation_error: err.downcast_ref::<SchemaValidationError>().cloned(),
         }
     }
 }
@@ -0,0 +1,204 @@
+// Database file header
+// The header identifies database files and describes how the payload after it is stored.
+
+use std::io;
+use crate::storage::{
+    error::DatabaseError,
+    pb::Compression,
+};
+
+/// Magic bytes at the start of database files.
+/// 
+/// Database files written before the header was added don't start with these.
+pub const FILE_MAGIC: [u8; 4] = [0x00, b'D', b'B', b'F'];
+
+/// Current database file format version.
+/// 
+/// Files with a newer format version cannot be read.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// Length of database file header in bytes.
+pub const FILE_HEADER_LEN: usize = 20;
+
+/// Flag telling the payload is encrypted.
+pub const FLAG_ENCRYPTED: u16 = 1;
+
+/// All flags that this format version knows.
+const KNOWN_FLAGS: u16 = FLAG_ENCRYPTED;
+
+/// Header at the start of database files.
+/// 
+/// The header has magic bytes, the file format version, the engine version
+/// that last wrote the file, flags, the compression and a CRC-32 checksum of the payload.
+/// Integers are stored in little-endian byte order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileHeader {
+    pub format_version: u16,
+    pub engine_version: [u16; 3],
+    pub flags: u16,
+    pub compression: Compression,
+    pub checksum: u32,
+}
+
+impl FileHeader {
+    /// Creates a header for the payload using the current format and engine versions.
+    pub fn new(flags: u16, compression: Compression, payload: &[u8]) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            engine_version: current_engine_version(),
+            flags,
+            compression,
+            checksum: crc32fast::hash(payload),
+        }
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.flags & FLAG_ENCRYPTED != 0
+    }
+
+    /// Gets the engine version that last wrote the file.
+    pub fn engine_version(&self) -> String {
+        let [major, minor, patch] = self.engine_version;
+        format!("{}.{}.{}", major, minor, patch)
+    }
+
+    /// Reads the header from the start of a buffer.
+    /// 
+    /// Returns `None` if the buffer doesn't start with the magic bytes.
+    /// Otherwise returns the header and the payload after it.
+    /// The checksum is not verified.
+    pub fn read(buf: &[u8]) -> io::Result<Option<(Self, &[u8])>> {
+        if !buf.starts_with(&FILE_MAGIC) {
+            return Ok(None);
+        }
+        if buf.len() < FILE_HEADER_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                DatabaseError::InvalidFileHeader
+            ));
+        }
+
+        let u16_at = |pos: usize| u16::from_le_bytes([buf[pos], buf[pos + 1]]);
+        let format_version = u16_at(4);
+        if format_version > FORMAT_VERSION || format_version == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                DatabaseError::UnsupportedFormatVersion
+            ));
+        }
+
+        let flags = u16_at(12);
+        if flags & !KNOWN_FLAGS != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                DatabaseError::UnsupportedFileFlags
+            ));
+        }
+
+        let compression = match Compression::from_i32(i32::from(buf[14])) {
+            Some(compression) => compression,
+            None => return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                DatabaseError::UnknownCompression
+            )),
+        };
+
+        let header = Self {
+            format_version,
+            engine_version: [u16_at(6), u16_at(8), u16_at(10)],
+            flags,
+            compression,
+            checksum: u32::from_le_bytes([buf[16], buf[17], buf[18], buf[19]]),
+        };
+
+        Ok(Some((header, &buf[FILE_HEADER_LEN..])))
+    }
+
+    /// Verifies the payload against the checksum.
+    pub fn verify(&self, payload: &[u8]) -> io::Result<()> {
+        if crc32fast::hash(payload) != self.checksum {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                DatabaseError::ChecksumMismatch
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Writes the header followed by the payload to a new buffer.
+    pub fn to_file_buf(&self, payload: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(FILE_HEADER_LEN + payload.len());
+        buf.extend_from_slice(&FILE_MAGIC);
+        buf.extend_from_slice(&self.format_version.to_le_bytes());
+        for version in self.engine_version {
+            buf.extend_from_slice(&version.to_le_bytes());
+        }
+        buf.extend_from_slice(&self.flags.to_le_bytes());
+        buf.push(self.compression as u8);
+        // Reserved
+        buf.push(0);
+        buf.extend_from_slice(&self.checksum.to_le_bytes());
+        buf.extend_from_slice(payload);
+
+        buf
+    }
+}
+
+/// Gets the version of this engine.
+fn current_engine_version() -> [u16; 3] {
+    [
+        env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap_or_default(),
+        env!("CARGO_PKG_VERSION_MINOR").parse().unwrap_or_default(),
+        env!("CARGO_PKG_VERSION_PATCH").parse().unwrap_or_default(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn database_error(err: &io::Error) -> Option<&DatabaseError> {
+        err.get_ref().and_then(|e| e.downcast_ref::<DatabaseError>())
+    }
+
+    #[test]
+    fn test_read_file_header() {
+        let payload = b"payload";
+        let header = FileHeader::new(FLAG_ENCRYPTED, Compression::Zstd, payload);
+        let buf = header.to_file_buf(payload);
+        assert_eq!(buf.len(), FILE_HEADER_LEN + payload.len());
+
+        let (read_header, read_payload) = FileHeader::read(&buf).unwrap().unwrap();
+        assert_eq!(read_header, header);
+        assert_eq!(read_payload, payload);
+        assert!(read_header.is_encrypted());
+        assert_eq!(read_header.engine_version(), env!("CARGO_PKG_VERSION"));
+        assert!(read_header.verify(read_payload).is_ok());
+
+        assert!(FileHeader::read(b"\x0a\x04test").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_read_file_header_errors() {
+        let payload = b"payload";
+        let buf = FileHeader::new(0, Compression::None, payload).to_file_buf(payload);
+
+        let (header, _) = FileHeader::read(&buf).unwrap().unwrap();
+        let err = header.verify(b"changed").unwrap_err();
+        assert!(matches!(database_error(&err), Some(DatabaseError::ChecksumMismatch)));
+
+        let mut newer_buf = buf.clone();
+        newer_buf[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        let err = FileHeader::read(&newer_buf).unwrap_err();
+        assert!(matches!(database_error(&err), Some(DatabaseError::UnsupportedFormatVersion)));
+
+        let mut flagged_buf = buf.clone();
+        flagged_buf[12..14].copy_from_slice(&(FLAG_ENCRYPTED << 1).to_le_bytes());
+        let err = FileHeader::read(&flagged_buf).unwrap_err();
+        assert!(matches!(database_error(&err), Some(DatabaseError::UnsupportedFileFlags)));
+
+        let err = FileHeader::read(&buf[..FILE_HEADER_LEN - 1]).unwrap_err();
+        assert!(matches!(database_error(&err), Some(DatabaseError::InvalidFileHeader)));
+    }
+}
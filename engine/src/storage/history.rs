@@ -0,0 +1,375 @@
+// Document revision history.
+// Collections with history mode keep the previous version of each replaced,
+// updated or deleted document as a revision. Deleted documents are kept as tombstones.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::Path,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use crate::storage::{
+    error::{
+        DatabaseError,
+        CollectionError,
+        DocumentError,
+    },
+    pb,
+    pb::document::Id,
+    document::DocumentDto,
+    journal::timestamp_to_datetime,
+    serialize_database,
+    deserialize_database,
+    read_database_file,
+    write_database_to_file,
+};
+
+/// Document revision data transfer object (DTO).
+/// 
+/// Exposes a previous version of a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentRevisionDto {
+    /// Revision number. Revisions of a document are numbered from 1.
+    pub revision: u64,
+
+    /// Time when the version was replaced or deleted.
+    pub timestamp: DateTime<Utc>,
+
+    /// Whether the document was deleted. Deleted documents can be restored from their tombstone.
+    pub deleted: bool,
+
+    /// Document before it was replaced, updated or deleted.
+    pub document: DocumentDto,
+}
+
+impl From<&pb::DocumentRevision> for DocumentRevisionDto {
+    fn from(revision: &pb::DocumentRevision) -> Self {
+        let document = match &revision.document {
+            Some(document) => DocumentDto::from(document),
+            None => DocumentDto::from(&pb::Document::default()),
+        };
+
+        Self {
+            revision: revision.revision,
+            timestamp: timestamp_to_datetime(revision.timestamp),
+            deleted: revision.deleted,
+            document,
+        }
+    }
+}
+
+impl pb::DocumentRevision {
+    pub fn document_id(&self) -> Option<&Id> {
+        self.document.as_ref().and_then(|document| document.id())
+    }
+}
+
+// Implements methods for protobuf type
+impl pb::Collection {
+    /// Keeps the previous version of a document as a revision
+    /// if this collection has history mode.
+    /// 
+    /// `deleted` makes the revision a tombstone of a deleted document.
+    pub fn record_revision(&mut self, document: pb::Document, deleted: bool) {
+        if !self.history {
+            return;
+        }
+
+        let revision = self.revisions
+            .iter()
+            .filter(|revision| revision.document_id() == document.id())
+            .map(|revision| revision.revision)
+            .max()
+            .unwrap_or(0) + 1;
+
+        self.revisions.push(pb::DocumentRevision {
+            revision,
+            timestamp: Utc::now().timestamp_micros(),
+            document: Some(document),
+            deleted,
+        });
+    }
+
+    /// Keeps the previous versions of documents as revisions
+    /// if this collection has history mode.
+    /// 
+    /// The latest revision numbers are found once for all documents,
+    /// so this should be used instead of `record_revision` when many documents change.
+    /// 
+    /// `deleted` makes the revisions tombstones of deleted documents.
+    pub fn record_revisions(&mut self, documents: Vec<pb::Document>, deleted: bool) {
+        if !self.history || documents.is_empty() {
+            return;
+        }
+
+        let mut last_revisions: HashMap<String, u64> = HashMap::new();
+        for revision in self.revisions.iter() {
+            let last_revision = last_revisions
+                .entry(format!("{:?}", revision.document_id()))
+                .or_insert(0);
+            *last_revision = (*last_revision).max(revision.revision);
+        }
+
+        let timestamp = Utc::now().timestamp_micros();
+        for document in documents {
+            let last_revision = last_revisions
+                .entry(format!("{:?}", document.id()))
+                .or_insert(0);
+            *last_revision += 1;
+
+            self.revisions.push(pb::DocumentRevision {
+                revision: *last_revision,
+                timestamp,
+                document: Some(document),
+                deleted,
+            });
+        }
+    }
+
+    /// Gets the revisions of a document in the order they were made.
+    pub fn document_revisions(&self, document_id: &Id) -> Vec<&pb::DocumentRevision> {
+        self.revisions
+            .iter()
+            .filter(|revision| revision.document_id() == Some(document_id))
+            .collect()
+    }
+
+    /// Removes revisions that were made before the time.
+    /// 
+    /// Returns the number of removed revisions.
+    pub fn remove_revisions_before(&mut self, timestamp: i64) -> usize {
+        let revision_count = self.revisions.len();
+        self.revisions.retain(|revision| revision.timestamp >= timestamp);
+
+        revision_count - self.revisions.len()
+    }
+}
+
+/// Finds a collection in a database by name.
+fn find_collection_mut<'a>(
+    database: &'a mut pb::Database,
+    collection_name: &str,
+) -> Result<&'a mut pb::Collection, CollectionError>
+{
+    database.collections
+        .iter_mut()
+        .find(|collection| collection.name == collection_name)
+        .ok_or(CollectionError::NotFound)
+}
+
+/// Enables or disables a collection's history mode.
+/// 
+/// Existing revisions are kept when history mode is disabled.
+/// 
+/// Writes the modified database to the database file.
+pub fn set_collection_history_in_database(
+    collection_name: &str,
+    file_path: &Path,
+    enabled: bool,
+) -> Result<(), Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    let collection = find_collection_mut(&mut database, collection_name)?;
+    collection.history = enabled;
+
+    let buf = serialize_database(&database)?;
+    write_database_to_file(&buf, file_path)?;
+
+    Ok(())
+}
+
+/// Finds the revisions of a document in a collection.
+/// 
+/// The document can be deleted.
+/// 
+/// Returns the revisions in the order they were made.
+pub fn find_document_revisions_in_collection(
+    file_path: &Path,
+    collection_name: &str,
+    document_id: &Id,
+) -> Result<Vec<DocumentRevisionDto>, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    let collection = find_collection_mut(&mut database, collection_name)?;
+
+    Ok(collection
+        .document_revisions(document_id)
+        .into_iter()
+        .map(DocumentRevisionDto::from)
+        .collect())
+}
+
+/// Finds a revision of a document in a collection by revision number.
+/// 
+/// Returns `None` if the revision doesn't exist.
+pub fn find_document_revision_in_collection(
+    file_path: &Path,
+    collection_name: &str,
+    document_id: &Id,
+    revision: u64,
+) -> Result<Option<DocumentRevisionDto>, Box<dyn Error>>
+{
+    let revisions = find_document_revisions_in_collection(file_path, collection_name, document_id)?;
+
+    Ok(revisions
+        .into_iter()
+        .find(|document_revision| document_revision.revision == revision))
+}
+
+/// Restores a document to a revision.
+/// 
+/// If the document exists, its data is replaced with the revision's data
+/// and its current version is kept as a new revision.
+/// If the document was deleted, it is inserted again with the same id.
+/// The restored document is validated against the collection's schema.
+/// If the collection is capped, the oldest other documents are evicted.
+/// 
+/// Writes the modified database to the database file.
+/// 
+/// Returns the restored document.
+pub fn restore_document_revision_in_collection(
+    file_path: &Path,
+    collection_name: &str,
+    document_id: &Id,
+    revision: u64,
+) -> Result<DocumentDto, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    let collection = find_collection_mut(&mut database, collection_name)?;
+    let data = match collection
+        .document_revisions(document_id)
+        .into_iter()
+        .find(|document_revision| document_revision.revision == revision)
+        .and_then(|document_revision| document_revision.document.as_ref())
+    {
+        Some(document) => document.data(),
+        None => return Err(Box::new(DocumentError::RevisionNotFound)),
+    };
+    if let Some(schema) = &collection.schema {
+        schema.validate_document(&data)?;
+    }
+
+    let mut document = pb::Document {
+        id: Some(document_id.clone()),
+        data: Vec::new(),
+    };
+    document.set_data(data.clone());
+    collection.check_capped_document(&document)?;
+
+    match collection.documents
+        .iter()
+        .position(|document| document.id() == Some(document_id))
+    {
+        Some(index) => {
+            let previous = std::mem::replace(&mut collection.documents[index], document);
+            collection.record_revision(previous, false);
+            let evicted_documents = collection.evict_capped_documents_except(document_id);
+            collection.record_revisions(evicted_documents, true);
+        },
+        None => {
+            collection.documents.push(document);
+            let evicted_documents = collection.evict_capped_documents();
+            collection.record_revisions(evicted_documents, true);
+        },
+    }
+
+    let buf = serialize_database(&database)?;
+    write_database_to_file(&buf, file_path)?;
+
+    Ok(DocumentDto::new(document_id.clone(), &data))
+}
+
+/// Removes revisions that are older than the given number of seconds from a collection.
+/// 
+/// Deleted documents cannot be restored after their tombstones are removed.
+/// The database file is written only if revisions were removed.
+/// 
+/// Returns the number of removed revisions.
+pub fn purge_document_history_in_collection(
+    file_path: &Path,
+    collection_name: &str,
+    older_than_seconds: u64,
+) -> Result<usize, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    let collection = find_collection_mut(&mut database, collection_name)?;
+    let older_than_micros = i64::try_from(older_than_seconds)
+        .unwrap_or(i64::MAX)
+        .saturating_mul(1_000_000);
+    let timestamp = Utc::now().timestamp_micros().saturating_sub(older_than_micros);
+    let removed_count = collection.remove_revisions_before(timestamp);
+
+    if removed_count > 0 {
+        let buf = serialize_database(&database)?;
+        write_database_to_file(&buf, file_path)?;
+    }
+
+    Ok(removed_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_revision() {
+        let mut collection = pb::Collection::from("people");
+        let document = pb::Document {
+            id: Some(Id::Integer(1)),
+            data: Vec::new(),
+        };
+        collection.record_revision(document.clone(), false);
+        assert!(collection.revisions.is_empty());
+
+        collection.history = true;
+        collection.record_revision(document.clone(), false);
+        collection.record_revision(pb::Document { id: Some(Id::Integer(2)), data: Vec::new() }, true);
+        collection.record_revision(document, true);
+
+        let revisions: Vec<(u64, bool)> = collection
+            .document_revisions(&Id::Integer(1))
+            .iter()
+            .map(|revision| (revision.revision, revision.deleted))
+            .collect();
+        assert_eq!(revisions, vec![(1, false), (2, true)]);
+        assert_eq!(collection.document_revisions(&Id::Integer(2)).len(), 1);
+
+        collection.revisions[0].timestamp = 100;
+        collection.revisions[1].timestamp = 200;
+        collection.revisions[2].timestamp = 300;
+        assert_eq!(collection.remove_revisions_before(200), 1);
+        assert_eq!(collection.revisions.len(), 2);
+
+        let documents = [1, 3, 1]
+            .into_iter()
+            .map(|id| pb::Document { id: Some(Id::Integer(id)), data: Vec::new() })
+            .collect();
+        collection.record_revisions(documents, false);
+        let revisions: Vec<u64> = collection
+            .document_revisions(&Id::Integer(1))
+            .iter()
+            .map(|revision| revision.revision)
+            .collect();
+        assert_eq!(revisions, vec![2, 3, 4]);
+        assert_eq!(collection.document_revisions(&Id::Integer(3))[0].revision, 1);
+    }
+}
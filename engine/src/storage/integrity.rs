@@ -0,0 +1,457 @@
+// Database integrity check and repair.
+// Collections and documents are decoded one by one,
+// so that a corrupted part of a database file doesn't hide the rest of it.
+
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt,
+    fs,
+    path::{Path, PathBuf},
+};
+use bytes::Buf;
+use prost::{
+    Message,
+    encoding::{
+        decode_key,
+        decode_varint,
+        WireType,
+    },
+};
+use crate::storage::{
+    pb,
+    pb::Compression,
+    pb::document::Id,
+    error::DatabaseError,
+    file_header::FileHeader,
+    database::sibling_database_file_path,
+    encryption,
+    decompress_payload,
+    read_database_file,
+    serialize_database,
+    write_database_to_file,
+};
+
+/// Problem found in a database file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntegrityProblem {
+    /// Collection that has the problem. `None` if the problem is in the database itself.
+    /// 
+    /// Collections without a readable name are referred to by their position, e.g. `#2`.
+    pub collection: Option<String>,
+
+    /// Document that has the problem.
+    /// 
+    /// Documents are referred to by their id,
+    /// or by their position in the collection if the id cannot be read, e.g. `#5`.
+    pub document: Option<String>,
+
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl IntegrityProblem {
+    fn database(message: String) -> Self {
+        Self {
+            collection: None,
+            document: None,
+            message,
+        }
+    }
+
+    fn collection(collection: &str, message: String) -> Self {
+        Self {
+            collection: Some(String::from(collection)),
+            document: None,
+            message,
+        }
+    }
+
+    fn document(collection: &str, document: &str, message: String) -> Self {
+        Self {
+            collection: Some(String::from(collection)),
+            document: Some(String::from(document)),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for IntegrityProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.collection, &self.document) {
+            (Some(collection), Some(document)) => write!(
+                f,
+                "Collection '{}', document {}: {}",
+                collection, document, self.message
+            ),
+            (Some(collection), None) => write!(f, "Collection '{}': {}", collection, self.message),
+            _ => write!(f, "Database: {}", self.message),
+        }
+    }
+}
+
+/// Result of a database integrity check.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntegrityReportDto {
+    /// Number of valid collections.
+    pub collection_count: usize,
+
+    /// Number of valid documents.
+    pub document_count: usize,
+
+    /// All problems found in the database file.
+    pub problems: Vec<IntegrityProblem>,
+}
+
+impl IntegrityReportDto {
+    /// Checks if no problems were found.
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Result of a database repair.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairReportDto {
+    /// File path of the repaired database.
+    pub file_path: PathBuf,
+
+    /// Problems found in the original database file.
+    /// The counts are the collections and documents that were salvaged.
+    pub report: IntegrityReportDto,
+}
+
+/// Checks the integrity of a database file.
+/// 
+/// The file header checksum is verified and every collection and document
+/// is decoded and validated. Duplicate document ids and collection id counts
+/// lower than the largest integer document id are reported too.
+/// 
+/// Returns an error only if the database payload cannot be read at all.
+pub fn check_database_file(file_path: &Path) -> Result<IntegrityReportDto, Box<dyn Error>> {
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let (_, report) = salvage_database_file(file_path)?;
+
+    Ok(report)
+}
+
+/// Repairs a database file by salvaging it to a new database in the same directory.
+/// 
+/// Every collection and document that can be decoded and is valid is copied.
+/// Only the first of documents with duplicate ids is copied
+/// and collection id counts are raised to the largest integer document id.
+/// The original database file is not modified.
+pub fn repair_database_file(
+    new_db_name: &str,
+    file_path: &Path
+) -> Result<RepairReportDto, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let (mut database, report) = salvage_database_file(file_path)?;
+    database.name = new_db_name.to_string();
+    if let Err(e) = database.validate_errors() {
+        return Err(Box::new(e));
+    }
+
+    let new_file_path = sibling_database_file_path(new_db_name, file_path);
+    if new_file_path.is_file() {
+        return Err(Box::new(DatabaseError::Exists));
+    }
+
+    fs::File::create(&new_file_path)?;
+    if let Some(key) = encryption::get_key(file_path) {
+        encryption::insert_key(&new_file_path, key);
+    }
+    let buf = serialize_database(&database)?;
+    write_database_to_file(&buf, &new_file_path)?;
+
+    Ok(RepairReportDto {
+        file_path: new_file_path,
+        report,
+    })
+}
+
+/// Reads the valid parts of a database file and reports its problems.
+fn salvage_database_file(file_path: &Path) -> Result<(pb::Database, IntegrityReportDto), Box<dyn Error>> {
+    let buf = read_database_file(file_path)?;
+    let mut report = IntegrityReportDto::default();
+
+    let (compression, payload) = match FileHeader::read(&buf)? {
+        Some((header, payload)) => {
+            if let Err(e) = header.verify(payload) {
+                report.problems.push(IntegrityProblem::database(e.to_string()));
+            }
+            (header.compression, payload)
+        },
+        None => (Compression::None, buf.as_slice()),
+    };
+    let payload = decompress_payload(compression, payload)?;
+
+    let mut database = pb::Database::default();
+    database.set_compression(compression);
+    let mut collection_names = HashSet::new();
+    let mut collection_index = 0;
+
+    let (fields, error) = read_fields(&payload);
+    if let Some(message) = error {
+        report.problems.push(IntegrityProblem::database(message));
+    }
+
+    for (tag, value, _) in fields {
+        match (tag, value) {
+            (1, FieldValue::Bytes(bytes)) => match String::from_utf8(bytes.to_vec()) {
+                Ok(name) => database.name = name,
+                Err(e) => report.problems.push(IntegrityProblem::database(
+                    format!("Cannot read database name: {}", e)
+                )),
+            },
+            (2, FieldValue::Bytes(bytes)) => match String::from_utf8(bytes.to_vec()) {
+                Ok(description) => database.description = description,
+                Err(e) => report.problems.push(IntegrityProblem::database(
+                    format!("Cannot read database description: {}", e)
+                )),
+            },
+            (3, FieldValue::Bytes(bytes)) => {
+                collection_index += 1;
+                let label = format!("#{}", collection_index);
+                if let Some(collection) = salvage_collection(bytes, &label, &mut report) {
+                    if !collection_names.insert(collection.name.clone()) {
+                        report.problems.push(IntegrityProblem::collection(
+                            &collection.name,
+                            String::from("Duplicate collection name")
+                        ));
+                        continue;
+                    }
+                    report.collection_count += 1;
+                    report.document_count += collection.documents.len();
+                    database.collections.push(collection);
+                }
+            },
+            // Compression is stored in the file header
+            _ => {},
+        }
+    }
+
+    if let Err(e) = database.validate_errors() {
+        report.problems.push(IntegrityProblem::database(e.to_string()));
+    }
+
+    Ok((database, report))
+}
+
+/// Reads the valid parts of an encoded collection and reports its problems.
+/// 
+/// Returns `None` if the collection is not valid.
+fn salvage_collection(
+    buf: &[u8],
+    label: &str,
+    report: &mut IntegrityReportDto,
+) -> Option<pb::Collection>
+{
+    let mut collection = pb::Collection::default();
+    let mut problems = Vec::new();
+    let mut encoded_documents = Vec::new();
+
+    let (fields, error) = read_fields(buf);
+    if let Some(message) = error {
+        problems.push(message);
+    }
+    for (tag, value, field_buf) in fields {
+        match (tag, value) {
+            (3, FieldValue::Bytes(bytes)) => encoded_documents.push(bytes),
+            // Other fields are decoded one by one, so a corrupted field doesn't lose the others
+            _ => match pb::Collection::decode(field_buf) {
+                Ok(_) => {
+                    if let Err(e) = collection.merge(field_buf) {
+                        problems.push(format!("Cannot decode field {}: {}", tag, e));
+                    }
+                },
+                Err(e) => problems.push(format!("Cannot decode field {}: {}", tag, e)),
+            },
+        }
+    }
+
+    let name = if collection.name.is_empty() { label } else { collection.name.as_str() };
+    let name = name.to_string();
+    let mut is_valid = true;
+    if let Err(e) = collection.validate_errors() {
+        problems.push(e.to_string());
+        is_valid = false;
+    }
+    for message in problems {
+        report.problems.push(IntegrityProblem::collection(&name, message));
+    }
+
+    let mut ids = HashSet::new();
+    let mut max_id = 0;
+    for (index, bytes) in encoded_documents.into_iter().enumerate() {
+        let document = match pb::Document::decode(bytes) {
+            Ok(document) => document,
+            Err(e) => {
+                report.problems.push(IntegrityProblem::document(
+                    &name,
+                    &format!("#{}", index + 1),
+                    format!("Cannot decode document: {}", e)
+                ));
+                continue;
+            },
+        };
+
+        let id = document.id.clone().unwrap_or_default();
+        if let Err(e) = document.validate_errors() {
+            report.problems.push(IntegrityProblem::document(&name, &id.to_string(), e.to_string()));
+            continue;
+        }
+        if !ids.insert(format!("{:?}", id)) {
+            report.problems.push(IntegrityProblem::document(
+                &name,
+                &id.to_string(),
+                String::from("Duplicate document id")
+            ));
+            continue;
+        }
+        if let Id::Integer(id) = id {
+            max_id = max_id.max(id);
+        }
+
+        collection.documents.push(document);
+    }
+
+    if collection.id_count < max_id {
+        report.problems.push(IntegrityProblem::collection(
+            &name,
+            format!(
+                "Id count {} is lower than the largest document id {}",
+                collection.id_count, max_id
+            )
+        ));
+        collection.id_count = max_id;
+    }
+
+    if is_valid {
+        return Some(collection);
+    }
+
+    None
+}
+
+/// Value of an encoded protobuf field.
+/// 
+/// Only length-delimited values are needed to find nested messages.
+enum FieldValue<'a> {
+    Varint,
+    Bytes(&'a [u8]),
+    Fixed,
+}
+
+/// Encoded protobuf field with its tag, its value and its encoded bytes with the key.
+type Field<'a> = (u32, FieldValue<'a>, &'a [u8]);
+
+/// Reads the fields of an encoded protobuf message without decoding the values.
+/// 
+/// If the message is corrupted, the fields before the corrupted part
+/// are returned with an error message.
+fn read_fields(mut buf: &[u8]) -> (Vec<Field<'_>>, Option<String>) {
+    let mut fields = Vec::new();
+
+    while buf.has_remaining() {
+        let field_start = buf;
+        match read_field(&mut buf) {
+            Ok((tag, value)) => {
+                let field_buf = &field_start[..field_start.len() - buf.len()];
+                fields.push((tag, value, field_buf));
+            },
+            Err(message) => return (fields, Some(format!("Cannot decode data: {}", message))),
+        }
+    }
+
+    (fields, None)
+}
+
+/// Reads one field from the start of a buffer and advances the buffer past it.
+fn read_field<'a>(buf: &mut &'a [u8]) -> Result<(u32, FieldValue<'a>), String> {
+    let (tag, wire_type) = decode_key(buf).map_err(|e| e.to_string())?;
+
+    let len = match wire_type {
+        WireType::Varint => {
+            decode_varint(buf).map_err(|e| e.to_string())?;
+            return Ok((tag, FieldValue::Varint));
+        },
+        WireType::LengthDelimited => decode_varint(buf).map_err(|e| e.to_string())? as usize,
+        WireType::SixtyFourBit => 8,
+        WireType::ThirtyTwoBit => 4,
+        WireType::StartGroup | WireType::EndGroup => return Err(String::from("unexpected group")),
+    };
+    if len > buf.len() {
+        return Err(String::from("buffer underflow"));
+    }
+
+    let (value, rest) = buf.split_at(len);
+    *buf = rest;
+
+    match wire_type {
+        WireType::LengthDelimited => Ok((tag, FieldValue::Bytes(value))),
+        _ => Ok((tag, FieldValue::Fixed)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_fields() {
+        let mut collection = pb::Collection::from("test");
+        collection.id_count = 3;
+        let buf = collection.encode_to_vec();
+
+        let (fields, error) = read_fields(&buf);
+        assert!(error.is_none());
+        assert!(matches!(fields[0], (1, FieldValue::Varint, _)));
+        assert!(matches!(fields[1], (2, FieldValue::Bytes(b"test"), _)));
+        assert_eq!(fields[0].2, &buf[..2]);
+
+        let (fields, error) = read_fields(&buf[..buf.len() - 1]);
+        assert!(error.is_some());
+        assert_eq!(fields.len(), 1);
+    }
+
+    #[test]
+    fn test_salvage_collection_keeps_every_field() {
+        let document = |id: u64| pb::Document {
+            id: Some(Id::Integer(id)),
+            data: Vec::new(),
+        };
+        // Lists every field, so new fields must be added here
+        let collection = pb::Collection {
+            id_count: 2,
+            name: String::from("people"),
+            documents: vec![document(1), document(2)],
+            id_strategy: pb::IdStrategy::ClientInteger as i32,
+            schema: Some(pb::CollectionSchema {
+                fields: Vec::new(),
+                forbid_unknown_fields: true,
+            }),
+            ttl: Some(pb::TtlPolicy::new("created_at", 60)),
+            capped: Some(pb::CappedLimits::new(10, 1000)),
+            history: true,
+            revisions: vec![pb::DocumentRevision {
+                revision: 1,
+                timestamp: 100,
+                document: Some(document(3)),
+                deleted: true,
+            }],
+        };
+
+        let mut report = IntegrityReportDto::default();
+        let salvaged = salvage_collection(&collection.encode_to_vec(), "#1", &mut report);
+        assert!(report.problems.is_empty());
+        assert_eq!(salvaged, Some(collection));
+    }
+}
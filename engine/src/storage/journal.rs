@@ -0,0 +1,790 @@
+// Database journal for point-in-time recovery.
+// A journal is an append-only file next to the database file. It starts with
+// a base snapshot of the database and has an entry for each write after it.
+
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    error::Error,
+    fmt,
+    fs::{self, File, OpenOptions},
+    str::FromStr,
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+use chrono::{
+    DateTime,
+    Local,
+    NaiveDateTime,
+    TimeZone,
+    Utc,
+};
+use prost::Message;
+use crate::storage::{
+    pb,
+    pb::mutation::{
+        CollectionDocument,
+        CollectionRevisions,
+        Mutation,
+    },
+    error::DatabaseError,
+    diff::{
+        DocumentChange,
+        diff_documents,
+    },
+    encryption,
+    serialize_database,
+    deserialize_database,
+    read_database_file,
+    write_database_to_file,
+};
+
+/// File extension that is appended to database file names to get their journal file names.
+pub const JOURNAL_FILE_EXTENSION: &str = "journal";
+
+/// Magic bytes at the start of journal files.
+const JOURNAL_FILE_MAGIC: [u8; 4] = [0x00, b'D', b'B', b'J'];
+
+/// Length of the journal file header.
+/// 
+/// The magic bytes are followed by the sequence number of the last entry
+/// and the length of the journal after it as little-endian u64.
+/// They are updated after each entry, so the next sequence number
+/// can be found without reading the whole journal.
+const JOURNAL_HEADER_LEN: usize = JOURNAL_FILE_MAGIC.len() + 16;
+
+/// Length of the length and the checksum before each journal record.
+const RECORD_HEADER_LEN: usize = 8;
+
+/// Point in a journal to restore a database to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JournalTarget {
+    /// Restores the database as it was after the entry with this sequence number.
+    Sequence(u64),
+
+    /// Restores the database as it was at this time.
+    Timestamp(DateTime<Utc>),
+}
+
+impl FromStr for JournalTarget {
+    type Err = DatabaseError;
+
+    /// Parses a sequence number, an RFC 3339 time
+    /// or a local time in format `2024-01-31 12:00:00`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(sequence) = s.parse::<u64>() {
+            return Ok(JournalTarget::Sequence(sequence));
+        }
+        if let Ok(datetime) = DateTime::parse_from_rfc3339(s) {
+            return Ok(JournalTarget::Timestamp(datetime.with_timezone(&Utc)));
+        }
+
+        match NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .and_then(|datetime| Local.from_local_datetime(&datetime).single())
+        {
+            Some(datetime) => Ok(JournalTarget::Timestamp(datetime.with_timezone(&Utc))),
+            None => Err(DatabaseError::InvalidJournalTarget),
+        }
+    }
+}
+
+/// Journal entry data transfer object (DTO).
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntryDto {
+    /// Sequence number of the entry. The base snapshot has sequence number 0.
+    pub sequence: u64,
+
+    /// Time when the entry was written.
+    pub timestamp: DateTime<Utc>,
+
+    /// Descriptions of the mutations in the entry.
+    pub mutations: Vec<String>,
+}
+
+impl fmt::Display for JournalEntryDto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}  {}  {}",
+            self.sequence,
+            self.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"),
+            self.mutations.join(", ")
+        )
+    }
+}
+
+impl fmt::Display for pb::Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let document_id = |document: &Option<pb::Document>| {
+            document.as_ref().and_then(|document| document.id.clone()).unwrap_or_default()
+        };
+
+        match &self.mutation {
+            Some(Mutation::SetDatabase(_)) => write!(f, "Modify database"),
+            Some(Mutation::PutCollection(collection)) => {
+                write!(f, "Create or modify collection '{}'", collection.name)
+            },
+            Some(Mutation::DeleteCollection(name)) => write!(f, "Delete collection '{}'", name),
+            Some(Mutation::InsertDocument(insert)) => write!(
+                f,
+                "Insert document {} to collection '{}'",
+                document_id(&insert.document), insert.collection
+            ),
+            Some(Mutation::ReplaceDocument(replace)) => write!(
+                f,
+                "Replace document {} in collection '{}'",
+                document_id(&replace.document), replace.collection
+            ),
+            Some(Mutation::DeleteDocument(delete)) => write!(
+                f,
+                "Delete document {} from collection '{}'",
+                document_id(&delete.document), delete.collection
+            ),
+            Some(Mutation::AppendRevisions(append)) => write!(
+                f,
+                "Append {} revisions to collection '{}'",
+                append.revisions.len(), append.collection
+            ),
+            Some(Mutation::RemoveRevisions(remove)) => write!(
+                f,
+                "Remove {} revisions from collection '{}'",
+                remove.revisions.len(), remove.collection
+            ),
+            None => write!(f, "Unknown mutation"),
+        }
+    }
+}
+
+/// Gets the file path of the journal of a database file.
+pub fn journal_file_path(db_file_path: &Path) -> PathBuf {
+    let mut file_name = db_file_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{}", JOURNAL_FILE_EXTENSION));
+
+    db_file_path.with_file_name(file_name)
+}
+
+/// Checks if a database file has a journal.
+pub fn is_journal_enabled(db_file_path: &Path) -> bool {
+    journal_file_path(db_file_path).is_file()
+}
+
+/// Enables the journal of a database.
+/// 
+/// The journal starts with a snapshot of the database as it is now.
+/// Encrypted databases cannot have a journal,
+/// because the journal is not encrypted.
+pub fn enable_journal(db_file_path: &Path) -> Result<(), Box<dyn Error>> {
+    if !db_file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    if is_journal_enabled(db_file_path) {
+        return Err(Box::new(DatabaseError::JournalEnabled));
+    }
+    if encryption::is_encrypted(&fs::read(db_file_path)?) {
+        return Err(Box::new(DatabaseError::JournalNotSupported));
+    }
+
+    let database = deserialize_database(&read_database_file(db_file_path)?)?;
+    let entry = pb::JournalEntry {
+        sequence: 0,
+        timestamp: Utc::now().timestamp_micros(),
+        snapshot: Some(database),
+        mutations: Vec::new(),
+    };
+
+    let record = encode_record(&entry);
+    let mut buf = encode_journal_header(0, (JOURNAL_HEADER_LEN + record.len()) as u64);
+    buf.extend_from_slice(&record);
+    fs::write(journal_file_path(db_file_path), buf)?;
+
+    Ok(())
+}
+
+/// Disables the journal of a database and deletes the journal file.
+pub fn disable_journal(db_file_path: &Path) -> Result<(), Box<dyn Error>> {
+    if !is_journal_enabled(db_file_path) {
+        return Err(Box::new(DatabaseError::JournalNotEnabled));
+    }
+
+    fs::remove_file(journal_file_path(db_file_path))?;
+
+    Ok(())
+}
+
+/// Finds all entries in the journal of a database.
+pub fn find_journal_entries(db_file_path: &Path) -> Result<Vec<JournalEntryDto>, Box<dyn Error>> {
+    if !is_journal_enabled(db_file_path) {
+        return Err(Box::new(DatabaseError::JournalNotEnabled));
+    }
+
+    let (entries, _) = read_journal_file(&journal_file_path(db_file_path))?;
+    let entries = entries
+        .iter()
+        .map(|entry| JournalEntryDto {
+            sequence: entry.sequence,
+            timestamp: timestamp_to_datetime(entry.timestamp),
+            mutations: match entry.snapshot {
+                Some(_) => vec![String::from("Base snapshot")],
+                None => entry.mutations.iter().map(|mutation| mutation.to_string()).collect(),
+            },
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// Restores a database to a point in its journal.
+/// 
+/// The database is rebuilt from the base snapshot by applying the entries
+/// up to the target. The database keeps its current name.
+/// The restore is written to the journal too,
+/// so it can be undone by restoring to the entry before it.
+/// 
+/// Returns the sequence number of the last applied entry.
+pub fn restore_database_from_journal(
+    db_file_path: &Path,
+    target: JournalTarget,
+) -> Result<u64, Box<dyn Error>>
+{
+    if !db_file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    if !is_journal_enabled(db_file_path) {
+        return Err(Box::new(DatabaseError::JournalNotEnabled));
+    }
+
+    let (entries, _) = read_journal_file(&journal_file_path(db_file_path))?;
+    let mut entries = entries.into_iter();
+    let (mut database, mut sequence) = match entries.next() {
+        Some(pb::JournalEntry { snapshot: Some(snapshot), sequence, timestamp, .. })
+            if is_before_target(sequence, timestamp, target) => (snapshot, sequence),
+        Some(pb::JournalEntry { snapshot: Some(_), .. }) => {
+            return Err(Box::new(DatabaseError::JournalTargetNotFound))
+        },
+        _ => return Err(Box::new(DatabaseError::InvalidJournal)),
+    };
+
+    for entry in entries {
+        if !is_before_target(entry.sequence, entry.timestamp, target) {
+            break;
+        }
+        for mutation in entry.mutations {
+            apply_mutation(&mut database, mutation)?;
+        }
+        sequence = entry.sequence;
+    }
+    if let JournalTarget::Sequence(target_sequence) = target {
+        if sequence != target_sequence {
+            return Err(Box::new(DatabaseError::JournalTargetNotFound));
+        }
+    }
+
+    // The database name must match the file name if the database was renamed
+    database.name = deserialize_database(&read_database_file(db_file_path)?)?.name;
+    let buf = serialize_database(&database)?;
+    write_database_to_file(&buf, db_file_path)?;
+
+    Ok(sequence)
+}
+
+/// Writes the changes of a database write to the journal if the database has one.
+/// 
+/// This must be called before the database file is written,
+/// because the changes are found by comparing the buffer to the database file.
+pub(crate) fn record_write(buf: &[u8], db_file_path: &Path) -> io::Result<()> {
+    if !is_journal_enabled(db_file_path) {
+        return Ok(());
+    }
+
+    let old_database = deserialize_database(&read_database_file(db_file_path)?)?;
+    let new_database = deserialize_database(buf)?;
+    let mutations = diff_databases(&old_database, &new_database);
+    if mutations.is_empty() {
+        return Ok(());
+    }
+
+    let journal_file_path = journal_file_path(db_file_path);
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&journal_file_path)?;
+    let (last_sequence, valid_len) = read_journal_tail(&mut file, &journal_file_path)?;
+    let entry = pb::JournalEntry {
+        sequence: last_sequence + 1,
+        timestamp: Utc::now().timestamp_micros(),
+        snapshot: None,
+        mutations,
+    };
+
+    // Drop a partially written record from the end
+    if file.metadata()?.len() != valid_len {
+        file.set_len(valid_len)?;
+    }
+    let record = encode_record(&entry);
+    file.seek(SeekFrom::Start(valid_len))?;
+    file.write_all(&record)?;
+    file.sync_all()?;
+
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(&encode_journal_header(entry.sequence, valid_len + record.len() as u64))?;
+
+    Ok(())
+}
+
+/// Reads the sequence number of the last journal entry and
+/// the length of the valid part of the journal.
+/// 
+/// These are read from the journal header. If the header doesn't match
+/// the journal length, because a write to the journal was interrupted,
+/// the whole journal is read instead.
+fn read_journal_tail(file: &mut File, file_path: &Path) -> io::Result<(u64, u64)> {
+    let mut header = [0u8; JOURNAL_HEADER_LEN];
+    file.seek(SeekFrom::Start(0))?;
+    file.read_exact(&mut header)?;
+    if !header.starts_with(&JOURNAL_FILE_MAGIC) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, DatabaseError::InvalidJournal));
+    }
+
+    let pos = JOURNAL_FILE_MAGIC.len();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&header[pos..pos + 8]);
+    let last_sequence = u64::from_le_bytes(bytes);
+    bytes.copy_from_slice(&header[pos + 8..pos + 16]);
+    let len = u64::from_le_bytes(bytes);
+    if len == file.metadata()?.len() {
+        return Ok((last_sequence, len));
+    }
+
+    let (entries, valid_len) = read_journal_file(file_path)?;
+    match entries.last() {
+        Some(entry) => Ok((entry.sequence, valid_len)),
+        None => Err(io::Error::new(io::ErrorKind::InvalidData, DatabaseError::InvalidJournal)),
+    }
+}
+
+/// Moves the journal of a renamed database file.
+pub(crate) fn rename_journal(db_file_path: &Path, new_db_file_path: &Path) -> io::Result<()> {
+    if !is_journal_enabled(db_file_path) {
+        return Ok(());
+    }
+
+    fs::rename(journal_file_path(db_file_path), journal_file_path(new_db_file_path))
+}
+
+/// Deletes the journal of a deleted database file.
+pub(crate) fn delete_journal(db_file_path: &Path) -> io::Result<()> {
+    if !is_journal_enabled(db_file_path) {
+        return Ok(());
+    }
+
+    fs::remove_file(journal_file_path(db_file_path))
+}
+
+/// Checks if a journal entry is at or before the target.
+fn is_before_target(sequence: u64, timestamp: i64, target: JournalTarget) -> bool {
+    match target {
+        JournalTarget::Sequence(target_sequence) => sequence <= target_sequence,
+        JournalTarget::Timestamp(target_timestamp) => timestamp <= target_timestamp.timestamp_micros(),
+    }
+}
+
+/// Converts a Unix timestamp in microseconds to time.
+pub(crate) fn timestamp_to_datetime(timestamp: i64) -> DateTime<Utc> {
+    let datetime = NaiveDateTime::from_timestamp_micros(timestamp).unwrap_or_default();
+
+    DateTime::<Utc>::from_utc(datetime, Utc)
+}
+
+/// Encodes the journal file header.
+fn encode_journal_header(last_sequence: u64, len: u64) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(JOURNAL_HEADER_LEN);
+    buf.extend_from_slice(&JOURNAL_FILE_MAGIC);
+    buf.extend_from_slice(&last_sequence.to_le_bytes());
+    buf.extend_from_slice(&len.to_le_bytes());
+
+    buf
+}
+
+/// Encodes a journal entry to a record.
+/// 
+/// Records start with the length and the CRC-32 checksum of the entry.
+fn encode_record(entry: &pb::JournalEntry) -> Vec<u8> {
+    let payload = entry.encode_to_vec();
+    let mut buf = Vec::with_capacity(RECORD_HEADER_LEN + payload.len());
+    buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+    buf.extend_from_slice(&payload);
+
+    buf
+}
+
+/// Reads all entries from a journal file.
+/// 
+/// Reading stops at a record that is not complete or doesn't match its checksum.
+/// This happens if a write to the journal was interrupted.
+/// 
+/// Returns the entries and the length of the valid part of the journal.
+fn read_journal_file(file_path: &Path) -> io::Result<(Vec<pb::JournalEntry>, u64)> {
+    let buf = fs::read(file_path)?;
+    if buf.len() < JOURNAL_HEADER_LEN || !buf.starts_with(&JOURNAL_FILE_MAGIC) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, DatabaseError::InvalidJournal));
+    }
+
+    let mut entries = Vec::new();
+    let mut pos = JOURNAL_HEADER_LEN;
+    while pos + RECORD_HEADER_LEN <= buf.len() {
+        let len = u32::from_le_bytes([buf[pos], buf[pos + 1], buf[pos + 2], buf[pos + 3]]) as usize;
+        let checksum = u32::from_le_bytes([buf[pos + 4], buf[pos + 5], buf[pos + 6], buf[pos + 7]]);
+        let end = pos + RECORD_HEADER_LEN + len;
+        if end > buf.len() {
+            break;
+        }
+
+        let payload = &buf[pos + RECORD_HEADER_LEN..end];
+        if crc32fast::hash(payload) != checksum {
+            break;
+        }
+        match pb::JournalEntry::decode(payload) {
+            Ok(entry) => entries.push(entry),
+            Err(_) => break,
+        }
+        pos = end;
+    }
+
+    Ok((entries, pos as u64))
+}
+
+/// Gets the mutations that change the old database to the new database.
+fn diff_databases(old: &pb::Database, new: &pb::Database) -> Vec<pb::Mutation> {
+    let mut mutations = Vec::new();
+    let mut push = |mutation: Mutation| mutations.push(pb::Mutation { mutation: Some(mutation) });
+
+    if old.name != new.name || old.description != new.description || old.compression != new.compression {
+        push(Mutation::SetDatabase(pb::Database {
+            name: new.name.clone(),
+            description: new.description.clone(),
+            collections: Vec::new(),
+            compression: new.compression,
+        }));
+    }
+
+    let old_collections: HashMap<&str, &pb::Collection> = old.collections
+        .iter()
+        .map(|collection| (collection.name.as_str(), collection))
+        .collect();
+
+    for new_collection in new.collections.iter() {
+        let old_collection = old_collections.get(new_collection.name.as_str());
+        let metadata_changed = match old_collection {
+            Some(old_collection) => {
+                old_collection.id_count != new_collection.id_count
+                    || old_collection.id_strategy != new_collection.id_strategy
+                    || old_collection.schema != new_collection.schema
+                    || old_collection.ttl != new_collection.ttl
+                    || old_collection.capped != new_collection.capped
+                    || old_collection.history != new_collection.history
+            },
+            None => true,
+        };
+        if metadata_changed {
+            push(Mutation::PutCollection(pb::Collection {
+                documents: Vec::new(),
+                revisions: Vec::new(),
+                ..new_collection.clone()
+            }));
+        }
+
+        let old_documents = old_collection
+            .map(|old_collection| old_collection.documents.as_slice())
+            .unwrap_or_default();
+        let collection_document = |document: pb::Document| CollectionDocument {
+            collection: new_collection.name.clone(),
+            document: Some(document),
+        };
+
+        for change in diff_documents(old_documents, &new_collection.documents) {
+            match change {
+                DocumentChange::Insert(document) => {
+                    push(Mutation::InsertDocument(collection_document(document.clone())));
+                },
+                DocumentChange::Replace { after, .. } => {
+                    push(Mutation::ReplaceDocument(collection_document(after.clone())));
+                },
+                DocumentChange::Delete(document) => {
+                    push(Mutation::DeleteDocument(collection_document(pb::Document {
+                        id: document.id.clone(),
+                        data: Vec::new(),
+                    })));
+                },
+            }
+        }
+
+        let old_revisions = old_collection
+            .map(|old_collection| old_collection.revisions.as_slice())
+            .unwrap_or_default();
+        let (removed_revisions, appended_revisions) = diff_revisions(old_revisions, &new_collection.revisions);
+        if !removed_revisions.is_empty() {
+            push(Mutation::RemoveRevisions(CollectionRevisions {
+                collection: new_collection.name.clone(),
+                revisions: removed_revisions,
+            }));
+        }
+        if !appended_revisions.is_empty() {
+            push(Mutation::AppendRevisions(CollectionRevisions {
+                collection: new_collection.name.clone(),
+                revisions: appended_revisions,
+            }));
+        }
+    }
+
+    for old_collection in old.collections.iter() {
+        if !new.collections.iter().any(|collection| collection.name == old_collection.name) {
+            push(Mutation::DeleteCollection(old_collection.name.clone()));
+        }
+    }
+
+    mutations
+}
+
+/// Gets a key for a document revision that is unique in its collection.
+fn revision_key(revision: &pb::DocumentRevision) -> (String, u64) {
+    (format!("{:?}", revision.document_id()), revision.revision)
+}
+
+/// Gets the revisions that are removed from the old revisions and appended after them
+/// to get the new revisions.
+/// 
+/// Revisions are only appended or purged, so usually only the changed revisions are returned.
+/// If the new revisions are not in the same order, all old revisions are removed
+/// and all new revisions are appended.
+fn diff_revisions(
+    old: &[pb::DocumentRevision],
+    new: &[pb::DocumentRevision],
+) -> (Vec<pb::DocumentRevision>, Vec<pb::DocumentRevision>)
+{
+    let removed_key = |revision: &pb::DocumentRevision| pb::DocumentRevision {
+        revision: revision.revision,
+        document: revision.document.as_ref().map(|document| pb::Document {
+            id: document.id.clone(),
+            data: Vec::new(),
+        }),
+        ..Default::default()
+    };
+
+    let new_keys: HashSet<(String, u64)> = new.iter().map(revision_key).collect();
+    let (kept, removed): (Vec<&pb::DocumentRevision>, Vec<&pb::DocumentRevision>) = old
+        .iter()
+        .partition(|revision| new_keys.contains(&revision_key(revision)));
+
+    let is_appended = kept.len() <= new.len()
+        && kept.iter().zip(new.iter()).all(|(kept, new)| *kept == new);
+    if !is_appended {
+        return (old.iter().map(removed_key).collect(), new.to_vec());
+    }
+
+    (removed.into_iter().map(removed_key).collect(), new[kept.len()..].to_vec())
+}
+
+/// Applies a journal mutation to a database.
+fn apply_mutation(database: &mut pb::Database, mutation: pb::Mutation) -> io::Result<()> {
+    let invalid_journal = || io::Error::new(io::ErrorKind::InvalidData, DatabaseError::InvalidJournal);
+    let find_collection = |database: &mut pb::Database, name: &str| {
+        database.collections
+            .iter_mut()
+            .position(|collection| collection.name == name)
+    };
+
+    match mutation.mutation {
+        Some(Mutation::SetDatabase(metadata)) => {
+            database.name = metadata.name;
+            database.description = metadata.description;
+            database.compression = metadata.compression;
+        },
+        Some(Mutation::PutCollection(collection)) => {
+            match find_collection(database, &collection.name) {
+                Some(index) => {
+                    let existing = &mut database.collections[index];
+                    existing.id_count = collection.id_count;
+                    existing.id_strategy = collection.id_strategy;
+                    existing.schema = collection.schema;
+                    existing.ttl = collection.ttl;
+                    existing.capped = collection.capped;
+                    existing.history = collection.history;
+                },
+                None => database.collections.push(collection),
+            }
+        },
+        Some(Mutation::DeleteCollection(name)) => {
+            database.collections.retain(|collection| collection.name != name);
+        },
+        Some(Mutation::InsertDocument(CollectionDocument { collection, document: Some(document) })) => {
+            let index = find_collection(database, &collection).ok_or_else(invalid_journal)?;
+            database.collections[index].documents.push(document);
+        },
+        Some(Mutation::ReplaceDocument(CollectionDocument { collection, document: Some(document) })) => {
+            let index = find_collection(database, &collection).ok_or_else(invalid_journal)?;
+            let documents = &mut database.collections[index].documents;
+            match documents.iter_mut().find(|existing| existing.id == document.id) {
+                Some(existing) => *existing = document,
+                None => documents.push(document),
+            }
+        },
+        Some(Mutation::DeleteDocument(CollectionDocument { collection, document: Some(document) })) => {
+            let index = find_collection(database, &collection).ok_or_else(invalid_journal)?;
+            database.collections[index].documents.retain(|existing| existing.id != document.id);
+        },
+        Some(Mutation::AppendRevisions(CollectionRevisions { collection, revisions })) => {
+            let index = find_collection(database, &collection).ok_or_else(invalid_journal)?;
+            database.collections[index].revisions.extend(revisions);
+        },
+        Some(Mutation::RemoveRevisions(CollectionRevisions { collection, revisions })) => {
+            let index = find_collection(database, &collection).ok_or_else(invalid_journal)?;
+            let removed_keys: HashSet<(String, u64)> = revisions.iter().map(revision_key).collect();
+            database.collections[index].revisions.retain(|revision| !removed_keys.contains(&revision_key(revision)));
+        },
+        _ => return Err(invalid_journal()),
+    }
+
+    Ok(())
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::pb::document::data_type::DataType;
+
+    fn person(id: u64) -> pb::Document {
+        pb::Document::test_document(id, "name", DataType::Text(String::from("John")))
+    }
+
+    #[test]
+    fn test_diff_and_apply_mutations() {
+        let mut old = pb::Database::from("test");
+        let mut people = pb::Collection::from("people");
+        people.documents = vec![person(1), person(2)];
+        old.collections = vec![people, pb::Collection::from("books")];
+
+        let mut new = old.clone();
+        new.description = String::from("changed");
+        new.collections.remove(1);
+        new.collections[0].documents.remove(0);
+        new.collections[0].documents.push(person(3));
+        new.collections[0].id_count = 3;
+        new.collections.push(pb::Collection::from("authors"));
+
+        let mutations = diff_databases(&old, &new);
+        let descriptions: Vec<String> = mutations.iter().map(|mutation| mutation.to_string()).collect();
+        assert_eq!(descriptions, vec![
+            "Modify database",
+            "Create or modify collection 'people'",
+            "Insert document 3 to collection 'people'",
+            "Delete document 1 from collection 'people'",
+            "Create or modify collection 'authors'",
+            "Delete collection 'books'",
+        ]);
+
+        let mut database = old.clone();
+        for mutation in mutations {
+            apply_mutation(&mut database, mutation).unwrap();
+        }
+        assert_eq!(database, new);
+        assert!(diff_databases(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_and_apply_revisions() {
+        let revision = |id: u64, revision: u64| pb::DocumentRevision {
+            revision,
+            timestamp: 0,
+            document: Some(person(id)),
+            deleted: false,
+        };
+        let mut old = pb::Database::from("test");
+        let mut people = pb::Collection::from("people");
+        people.history = true;
+        people.revisions = vec![revision(1, 1), revision(2, 1), revision(1, 2)];
+        old.collections = vec![people];
+
+        // Only the purged and appended revisions are journaled
+        let mut new = old.clone();
+        new.collections[0].revisions.remove(0);
+        new.collections[0].revisions.push(revision(2, 2));
+        let mutations = diff_databases(&old, &new);
+        let descriptions: Vec<String> = mutations.iter().map(|mutation| mutation.to_string()).collect();
+        assert_eq!(descriptions, vec![
+            "Remove 1 revisions from collection 'people'",
+            "Append 1 revisions to collection 'people'",
+        ]);
+
+        let mut database = old.clone();
+        for mutation in mutations {
+            apply_mutation(&mut database, mutation).unwrap();
+        }
+        assert_eq!(database, new);
+
+        // Reordered revisions are replaced
+        let mut new = old.clone();
+        new.collections[0].revisions.swap(0, 1);
+        let mutations = diff_databases(&old, &new);
+        assert_eq!(mutations.len(), 2);
+
+        let mut database = old.clone();
+        for mutation in mutations {
+            apply_mutation(&mut database, mutation).unwrap();
+        }
+        assert_eq!(database, new);
+    }
+
+    #[test]
+    fn test_read_journal_file_with_partial_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("test.db.journal");
+        let entry = pb::JournalEntry {
+            sequence: 0,
+            timestamp: 0,
+            snapshot: Some(pb::Database::from("test")),
+            mutations: Vec::new(),
+        };
+        let record = encode_record(&entry);
+        let mut buf = encode_journal_header(0, (JOURNAL_HEADER_LEN + record.len()) as u64);
+        buf.extend_from_slice(&record);
+        let valid_len = buf.len() as u64;
+        buf.extend_from_slice(&encode_record(&entry)[..5]);
+        fs::write(&file_path, buf).unwrap();
+
+        let (entries, len) = read_journal_file(&file_path).unwrap();
+        assert_eq!(entries, vec![entry]);
+        assert_eq!(len, valid_len);
+
+        // The header doesn't match the journal length, so the journal is read
+        let mut file = OpenOptions::new().read(true).write(true).open(&file_path).unwrap();
+        assert_eq!(read_journal_tail(&mut file, &file_path).unwrap(), (0, valid_len));
+
+        file.set_len(valid_len).unwrap();
+        file.seek(SeekFrom::Start(0)).unwrap();
+        file.write_all(&encode_journal_header(7, valid_len)).unwrap();
+        assert_eq!(read_journal_tail(&mut file, &file_path).unwrap(), (7, valid_len));
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_parse_journal_target() {
+        assert_eq!(JournalTarget::from_str("3").unwrap(), JournalTarget::Sequence(3));
+
+        let target = JournalTarget::from_str("2024-01-31T12:00:00Z").unwrap();
+        let datetime = DateTime::parse_from_rfc3339("2024-01-31T12:00:00Z").unwrap();
+        assert_eq!(target, JournalTarget::Timestamp(datetime.with_timezone(&Utc)));
+
+        assert!(matches!(
+            JournalTarget::from_str("2024-01-31 12:00:00"),
+            Ok(JournalTarget::Timestamp(_))
+        ));
+        assert!(JournalTarget::from_str("yesterday").is_err());
+    }
+}
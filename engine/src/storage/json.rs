@@ -0,0 +1,629 @@
+// JSON and JSON Lines export and import.
+// Typed JSON keeps the data type of every value, so exported data is imported without changes.
+// Relaxed JSON is plain JSON and the data types are inferred when it is imported.
+
+use std::{
+    error::Error,
+    ffi::OsStr,
+    fs,
+    path::Path,
+};
+use indexmap::IndexMap;
+use rust_decimal::prelude::ToPrimitive;
+use serde_json::{
+    Map,
+    Number,
+    Value,
+};
+use crate::{
+    DocumentInputDataField,
+    storage::{
+        error::{
+            DatabaseError,
+            CollectionError,
+            JsonError,
+        },
+        pb,
+        pb::document::DataType,
+        pb::document::data_type,
+        pb::document::Id,
+        document::{
+            DATA_TYPE_NAMES,
+            data_type_name,
+            insert_imported_document,
+            decode_decimal128,
+            decode_int128,
+            decode_uint128,
+            value_to_string,
+        },
+        serialize_database,
+        deserialize_database,
+        read_database_file,
+        write_database_to_file,
+    },
+};
+
+/// Key of the document id in JSON documents.
+pub const JSON_ID_KEY: &str = "_id";
+
+/// Key of the collection name in JSON Lines documents exported from a database.
+pub const JSON_COLLECTION_KEY: &str = "_collection";
+
+/// JSON file format.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum JsonFormat {
+    /// One JSON value.
+    /// Collections are arrays of documents and databases are objects of collections.
+    #[default]
+    Json,
+
+    /// One JSON document per line.
+    JsonLines,
+}
+
+impl JsonFormat {
+    /// Gets the format from a file extension.
+    /// 
+    /// Files with extension `jsonl` or `ndjson` are JSON Lines, others are JSON.
+    pub fn from_file_path(file_path: &Path) -> Self {
+        match file_path.extension().and_then(OsStr::to_str) {
+            Some("jsonl" | "ndjson") => JsonFormat::JsonLines,
+            _ => JsonFormat::Json,
+        }
+    }
+}
+
+/// How document values are encoded in JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum JsonMode {
+    /// Values are objects with the data type as the key, e.g. `{"Int64": "5"}`.
+    /// 
+    /// 64-bit and 128-bit integers and `Decimal128` values are strings,
+    /// so JSON parsers that read numbers as doubles don't lose precision.
+    #[default]
+    Typed,
+
+    /// Values are plain JSON values and data types are inferred when imported.
+    /// 
+    /// Integers are imported as `Int32` if they fit, otherwise as `Int64` or `UInt64`.
+    /// Other numbers are imported as `Decimal`. Fields with `null` values are skipped.
+    Relaxed,
+}
+
+/// Options for JSON export and import.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct JsonOptions {
+    pub format: JsonFormat,
+    pub mode: JsonMode,
+}
+
+impl JsonOptions {
+    /// Creates new JSON options.
+    pub fn new(format: JsonFormat, mode: JsonMode) -> Self {
+        Self {
+            format,
+            mode,
+        }
+    }
+}
+
+/// Exports all documents in a collection to a JSON file.
+/// 
+/// The file is created or overwritten.
+/// 
+/// Returns the number of exported documents.
+pub fn export_collection_to_json_file(
+    file_path: &Path,
+    collection_name: &str,
+    json_file_path: &Path,
+    options: &JsonOptions,
+) -> Result<usize, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    let database = deserialize_database(&read_database_file(file_path)?)?;
+
+    let collection = match database
+        .collections
+        .iter()
+        .find(|collection| collection.name == collection_name)
+    {
+        Some(collection) => collection,
+        None => return Err(Box::new(CollectionError::NotFound)),
+    };
+    let documents: Vec<Value> = collection.documents
+        .iter()
+        .map(|document| Value::Object(document_to_json(document, options.mode)))
+        .collect();
+    let document_count = documents.len();
+
+    let json = match options.format {
+        JsonFormat::Json => serde_json::to_string_pretty(&Value::Array(documents))?,
+        JsonFormat::JsonLines => to_json_lines(documents)?,
+    };
+    fs::write(json_file_path, json)?;
+
+    Ok(document_count)
+}
+
+/// Exports all documents in a database to a JSON file.
+/// 
+/// In JSON format the file has an object with the collection names as keys.
+/// In JSON Lines format each document has the collection name in key `_collection`.
+/// The file is created or overwritten.
+/// 
+/// Returns the number of exported documents.
+pub fn export_database_to_json_file(
+    file_path: &Path,
+    json_file_path: &Path,
+    options: &JsonOptions,
+) -> Result<usize, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    let database = deserialize_database(&read_database_file(file_path)?)?;
+    let mut document_count = 0;
+
+    let json = match options.format {
+        JsonFormat::Json => {
+            let mut collections = Map::new();
+            for collection in database.collections.iter() {
+                let documents: Vec<Value> = collection.documents
+                    .iter()
+                    .map(|document| Value::Object(document_to_json(document, options.mode)))
+                    .collect();
+                document_count += documents.len();
+                collections.insert(collection.name.clone(), Value::Array(documents));
+            }
+            serde_json::to_string_pretty(&Value::Object(collections))?
+        },
+        JsonFormat::JsonLines => {
+            let mut documents = Vec::new();
+            for collection in database.collections.iter() {
+                for document in collection.documents.iter() {
+                    let mut object = Map::new();
+                    object.insert(String::from(JSON_COLLECTION_KEY), Value::from(collection.name.as_str()));
+                    object.extend(document_to_json(document, options.mode));
+                    documents.push(Value::Object(object));
+                }
+            }
+            document_count = documents.len();
+            to_json_lines(documents)?
+        },
+    };
+    fs::write(json_file_path, json)?;
+
+    Ok(document_count)
+}
+
+/// Imports documents from a JSON file to a collection.
+/// 
+/// Document ids are kept if the collection has a client id strategy.
+/// Otherwise new ids are generated. All documents are validated
+/// before the database is written, so nothing is imported if a document is not valid.
+/// 
+/// Returns the number of imported documents.
+pub fn import_json_file_to_collection(
+    file_path: &Path,
+    collection_name: &str,
+    json_file_path: &Path,
+    options: &JsonOptions,
+) -> Result<usize, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    let json = fs::read_to_string(json_file_path)?;
+    let documents = match options.format {
+        JsonFormat::Json => match parse_json(&json)? {
+            Value::Array(values) => values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| (format!("document {}", index + 1), value))
+                .collect(),
+            _ => return Err(Box::new(JsonError::UnexpectedValue(
+                String::from("Collection must be an array of documents")
+            ))),
+        },
+        JsonFormat::JsonLines => parse_json_lines(&json)?,
+    };
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    let collection = match database
+        .collections
+        .iter_mut()
+        .find(|collection| collection.name == collection_name)
+    {
+        Some(collection) => collection,
+        None => return Err(Box::new(CollectionError::NotFound)),
+    };
+
+    let document_count = documents.len();
+    for (location, value) in documents {
+        if let Err(message) = import_document(collection, value, options.mode) {
+            return Err(Box::new(JsonError::InvalidDocument { location, message }));
+        }
+    }
+
+    let buf = serialize_database(&database)?;
+    write_database_to_file(&buf, file_path)?;
+
+    Ok(document_count)
+}
+
+/// Imports documents from a JSON file to a database.
+/// 
+/// The file must be in the same format as database exports.
+/// Collections that don't exist are created.
+/// Document ids are kept if the collection has a client id strategy.
+/// Otherwise new ids are generated. All documents are validated
+/// before the database is written, so nothing is imported if a document is not valid.
+/// 
+/// Returns the number of imported documents.
+pub fn import_json_file_to_database(
+    file_path: &Path,
+    json_file_path: &Path,
+    options: &JsonOptions,
+) -> Result<usize, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    let json = fs::read_to_string(json_file_path)?;
+    let mut documents = Vec::new();
+
+    match options.format {
+        JsonFormat::Json => match parse_json(&json)? {
+            Value::Object(collections) => {
+                for (collection_name, value) in collections {
+                    let values = match value {
+                        Value::Array(values) => values,
+                        _ => return Err(Box::new(JsonError::UnexpectedValue(format!(
+                            "Collection '{}' must be an array of documents",
+                            collection_name
+                        )))),
+                    };
+                    for (index, value) in values.into_iter().enumerate() {
+                        let location = format!("collection '{}', document {}", collection_name, index + 1);
+                        documents.push((collection_name.clone(), location, value));
+                    }
+                }
+            },
+            _ => return Err(Box::new(JsonError::UnexpectedValue(
+                String::from("Database must be an object of collections")
+            ))),
+        },
+        JsonFormat::JsonLines => {
+            for (location, value) in parse_json_lines(&json)? {
+                let mut collection_name = None;
+                let mut object = Map::new();
+                if let Value::Object(fields) = value {
+                    for (key, value) in fields {
+                        match (key.as_str(), value) {
+                            (JSON_COLLECTION_KEY, Value::String(name)) => collection_name = Some(name),
+                            (_, value) => {
+                                object.insert(key, value);
+                            },
+                        }
+                    }
+                }
+                let collection_name = match collection_name {
+                    Some(collection_name) => collection_name,
+                    None => return Err(Box::new(JsonError::InvalidDocument {
+                        location,
+                        message: format!("Document must have collection name in '{}'", JSON_COLLECTION_KEY),
+                    })),
+                };
+                documents.push((collection_name, location, Value::Object(object)));
+            }
+        },
+    }
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    let document_count = documents.len();
+
+    for (collection_name, location, value) in documents {
+        let index = match database
+            .collections
+            .iter()
+            .position(|collection| collection.name == collection_name)
+        {
+            Some(index) => index,
+            None => {
+                let collection = pb::Collection::from(collection_name.as_str());
+                if let Err(e) = collection.validate_errors() {
+                    return Err(Box::new(e));
+                }
+                database.collections.push(collection);
+                database.collections.len() - 1
+            },
+        };
+
+        if let Err(message) = import_document(&mut database.collections[index], value, options.mode) {
+            return Err(Box::new(JsonError::InvalidDocument { location, message }));
+        }
+    }
+
+    let buf = serialize_database(&database)?;
+    write_database_to_file(&buf, file_path)?;
+
+    Ok(document_count)
+}
+
+/// Converts a document to a JSON object with the id in key `_id`.
+pub(crate) fn document_to_json(document: &pb::Document, mode: JsonMode) -> Map<String, Value> {
+    let mut object = Map::new();
+    let id = match document.id.clone().unwrap_or_default() {
+        Id::Integer(id) => Value::from(id),
+        Id::Text(id) => Value::from(id),
+    };
+    object.insert(String::from(JSON_ID_KEY), id);
+
+    for field in document.data.iter() {
+        let value = match field.value.as_ref().and_then(|value| value.data_type.as_ref()) {
+            Some(value) => value,
+            None => continue,
+        };
+        let json_value = match mode {
+            JsonMode::Typed => typed_json_value(value),
+            JsonMode::Relaxed => relaxed_json_value(value),
+        };
+        object.insert(field.key.clone(), json_value);
+    }
+
+    object
+}
+
+/// Converts a document value to a JSON object with the data type as the key.
+pub(crate) fn typed_json_value(value: &data_type::DataType) -> Value {
+    let json_value = match value {
+        data_type::DataType::Int32(value) => Value::from(*value),
+        data_type::DataType::Uint32(value) => Value::from(*value),
+        data_type::DataType::Bool(value) => Value::from(*value),
+        data_type::DataType::Text(value) => Value::from(value.as_str()),
+        // Infinity and NaN are not JSON numbers
+        data_type::DataType::Decimal(value) if value.is_finite() => Value::from(*value),
+        _ => Value::from(value_to_string(value).unwrap_or_default()),
+    };
+
+    let mut object = Map::new();
+    object.insert(String::from(data_type_name(value)), json_value);
+
+    Value::Object(object)
+}
+
+/// Converts a document value to a plain JSON value.
+/// 
+/// Numbers that JSON cannot represent are converted to strings or `null`.
+fn relaxed_json_value(value: &data_type::DataType) -> Value {
+    let number = match value {
+        data_type::DataType::Int32(value) => Some(Number::from(*value)),
+        data_type::DataType::Int64(value) => Some(Number::from(*value)),
+        data_type::DataType::Uint32(value) => Some(Number::from(*value)),
+        data_type::DataType::Uint64(value) => Some(Number::from(*value)),
+        data_type::DataType::Decimal(value) => Number::from_f64(*value),
+        data_type::DataType::Decimal128(value) => decode_decimal128(value)
+            .and_then(|value| value.to_f64())
+            .and_then(Number::from_f64),
+        data_type::DataType::Int128(value) => decode_int128(value)
+            .and_then(|value| i64::try_from(value).ok())
+            .map(Number::from),
+        data_type::DataType::Uint128(value) => decode_uint128(value)
+            .and_then(|value| u64::try_from(value).ok())
+            .map(Number::from),
+        data_type::DataType::Bool(value) => return Value::from(*value),
+        data_type::DataType::Text(value) => return Value::from(value.as_str()),
+    };
+
+    match (number, value) {
+        (Some(number), _) => Value::Number(number),
+        (None, data_type::DataType::Decimal(_)) => Value::Null,
+        (None, value) => Value::from(value_to_string(value).unwrap_or_default()),
+    }
+}
+
+/// Converts a JSON value to a document value.
+/// 
+/// Returns `None` for `null` values in relaxed mode.
+pub(crate) fn document_value(value: Value, mode: JsonMode) -> Result<Option<DataType>, String> {
+    let value = match (mode, value) {
+        (JsonMode::Typed, Value::Object(object)) if object.len() == 1 => object,
+        (JsonMode::Typed, _) => {
+            return Err(String::from("Value must be an object with the data type as the key"));
+        },
+        (JsonMode::Relaxed, value) => return relaxed_document_value(value),
+    };
+    let (data_type, value) = match value.into_iter().next() {
+        Some(entry) => entry,
+        None => return Err(String::from("Value must be an object with the data type as the key")),
+    };
+    if !DATA_TYPE_NAMES.contains(&data_type.as_str()) {
+        return Err(format!("Unknown data type '{}'", data_type));
+    }
+
+    let value = match value {
+        Value::String(value) => value,
+        Value::Number(value) => value.to_string(),
+        Value::Bool(value) => value.to_string(),
+        _ => return Err(format!("Value of data type '{}' must be a string, number or boolean", data_type)),
+    };
+
+    match DocumentInputDataField::new("", &data_type, &value)
+        .parse_to_document_data_type(&value, &data_type)
+    {
+        Ok(value) => Ok(Some(value)),
+        Err(e) => Err(format!("Data type '{}' is not valid: {}", data_type, e)),
+    }
+}
+
+/// Infers the data type of a plain JSON value.
+fn relaxed_document_value(value: Value) -> Result<Option<DataType>, String> {
+    let value = match value {
+        Value::Null => return Ok(None),
+        Value::Bool(value) => data_type::DataType::Bool(value),
+        Value::String(value) => data_type::DataType::Text(value),
+        Value::Number(number) => {
+            if let Some(value) = number.as_i64() {
+                match i32::try_from(value) {
+                    Ok(value) => data_type::DataType::Int32(value),
+                    Err(_) => data_type::DataType::Int64(value),
+                }
+            } else if let Some(value) = number.as_u64() {
+                data_type::DataType::Uint64(value)
+            } else {
+                data_type::DataType::Decimal(number.as_f64().unwrap_or_default())
+            }
+        },
+        Value::Array(_) | Value::Object(_) => {
+            return Err(String::from("Arrays and objects are not supported as values"));
+        },
+    };
+
+    Ok(Some(DataType { data_type: Some(value) }))
+}
+
+/// Converts a JSON document and inserts it to a collection.
+/// 
+/// Returns the reason if the document is not valid.
+fn import_document(
+    collection: &mut pb::Collection,
+    value: Value,
+    mode: JsonMode,
+) -> Result<(), String>
+{
+    let object = match value {
+        Value::Object(object) => object,
+        _ => return Err(String::from("Document must be an object")),
+    };
+
+    let mut document_id = None;
+    let mut data = IndexMap::new();
+    for (key, value) in object {
+        if key == JSON_ID_KEY {
+            document_id = match value {
+                Value::Number(id) => id.as_u64().map(Id::Integer),
+                Value::String(id) => Some(Id::Text(id)),
+                _ => None,
+            };
+            if document_id.is_none() {
+                return Err(String::from("Document id must be a positive integer or a string"));
+            }
+            continue;
+        }
+        if let Some(value) = document_value(value, mode).map_err(|e| format!("Field '{}': {}", key, e))? {
+            data.insert(key, value);
+        }
+    }
+
+    insert_imported_document(collection, document_id, data).map_err(|e| e.to_string())
+}
+
+/// Parses a JSON value.
+fn parse_json(json: &str) -> Result<Value, JsonError> {
+    serde_json::from_str(json).map_err(|e| JsonError::Parse(e.to_string()))
+}
+
+/// Parses JSON Lines. Empty lines are skipped.
+/// 
+/// Returns the values with their line numbers.
+fn parse_json_lines(json: &str) -> Result<Vec<(String, Value)>, JsonError> {
+    let mut values = Vec::new();
+
+    for (index, line) in json.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(value) => values.push((format!("line {}", index + 1), value)),
+            Err(e) => return Err(JsonError::Parse(format!("line {}: {}", index + 1, e))),
+        }
+    }
+
+    Ok(values)
+}
+
+/// Writes JSON values as JSON Lines.
+fn to_json_lines(values: Vec<Value>) -> serde_json::Result<String> {
+    let mut json = String::new();
+
+    for value in values {
+        json.push_str(&serde_json::to_string(&value)?);
+        json.push('\n');
+    }
+
+    Ok(json)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::document::encode_decimal128;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn document() -> pb::Document {
+        let mut data = IndexMap::new();
+        let values = [
+            ("name", data_type::DataType::Text(String::from("John"))),
+            ("age", data_type::DataType::Int32(35)),
+            ("big", data_type::DataType::Int64(i64::MAX)),
+            ("price", data_type::DataType::Decimal128(encode_decimal128(
+                &Decimal::from_str("10.50").unwrap()
+            ))),
+            ("ratio", data_type::DataType::Decimal(0.1)),
+            ("active", data_type::DataType::Bool(true)),
+        ];
+        for (key, value) in values {
+            data.insert(String::from(key), DataType { data_type: Some(value) });
+        }
+
+        let mut document = pb::Document {
+            id: Some(Id::Integer(1)),
+            data: Vec::new(),
+        };
+        document.set_data(data);
+        document
+    }
+
+    #[test]
+    fn test_typed_json_round_trip() {
+        let document = document();
+        let object = document_to_json(&document, JsonMode::Typed);
+        assert_eq!(
+            Value::Object(object.clone()).to_string(),
+            concat!(
+                r#"{"_id":1,"name":{"Text":"John"},"age":{"Int32":35},"big":{"Int64":"9223372036854775807"},"#,
+                r#""price":{"Decimal128":"10.50"},"ratio":{"Decimal":0.1},"active":{"Bool":true}}"#
+            )
+        );
+
+        let mut collection = pb::Collection::from("test");
+        import_document(&mut collection, Value::Object(object), JsonMode::Typed).unwrap();
+        assert_eq!(collection.documents[0], document);
+    }
+
+    #[test]
+    fn test_relaxed_json_values() {
+        let object = document_to_json(&document(), JsonMode::Relaxed);
+        assert_eq!(
+            Value::Object(object.clone()).to_string(),
+            r#"{"_id":1,"name":"John","age":35,"big":9223372036854775807,"price":10.5,"ratio":0.1,"active":true}"#
+        );
+
+        let mut collection = pb::Collection::from("test");
+        import_document(&mut collection, Value::Object(object), JsonMode::Relaxed).unwrap();
+        let data = collection.documents[0].data();
+        assert_eq!(data["age"].data_type, Some(data_type::DataType::Int32(35)));
+        assert_eq!(data["big"].data_type, Some(data_type::DataType::Int64(i64::MAX)));
+        assert_eq!(data["price"].data_type, Some(data_type::DataType::Decimal(10.5)));
+
+        let value = serde_json::json!({ "tags": ["a", "b"] });
+        assert!(import_document(&mut collection, value, JsonMode::Relaxed).is_err());
+        let value = serde_json::json!({ "age": { "Int16": 1 } });
+        assert!(import_document(&mut collection, value, JsonMode::Typed).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+// Collection-wide field migrations.
+// Migrations rename, drop or convert a field in every document of a collection.
+
+use std::{
+    path::Path,
+    error::Error,
+    fmt::{self, Display},
+};
+use indexmap::IndexMap;
+use crate::{
+    DocumentInputDataField,
+    storage::{
+        error::{
+            DatabaseError,
+            CollectionError,
+            DocumentError,
+            ParseError,
+        },
+        pb::document::DataType,
+        pb::document::Id,
+        document::{
+            DATA_TYPE_NAMES,
+            value_to_string,
+        },
+        serialize_database,
+        deserialize_database,
+        read_database_file,
+        write_database_to_file_with_operation,
+        change_stream::ChangeOperation,
+    },
+    util::has_whitespaces,
+};
+
+/// Field migration that is applied to every document in a collection.
+/// 
+/// Documents that don't have the field are not changed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldMigration {
+    /// Renames a field. The field keeps its position in the documents.
+    Rename {
+        field: String,
+        new_field: String,
+    },
+
+    /// Removes a field.
+    Drop {
+        field: String,
+    },
+
+    /// Converts a field's values to another data type.
+    /// 
+    /// Values are converted with the same parsing rules as input data,
+    /// so `Text` value "42" can be converted to `Int32` value 42.
+    Convert {
+        field: String,
+        data_type: String,
+    },
+}
+
+impl FieldMigration {
+    /// Gets the name of the migrated field.
+    pub fn field(&self) -> &str {
+        match self {
+            FieldMigration::Rename { field, .. } => field,
+            FieldMigration::Drop { field } => field,
+            FieldMigration::Convert { field, .. } => field,
+        }
+    }
+
+    /// Validates migration by checking its field names and data type.
+    /// 
+    /// Returns any errors that may occur during the process.
+    pub fn validate_errors(&self) -> Result<(), Box<dyn Error>> {
+        if let FieldMigration::Rename { field, new_field } = self {
+            if new_field.is_empty() {
+                return Err(Box::new(DocumentError::EmptyFieldName));
+            }
+            if has_whitespaces(new_field) {
+                return Err(Box::new(DocumentError::FieldNameHasWhitespace));
+            }
+            if new_field == field {
+                return Err(Box::new(DocumentError::FieldExists));
+            }
+        }
+        if let FieldMigration::Convert { data_type, .. } = self {
+            if !DATA_TYPE_NAMES.contains(&data_type.as_str()) {
+                return Err(Box::new(ParseError::Unknown));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies migration to document data that has the field.
+    fn apply(&self, data: &mut IndexMap<String, DataType>) -> Result<(), Box<dyn Error>> {
+        match self {
+            FieldMigration::Rename { field, new_field } => {
+                if data.contains_key(new_field) {
+                    return Err(Box::new(DocumentError::FieldExists));
+                }
+                if let Some((index, _, value)) = data.shift_remove_full(field) {
+                    data.shift_insert(index, new_field.clone(), value);
+                }
+            },
+            FieldMigration::Drop { field } => {
+                data.shift_remove(field);
+            },
+            FieldMigration::Convert { field, data_type } => {
+                let value = match data
+                    .get(field)
+                    .and_then(|value| value.data_type.as_ref())
+                    .and_then(value_to_string)
+                {
+                    Some(value) => value,
+                    None => return Err(Box::new(ParseError::Unknown)),
+                };
+                let input_data = DocumentInputDataField::new(field, data_type, &value);
+                let converted_value = input_data.parse_to_document_data_type(
+                    input_data.value(),
+                    input_data.data_type()
+                )?;
+                data.insert(field.clone(), converted_value);
+            },
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for FieldMigration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldMigration::Rename { field, new_field } => {
+                write!(f, "rename field '{}' to '{}'", field, new_field)
+            },
+            FieldMigration::Drop { field } => write!(f, "drop field '{}'", field),
+            FieldMigration::Convert { field, data_type } => {
+                write!(f, "convert field '{}' to '{}'", field, data_type)
+            },
+        }
+    }
+}
+
+/// Result of a field migration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMigrationResultDto {
+    /// Number of documents that were migrated.
+    pub converted_count: usize,
+
+    /// Number of documents that could not be migrated.
+    /// These documents were not changed.
+    pub failed_count: usize,
+
+    /// Ids of the documents that could not be migrated.
+    pub failed_document_ids: Vec<Id>,
+}
+
+/// Migrates a field in every document of a collection.
+/// 
+/// Documents that fail the migration or don't follow the collection's
+/// schema after it are not changed. All other documents are migrated
+/// and the modified database is written to the database file at once.
+/// Previous versions are kept as revisions if the collection has history mode.
+/// 
+/// Returns the number of migrated and failed documents.
+pub fn migrate_field_in_collection(
+    file_path: &Path,
+    collection_name: &str,
+    migration: &FieldMigration,
+) -> Result<FieldMigrationResultDto, Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    migration.validate_errors()?;
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    let collection = match database
+        .collections_mut()
+        .iter_mut()
+        .find(|collection| collection.name() == collection_name)
+    {
+        Some(collection) => collection,
+        None => return Err(Box::new(CollectionError::NotFound)),
+    };
+
+    let mut result = FieldMigrationResultDto {
+        converted_count: 0,
+        failed_count: 0,
+        failed_document_ids: Vec::new(),
+    };
+
+    let mut previous_documents = Vec::new();
+    for document in collection.documents.iter_mut() {
+        let mut data = document.data();
+        if !data.contains_key(migration.field()) {
+            continue;
+        }
+
+        let migrated = match migration.apply(&mut data) {
+            Ok(()) => match &collection.schema {
+                Some(schema) => schema.validate_document(&data).is_ok(),
+                None => true,
+            },
+            Err(_) => false,
+        };
+
+        if migrated {
+            previous_documents.push(document.clone());
+            document.set_data(data);
+            result.converted_count += 1;
+        } else {
+            result.failed_count += 1;
+            result.failed_document_ids.push(document.id.clone().unwrap_or_default());
+        }
+    }
+
+    collection.record_revisions(previous_documents, false);
+
+    if result.converted_count > 0 {
+        let buf = serialize_database(&database)?;
+        write_database_to_file_with_operation(&buf, file_path, ChangeOperation::Update)?;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::pb::document::data_type;
+
+    fn value(value: data_type::DataType) -> DataType {
+        DataType { data_type: Some(value) }
+    }
+
+    fn create_test_data() -> IndexMap<String, DataType> {
+        let mut data = IndexMap::new();
+        data.insert(String::from("name"), value(data_type::DataType::Text(String::from("John"))));
+        data.insert(String::from("age"), value(data_type::DataType::Text(String::from("42"))));
+        data.insert(String::from("active"), value(data_type::DataType::Bool(true)));
+        data
+    }
+
+    #[test]
+    fn test_rename_field_keeps_position() {
+        let mut data = create_test_data();
+        let migration = FieldMigration::Rename {
+            field: String::from("age"),
+            new_field: String::from("years"),
+        };
+        assert!(migration.apply(&mut data).is_ok());
+
+        let keys: Vec<&str> = data.keys().map(|key| key.as_str()).collect();
+        assert_eq!(keys, vec!["name", "years", "active"]);
+
+        let migration = FieldMigration::Rename {
+            field: String::from("years"),
+            new_field: String::from("name"),
+        };
+        assert!(migration.apply(&mut data).is_err());
+    }
+
+    #[test]
+    fn test_convert_field() {
+        let mut data = create_test_data();
+        let migration = FieldMigration::Convert {
+            field: String::from("age"),
+            data_type: String::from("Int32"),
+        };
+        assert!(migration.apply(&mut data).is_ok());
+        assert_eq!(data.get("age"), Some(&value(data_type::DataType::Int32(42))));
+
+        let migration = FieldMigration::Convert {
+            field: String::from("name"),
+            data_type: String::from("Int32"),
+        };
+        assert!(migration.apply(&mut data).is_err());
+
+        let migration = FieldMigration::Convert {
+            field: String::from("age"),
+            data_type: String::from("Integer"),
+        };
+        assert!(migration.validate_errors().is_err());
+    }
+}
@@ -0,0 +1,491 @@
+// Collection schema validation and inference.
+// Documents are validated against their collection's schema when they are written.
+// Collections without a schema can be scanned to report their implicit schema.
+
+use std::{
+    cmp::Ordering,
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    sync::{
+        Mutex,
+        OnceLock,
+    },
+};
+use indexmap::IndexMap;
+use regex::Regex;
+use crate::storage::{
+    pb,
+    pb::document::DataType,
+    error::{
+        SchemaError,
+        SchemaValidationError,
+        SchemaViolation,
+        SchemaViolationKind,
+    },
+    document::{
+        DATA_TYPE_NAMES,
+        data_type_name,
+    },
+    pb::document::data_type,
+    compare::{
+        compare_values,
+        values_equal,
+    },
+};
+
+// Implements methods for protobuf type
+impl pb::CollectionSchema {
+    pub fn fields(&self) -> &Vec<pb::FieldSchema> {
+        &self.fields
+    }
+
+    /// Finds a field schema by field name.
+    pub fn field(&self, name: &str) -> Option<&pb::FieldSchema> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Validates schema by checking its field schemas.
+    ///
+    /// Returns any errors that may occur during the process.
+    pub fn validate_errors(&self) -> Result<(), SchemaError> {
+        let mut field_names = HashSet::new();
+
+        for field in self.fields.iter() {
+            if field.name.is_empty() {
+                return Err(SchemaError::EmptyFieldName);
+            }
+            if !field_names.insert(field.name.as_str()) {
+                return Err(SchemaError::DuplicateField(field.name.clone()));
+            }
+            for data_type in field.data_types.iter() {
+                if !DATA_TYPE_NAMES.contains(&data_type.as_str()) {
+                    return Err(SchemaError::UnknownDataType(field.name.clone(), data_type.clone()));
+                }
+            }
+            if !field.pattern.is_empty() && compile_pattern(&field.pattern).is_none() {
+                return Err(SchemaError::InvalidPattern(field.name.clone()));
+            }
+            if let (Some(min), Some(max)) = (field.min_value(), field.max_value()) {
+                if compare_values(min, max) == Some(Ordering::Greater) {
+                    return Err(SchemaError::MinGreaterThanMax(field.name.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates document data against this schema.
+    ///
+    /// Returns an error that lists every violation of the schema.
+    pub fn validate_document(&self, data: &IndexMap<String, DataType>) -> Result<(), SchemaValidationError> {
+        let mut violations = Vec::new();
+
+        for field in self.fields.iter() {
+            match data.get(&field.name).and_then(|value| value.data_type.as_ref()) {
+                Some(value) => field.validate_value(value, &mut violations),
+                None => {
+                    if field.required {
+                        violations.push(SchemaViolation {
+                            field: field.name.clone(),
+                            kind: SchemaViolationKind::MissingField,
+                        });
+                    }
+                },
+            }
+        }
+
+        if self.forbid_unknown_fields {
+            for key in data.keys() {
+                if self.field(key).is_none() {
+                    violations.push(SchemaViolation {
+                        field: key.clone(),
+                        kind: SchemaViolationKind::UnknownField,
+                    });
+                }
+            }
+        }
+
+        if !violations.is_empty() {
+            return Err(SchemaValidationError { violations });
+        }
+
+        Ok(())
+    }
+}
+
+// Implements methods for protobuf type
+impl pb::FieldSchema {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the min value if it is set.
+    fn min_value(&self) -> Option<&pb::document::data_type::DataType> {
+        self.min.as_ref().and_then(|min| min.data_type.as_ref())
+    }
+
+    /// Gets the max value if it is set.
+    fn max_value(&self) -> Option<&pb::document::data_type::DataType> {
+        self.max.as_ref().and_then(|max| max.data_type.as_ref())
+    }
+
+    /// Validates a field value against this field schema.
+    ///
+    /// Adds the found violations to `violations`.
+    fn validate_value(
+        &self,
+        value: &pb::document::data_type::DataType,
+        violations: &mut Vec<SchemaViolation>,
+    ) {
+        let mut add_violation = |kind| {
+            violations.push(SchemaViolation {
+                field: self.name.clone(),
+                kind,
+            });
+        };
+
+        let found_data_type = data_type_name(value);
+        if !self.data_types.is_empty() && !self.data_types.iter().any(|data_type| data_type == found_data_type) {
+            // Other constraints don't apply to values of wrong data type
+            return add_violation(SchemaViolationKind::DataType(
+                self.data_types.clone(),
+                found_data_type.to_string(),
+            ));
+        }
+
+        // Values that cannot be compared with min or max are violations
+        if let Some(min) = self.min_value() {
+            match compare_values(value, min) {
+                Some(Ordering::Greater) | Some(Ordering::Equal) => {},
+                _ => add_violation(SchemaViolationKind::LessThanMin),
+            }
+        }
+        if let Some(max) = self.max_value() {
+            match compare_values(value, max) {
+                Some(Ordering::Less) | Some(Ordering::Equal) => {},
+                _ => add_violation(SchemaViolationKind::GreaterThanMax),
+            }
+        }
+
+        if let pb::document::data_type::DataType::Text(text) = value {
+            if self.max_length > 0 && text.chars().count() as u64 > self.max_length {
+                add_violation(SchemaViolationKind::TooLong(self.max_length));
+            }
+            if !self.pattern.is_empty() {
+                // Schemas with invalid patterns are rejected by `validate_errors`
+                match compile_pattern(&self.pattern) {
+                    Some(pattern) if pattern.is_match(text) => {},
+                    _ => add_violation(SchemaViolationKind::PatternMismatch),
+                }
+            }
+        }
+
+        if !self.allowed_values.is_empty() && !self.allowed_values
+            .iter()
+            .filter_map(|allowed_value| allowed_value.data_type.as_ref())
+            .any(|allowed_value| values_equal(value, allowed_value, false))
+        {
+            add_violation(SchemaViolationKind::ValueNotAllowed);
+        }
+    }
+}
+
+/// Compiles a field schema pattern.
+/// 
+/// Compiled patterns are cached for the lifetime of the process,
+/// so documents can be validated without compiling the pattern again.
+/// 
+/// Returns `None` if the pattern is not a valid regular expression.
+fn compile_pattern(pattern: &str) -> Option<Regex> {
+    static PATTERNS: OnceLock<Mutex<HashMap<String, Regex>>> = OnceLock::new();
+
+    let mut patterns = PATTERNS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(regex) = patterns.get(pattern) {
+        return Some(regex.clone());
+    }
+
+    let regex = Regex::new(pattern).ok()?;
+    patterns.insert(pattern.to_string(), regex.clone());
+
+    Some(regex)
+}
+
+
+/// Maximum number of sample values reported for each field.
+pub const SAMPLE_VALUE_COUNT: usize = 3;
+
+/// Implicit schema of a collection, inferred from its documents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaReportDto {
+    /// Number of scanned documents.
+    pub document_count: usize,
+
+    /// Fields in the order they were first seen.
+    pub fields: Vec<FieldReportDto>,
+}
+
+/// How a field occurs in a collection's documents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldReportDto {
+    /// Path of the field. Documents don't have nested fields,
+    /// so this is the field name.
+    pub path: String,
+
+    /// Number of documents that have the field.
+    pub present_count: usize,
+
+    /// Number of documents where the field has no value.
+    pub null_count: usize,
+
+    /// Data types that occur in the field and their counts.
+    pub data_types: IndexMap<String, usize>,
+
+    /// A few distinct values of the field.
+    pub sample_values: Vec<data_type::DataType>,
+}
+
+impl FieldReportDto {
+    fn new(path: &str) -> Self {
+        Self {
+            path: String::from(path),
+            present_count: 0,
+            null_count: 0,
+            data_types: IndexMap::new(),
+            sample_values: Vec::new(),
+        }
+    }
+}
+
+impl SchemaReportDto {
+    /// Infers the implicit schema of documents.
+    pub fn from_documents(documents: &[pb::Document]) -> Self {
+        let mut fields: IndexMap<String, FieldReportDto> = IndexMap::new();
+
+        for document in documents {
+            for field in document.data.iter() {
+                let report = fields
+                    .entry(field.key.clone())
+                    .or_insert_with(|| FieldReportDto::new(&field.key));
+                report.present_count += 1;
+
+                match field.value.as_ref().and_then(|value| value.data_type.as_ref()) {
+                    Some(value) => {
+                        *report.data_types
+                            .entry(data_type_name(value).to_string())
+                            .or_insert(0) += 1;
+
+                        if report.sample_values.len() < SAMPLE_VALUE_COUNT
+                            && !report.sample_values.contains(value)
+                        {
+                            report.sample_values.push(value.clone());
+                        }
+                    },
+                    None => report.null_count += 1,
+                }
+            }
+        }
+
+        Self {
+            document_count: documents.len(),
+            fields: fields.into_values().collect(),
+        }
+    }
+
+    /// Creates a schema that all scanned documents follow.
+    /// 
+    /// Fields that all documents have are required, and each field
+    /// allows the data types that occur in it.
+    pub fn suggested_schema(&self) -> pb::CollectionSchema {
+        let fields = self.fields
+            .iter()
+            .map(|field| pb::FieldSchema {
+                name: field.path.clone(),
+                required: field.present_count == self.document_count && field.null_count == 0,
+                data_types: field.data_types.keys().cloned().collect(),
+                ..Default::default()
+            })
+            .collect();
+
+        pb::CollectionSchema {
+            fields,
+            forbid_unknown_fields: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pb::document::data_type;
+
+    fn value(data_type: data_type::DataType) -> DataType {
+        DataType {
+            data_type: Some(data_type),
+        }
+    }
+
+    fn test_schema() -> pb::CollectionSchema {
+        pb::CollectionSchema {
+            fields: vec![
+                pb::FieldSchema {
+                    name: String::from("name"),
+                    required: true,
+                    data_types: vec![String::from("Text")],
+                    max_length: 10,
+                    ..Default::default()
+                },
+                pb::FieldSchema {
+                    name: String::from("age"),
+                    data_types: vec![String::from("Int32"), String::from("Int64")],
+                    min: Some(value(data_type::DataType::Int32(0))),
+                    max: Some(value(data_type::DataType::Int32(150))),
+                    ..Default::default()
+                },
+                pb::FieldSchema {
+                    name: String::from("email"),
+                    pattern: String::from("^[^@]+@[^@]+$"),
+                    ..Default::default()
+                },
+                pb::FieldSchema {
+                    name: String::from("role"),
+                    allowed_values: vec![
+                        value(data_type::DataType::Text(String::from("admin"))),
+                        value(data_type::DataType::Text(String::from("user"))),
+                    ],
+                    ..Default::default()
+                },
+            ],
+            forbid_unknown_fields: true,
+        }
+    }
+
+    #[test]
+    fn test_validate_schema() {
+        assert!(test_schema().validate_errors().is_ok());
+
+        let mut schema = test_schema();
+        schema.fields[0].data_types.push(String::from("Int33"));
+        assert_eq!(
+            schema.validate_errors(),
+            Err(SchemaError::UnknownDataType(String::from("name"), String::from("Int33")))
+        );
+
+        let mut schema = test_schema();
+        schema.fields[2].pattern = String::from("[");
+        assert_eq!(schema.validate_errors(), Err(SchemaError::InvalidPattern(String::from("email"))));
+
+        let mut schema = test_schema();
+        schema.fields[1].min = Some(value(data_type::DataType::Int32(200)));
+        assert_eq!(schema.validate_errors(), Err(SchemaError::MinGreaterThanMax(String::from("age"))));
+    }
+
+    #[test]
+    fn test_validate_document() {
+        let schema = test_schema();
+        let mut data = IndexMap::new();
+        data.insert(String::from("name"), value(data_type::DataType::Text(String::from("John"))));
+        data.insert(String::from("age"), value(data_type::DataType::Int64(42)));
+        data.insert(String::from("email"), value(data_type::DataType::Text(String::from("john@example.com"))));
+        data.insert(String::from("role"), value(data_type::DataType::Text(String::from("admin"))));
+        assert!(schema.validate_document(&data).is_ok());
+    }
+
+    #[test]
+    fn test_validate_document_lists_every_violation() {
+        let schema = test_schema();
+        let mut data = IndexMap::new();
+        data.insert(String::from("age"), value(data_type::DataType::Int32(-1)));
+        data.insert(String::from("email"), value(data_type::DataType::Text(String::from("john"))));
+        data.insert(String::from("role"), value(data_type::DataType::Text(String::from("owner"))));
+        data.insert(String::from("nickname"), value(data_type::DataType::Text(String::from("Johnny"))));
+
+        let error = schema.validate_document(&data).unwrap_err();
+        let violations: Vec<(&str, &SchemaViolationKind)> = error.violations
+            .iter()
+            .map(|violation| (violation.field.as_str(), &violation.kind))
+            .collect();
+        assert_eq!(violations, vec![
+            ("name", &SchemaViolationKind::MissingField),
+            ("age", &SchemaViolationKind::LessThanMin),
+            ("email", &SchemaViolationKind::PatternMismatch),
+            ("role", &SchemaViolationKind::ValueNotAllowed),
+            ("nickname", &SchemaViolationKind::UnknownField),
+        ]);
+    }
+
+    #[test]
+    fn test_validate_document_data_type_and_length() {
+        let schema = test_schema();
+        let mut data = IndexMap::new();
+        data.insert(String::from("name"), value(data_type::DataType::Text(String::from("John Smith Jr."))));
+        data.insert(String::from("age"), value(data_type::DataType::Text(String::from("42"))));
+
+        let error = schema.validate_document(&data).unwrap_err();
+        assert_eq!(error.violations, vec![
+            SchemaViolation {
+                field: String::from("name"),
+                kind: SchemaViolationKind::TooLong(10),
+            },
+            SchemaViolation {
+                field: String::from("age"),
+                kind: SchemaViolationKind::DataType(
+                    vec![String::from("Int32"), String::from("Int64")],
+                    String::from("Text")
+                ),
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_infer_schema_report() {
+        let mut documents = Vec::new();
+        for (index, age) in [
+            Some(data_type::DataType::Int32(30)),
+            Some(data_type::DataType::Int64(40)),
+            Some(data_type::DataType::Int32(30)),
+            None,
+        ].into_iter().enumerate() {
+            let mut data = IndexMap::new();
+            data.insert(String::from("name"), value(data_type::DataType::Text(format!("Person {}", index))));
+            data.insert(String::from("age"), DataType { data_type: age });
+            if index == 0 {
+                data.insert(String::from("email"), value(data_type::DataType::Text(String::from("a@b.c"))));
+            }
+            let mut document = pb::Document::default();
+            document.set_data(data);
+            documents.push(document);
+        }
+
+        let report = SchemaReportDto::from_documents(&documents);
+        assert_eq!(report.document_count, 4);
+        let paths: Vec<&str> = report.fields.iter().map(|field| field.path.as_str()).collect();
+        assert_eq!(paths, vec!["name", "age", "email"]);
+
+        let name = &report.fields[0];
+        assert_eq!(name.present_count, 4);
+        assert_eq!(name.sample_values.len(), SAMPLE_VALUE_COUNT);
+
+        let age = &report.fields[1];
+        assert_eq!(age.present_count, 4);
+        assert_eq!(age.null_count, 1);
+        assert_eq!(age.data_types.get("Int32"), Some(&2));
+        assert_eq!(age.data_types.get("Int64"), Some(&1));
+        assert_eq!(age.sample_values, vec![
+            data_type::DataType::Int32(30),
+            data_type::DataType::Int64(40),
+        ]);
+
+        let schema = report.suggested_schema();
+        assert!(schema.validate_errors().is_ok());
+        assert!(schema.field("name").unwrap().required);
+        assert!(!schema.field("age").unwrap().required);
+        assert!(!schema.field("email").unwrap().required);
+        assert_eq!(schema.field("age").unwrap().data_types, vec!["Int32", "Int64"]);
+    }
+}
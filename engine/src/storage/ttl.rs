@@ -0,0 +1,220 @@
+// Document time-to-live (TTL) expiry.
+// Collections can have a TTL policy that expires documents by a time field.
+// Expired documents are not returned by reads and they are removed by sweeps.
+
+use std::{
+    error::Error,
+    fmt,
+    path::Path,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use crate::storage::{
+    error::{
+        DatabaseError,
+        CollectionError,
+    },
+    pb,
+    pb::document::data_type::DataType,
+    serialize_database,
+    deserialize_database,
+    read_database_file,
+    write_database_to_file,
+};
+
+// Implements methods for protobuf type
+impl pb::TtlPolicy {
+    /// Creates a new TTL policy.
+    /// 
+    /// Documents expire `expire_after_seconds` after the time in the field.
+    /// If it is 0, the field has the expiry time.
+    pub fn new(field: &str, expire_after_seconds: u64) -> Self {
+        Self {
+            field: field.to_string(),
+            expire_after_seconds,
+        }
+    }
+
+    /// Validates TTL policy by checking its field values.
+    pub fn validate_errors(&self) -> Result<(), CollectionError> {
+        if self.field.is_empty() {
+            return Err(CollectionError::EmptyTtlField);
+        }
+
+        Ok(())
+    }
+
+    /// Gets the time when a document expires as a Unix timestamp in seconds.
+    /// 
+    /// Returns `None` if the document doesn't have the field
+    /// or its value is not a Unix timestamp or an RFC 3339 time.
+    pub fn expires_at(&self, document: &pb::Document) -> Option<i64> {
+        let value = document.data
+            .iter()
+            .find(|field| field.key == self.field)
+            .and_then(|field| field.value.as_ref())
+            .and_then(|value| value.data_type.as_ref())?;
+
+        let timestamp = match value {
+            DataType::Int32(timestamp) => i64::from(*timestamp),
+            DataType::Int64(timestamp) => *timestamp,
+            DataType::Uint32(timestamp) => i64::from(*timestamp),
+            DataType::Uint64(timestamp) => i64::try_from(*timestamp).ok()?,
+            DataType::Text(time) => DateTime::parse_from_rfc3339(time).ok()?.timestamp(),
+            _ => return None,
+        };
+
+        timestamp.checked_add(i64::try_from(self.expire_after_seconds).ok()?)
+    }
+}
+
+impl fmt::Display for pb::TtlPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.expire_after_seconds == 0 {
+            write!(f, "Expires at the time in field '{}'", self.field)
+        } else {
+            write!(f, "Expires {} seconds after the time in field '{}'", self.expire_after_seconds, self.field)
+        }
+    }
+}
+
+impl pb::Collection {
+    /// Checks if a document in this collection has expired at the time.
+    /// 
+    /// Documents don't expire if the collection has no TTL policy.
+    pub fn is_expired(&self, document: &pb::Document, now: i64) -> bool {
+        match &self.ttl {
+            Some(ttl) => ttl.expires_at(document).is_some_and(|expires_at| expires_at <= now),
+            None => false,
+        }
+    }
+
+    /// Removes the documents in this collection that have expired at the time.
+    /// 
+    /// Removed documents are kept as tombstones if this collection has history mode.
+    /// 
+    /// Returns the number of removed documents.
+    pub fn remove_expired_documents(&mut self, now: i64) -> usize {
+        if self.ttl.is_none() {
+            return 0;
+        }
+
+        let documents = std::mem::take(&mut self.documents);
+        let (expired, documents): (Vec<_>, Vec<_>) = documents
+            .into_iter()
+            .partition(|document| self.is_expired(document, now));
+        self.documents = documents;
+
+        let removed_count = expired.len();
+        self.record_revisions(expired, true);
+
+        removed_count
+    }
+}
+
+/// Gets the current time as a Unix timestamp in seconds.
+pub(crate) fn now() -> i64 {
+    Utc::now().timestamp()
+}
+
+/// Sets a collection's TTL policy. `None` removes the policy.
+/// 
+/// Writes the modified database to the database file.
+pub fn set_collection_ttl_in_database(
+    collection_name: &str,
+    file_path: &Path,
+    ttl: Option<pb::TtlPolicy>,
+) -> Result<(), Box<dyn Error>>
+{
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+    if let Some(ttl) = &ttl {
+        ttl.validate_errors()?;
+    }
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    let collection = match database
+        .collections
+        .iter_mut()
+        .find(|collection| collection.name == collection_name)
+    {
+        Some(collection) => collection,
+        None => return Err(Box::new(CollectionError::NotFound)),
+    };
+    collection.ttl = ttl;
+
+    let buf = serialize_database(&database)?;
+    write_database_to_file(&buf, file_path)?;
+
+    Ok(())
+}
+
+/// Removes expired documents from all collections in a database.
+/// 
+/// The database file is written only if documents were removed.
+/// 
+/// Returns the number of removed documents.
+pub fn purge_expired_documents_in_database(file_path: &Path) -> Result<usize, Box<dyn Error>> {
+    if !file_path.is_file() {
+        return Err(Box::new(DatabaseError::NotFound));
+    }
+
+    let mut database = deserialize_database(&read_database_file(file_path)?)?;
+    let now = now();
+    let removed_count = database.collections
+        .iter_mut()
+        .map(|collection| collection.remove_expired_documents(now))
+        .sum();
+
+    if removed_count > 0 {
+        let buf = serialize_database(&database)?;
+        write_database_to_file(&buf, file_path)?;
+    }
+
+    Ok(removed_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::pb::document::Id;
+
+    #[test]
+    fn test_remove_expired_documents() {
+        let now = 1_700_000_000;
+        let mut collection = pb::Collection::from("sessions");
+        collection.documents = vec![
+            pb::Document::test_document(1, "created_at", DataType::Int64(now - 120)),
+            pb::Document::test_document(2, "created_at", DataType::Int64(now - 30)),
+            pb::Document::test_document(3, "created_at", DataType::Text("2023-11-14T22:00:00Z".to_string())),
+            pb::Document::test_document(4, "created_at", DataType::Bool(true)),
+        ];
+        assert_eq!(collection.remove_expired_documents(now), 0);
+
+        collection.ttl = Some(pb::TtlPolicy::new("created_at", 60));
+        assert!(collection.is_expired(&collection.documents[0], now));
+        assert!(!collection.is_expired(&collection.documents[1], now));
+        assert!(collection.is_expired(&collection.documents[2], now));
+
+        assert_eq!(collection.remove_expired_documents(now), 2);
+        let ids: Vec<_> = collection.documents.iter().map(|document| document.id.clone()).collect();
+        assert_eq!(ids, vec![Some(Id::Integer(2)), Some(Id::Integer(4))]);
+        assert!(collection.revisions.is_empty());
+
+        // Expired documents are kept as tombstones with history mode
+        collection.history = true;
+        collection.documents.push(pb::Document::test_document(7, "created_at", DataType::Int64(now - 120)));
+        assert_eq!(collection.remove_expired_documents(now), 1);
+        assert_eq!(collection.revisions.len(), 1);
+        assert!(collection.revisions[0].deleted);
+        assert_eq!(collection.revisions[0].document_id(), Some(&Id::Integer(7)));
+
+        // Absolute expiry time
+        collection.ttl = Some(pb::TtlPolicy::new("created_at", 0));
+        assert!(collection.is_expired(&pb::Document::test_document(5, "created_at", DataType::Int64(now)), now));
+        assert!(!collection.is_expired(&pb::Document::test_document(6, "created_at", DataType::Int64(now + 1)), now));
+    }
+}
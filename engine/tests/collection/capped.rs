@@ -0,0 +1,228 @@
+use engine::{
+    Engine,
+    DocumentInputDataField,
+    storage::{
+        DB_FILE_EXTENSION,
+        collection::CollectionOptions,
+        pb::CappedLimits,
+        pb::document::Id,
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+
+#[test]
+fn capped_collection_evicts_oldest_documents() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "events";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let options = CollectionOptions {
+        capped: Some(CappedLimits::new(3, 0)),
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .create_collection_with_options(collection_name, &file_path, &options);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_collection(collection_name, &file_path);
+    let collection = result.data.unwrap().unwrap();
+    assert_eq!(collection.capped(), Some(&CappedLimits::new(3, 0)));
+
+    for _ in 0..5 {
+        let result = engine
+            .storage_api()
+            .create_document(&file_path, collection_name, create_document_input_data());
+        assert!(result.success);
+    }
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, collection_name, None);
+    let ids: Vec<Id> = result.data
+        .unwrap()
+        .iter()
+        .map(|document| document.id().clone())
+        .collect();
+    assert_eq!(ids, vec![Id::Integer(3), Id::Integer(4), Id::Integer(5)]);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn capped_collection_max_size_fail() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "events";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let options = CollectionOptions {
+        capped: Some(CappedLimits::new(0, 0)),
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .create_collection_with_options(collection_name, &file_path, &options);
+    assert!(!result.success);
+
+    let options = CollectionOptions {
+        capped: Some(CappedLimits::new(0, 100)),
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .create_collection_with_options(collection_name, &file_path, &options);
+    assert!(result.success);
+
+    let mut data = create_document_input_data();
+    data.push(DocumentInputDataField::new("message", "Text", &"x".repeat(200)));
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, data);
+    assert!(!result.success);
+    assert!(result.error.is_some());
+
+    for _ in 0..5 {
+        let result = engine
+            .storage_api()
+            .create_document(&file_path, collection_name, create_document_input_data());
+        assert!(result.success);
+    }
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, collection_name, None);
+    let documents = result.data.unwrap();
+    assert!(documents.len() < 5);
+    assert_eq!(documents.last().unwrap().id(), &Id::Integer(5));
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn capped_collection_replace_evicts_other_documents() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "events";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let options = CollectionOptions {
+        capped: Some(CappedLimits::new(0, 100)),
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .create_collection_with_options(collection_name, &file_path, &options);
+    assert!(result.success);
+
+    for _ in 0..3 {
+        let data = vec![DocumentInputDataField::new("message", "Text", "x")];
+        let result = engine
+            .storage_api()
+            .create_document(&file_path, collection_name, data);
+        assert!(result.success);
+    }
+
+    // Replacement grows the collection past its max size
+    let data = vec![DocumentInputDataField::new("message", "Text", &"x".repeat(60))];
+    let result = engine
+        .storage_api()
+        .replace_document(&file_path, &Id::Integer(2), collection_name, data);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, collection_name, None);
+    let ids: Vec<Id> = result.data
+        .unwrap()
+        .iter()
+        .map(|document| document.id().clone())
+        .collect();
+    assert_eq!(ids, vec![Id::Integer(2), Id::Integer(3)]);
+
+    let data = vec![DocumentInputDataField::new("message", "Text", &"x".repeat(200))];
+    let result = engine
+        .storage_api()
+        .replace_document(&file_path, &Id::Integer(2), collection_name, data);
+    assert!(!result.success);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn capped_collection_with_history_keeps_evicted_documents_as_tombstones() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "events";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let options = CollectionOptions {
+        capped: Some(CappedLimits::new(2, 0)),
+        history: true,
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .create_collection_with_options(collection_name, &file_path, &options);
+    assert!(result.success);
+
+    for _ in 0..3 {
+        let result = engine
+            .storage_api()
+            .create_document(&file_path, collection_name, create_document_input_data());
+        assert!(result.success);
+    }
+
+    let result = engine
+        .storage_api()
+        .find_document_revisions(&file_path, collection_name, &Id::Integer(1));
+    let revisions = result.data.unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert!(revisions[0].deleted);
+    assert_eq!(revisions[0].document.id(), &Id::Integer(1));
+
+    let result = engine
+        .storage_api()
+        .find_document_revisions(&file_path, collection_name, &Id::Integer(2));
+    assert!(result.data.unwrap().is_empty());
+
+    config_settings.close_temp_dirs();
+}
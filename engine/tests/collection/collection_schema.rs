@@ -0,0 +1,265 @@
+use engine::{
+    Engine,
+    DocumentInputDataField,
+    storage::{
+        DB_FILE_EXTENSION,
+        pb::{
+            CollectionSchema,
+            FieldSchema,
+            document::{
+                DataType,
+                data_type,
+            },
+        },
+        error::SchemaViolationKind,
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+
+// Creates schema that accepts the documents created in tests
+fn create_test_schema() -> CollectionSchema {
+    CollectionSchema {
+        fields: vec![
+            FieldSchema {
+                name: "first_name".to_string(),
+                required: true,
+                data_types: vec!["Text".to_string()],
+                max_length: 20,
+                ..Default::default()
+            },
+            FieldSchema {
+                name: "last_name".to_string(),
+                required: true,
+                data_types: vec!["Text".to_string()],
+                ..Default::default()
+            },
+            FieldSchema {
+                name: "age".to_string(),
+                data_types: vec!["Int32".to_string()],
+                min: Some(DataType { data_type: Some(data_type::DataType::Int32(0)) }),
+                max: Some(DataType { data_type: Some(data_type::DataType::Int32(150)) }),
+                ..Default::default()
+            },
+        ],
+        forbid_unknown_fields: true,
+    }
+}
+
+#[test]
+fn set_collection_schema_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_collection_schema(collection_name, &file_path);
+    assert!(result.success);
+    assert!(result.data.unwrap().is_none());
+
+    let result = engine
+        .storage_api()
+        .set_collection_schema(collection_name, &file_path, Some(create_test_schema()));
+    assert!(result.success);
+    assert!(result.error.is_none());
+
+    let result = engine
+        .storage_api()
+        .find_collection_schema(collection_name, &file_path);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap(), Some(create_test_schema()));
+
+    let result = engine
+        .storage_api()
+        .set_collection_schema(collection_name, &file_path, None);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_collection_schema(collection_name, &file_path);
+    assert!(result.data.unwrap().is_none());
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn set_invalid_collection_schema_fails() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let mut schema = create_test_schema();
+    schema.fields[2].data_types.push("Integer".to_string());
+    let result = engine
+        .storage_api()
+        .set_collection_schema(collection_name, &file_path, Some(schema));
+    assert!(!result.success);
+    assert!(result.error.is_some());
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn create_document_schema_violations_fail() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .set_collection_schema(collection_name, &file_path, Some(create_test_schema()));
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    assert!(result.success);
+    let document = result.data.unwrap();
+
+    let data = vec![
+        DocumentInputDataField::new("first_name", "Int32", "1"),
+        DocumentInputDataField::new("age", "Int32", "200"),
+        DocumentInputDataField::new("email", "Text", "john@example.com"),
+    ];
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, data);
+    assert!(!result.success);
+
+    let validation_error = result.error.unwrap().validation_error.unwrap();
+    let violations: Vec<(&str, &SchemaViolationKind)> = validation_error.violations
+        .iter()
+        .map(|violation| (violation.field.as_str(), &violation.kind))
+        .collect();
+    assert_eq!(violations, vec![
+        ("first_name", &SchemaViolationKind::DataType(vec!["Text".to_string()], "Int32".to_string())),
+        ("last_name", &SchemaViolationKind::MissingField),
+        ("age", &SchemaViolationKind::GreaterThanMax),
+        ("email", &SchemaViolationKind::UnknownField),
+    ]);
+
+    // Replaced documents are validated too
+    let data = vec![DocumentInputDataField::new("first_name", "Text", "John")];
+    let result = engine
+        .storage_api()
+        .replace_document(&file_path, document.id(), collection_name, data);
+    assert!(!result.success);
+    assert_eq!(result.error.unwrap().validation_error.unwrap().violations.len(), 1);
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, collection_name, None);
+    assert_eq!(result.data.unwrap().len(), 1);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn infer_collection_schema_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    assert!(result.success);
+
+    let data = vec![
+        DocumentInputDataField::new("first_name", "Text", "Jane"),
+        DocumentInputDataField::new("age", "Int64", "35"),
+    ];
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, data);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .infer_collection_schema(collection_name, &file_path);
+    assert!(result.success);
+    let report = result.data.unwrap();
+    assert_eq!(report.document_count, 2);
+
+    let first_name = report.fields.iter().find(|field| field.path == "first_name").unwrap();
+    assert_eq!(first_name.present_count, 2);
+    assert_eq!(first_name.null_count, 0);
+    assert_eq!(first_name.sample_values.len(), 2);
+
+    let age = report.fields.iter().find(|field| field.path == "age").unwrap();
+    assert_eq!(age.data_types.get("Int32"), Some(&1));
+    assert_eq!(age.data_types.get("Int64"), Some(&1));
+
+    let last_name = report.fields.iter().find(|field| field.path == "last_name").unwrap();
+    assert_eq!(last_name.present_count, 1);
+
+    let schema = report.suggested_schema();
+    assert!(schema.field("first_name").unwrap().required);
+    assert!(!schema.field("last_name").unwrap().required);
+
+    let result = engine
+        .storage_api()
+        .infer_collection_schema("missing", &file_path);
+    assert!(!result.success);
+
+    config_settings.close_temp_dirs();
+}
@@ -0,0 +1,174 @@
+use engine::{
+    Engine,
+    storage::{
+        DB_FILE_EXTENSION,
+        csv::{
+            CsvColumn,
+            CsvExportOptions,
+            CsvImportOptions,
+        },
+        pb::document::data_type,
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+use std::fs;
+
+#[test]
+fn export_collection_to_csv_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+    let csv_file_path = config_settings.db_dir.path().join("people.csv");
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_collection("people", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, "people", create_document_input_data());
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .export_collection_to_csv(&file_path, "people", &csv_file_path, &CsvExportOptions::default());
+    assert!(result.success);
+    assert_eq!(result.data.unwrap(), 1);
+    assert_eq!(
+        fs::read_to_string(&csv_file_path).unwrap(),
+        "_id,first_name,last_name,age\n1,John,Smith,42\n"
+    );
+
+    let options = CsvExportOptions {
+        fields: vec![String::from("age"), String::from("email")],
+        header: false,
+    };
+    let result = engine
+        .storage_api()
+        .export_collection_to_csv(&file_path, "people", &csv_file_path, &options);
+    assert!(result.success);
+    assert_eq!(fs::read_to_string(&csv_file_path).unwrap(), "42,\n");
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn import_csv_to_collection_reports_row_errors() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+    let csv_file_path = config_settings.db_dir.path().join("people.csv");
+    fs::write(&csv_file_path, "name,age\nJohn,42\nJane,old\nJack,30\n").unwrap();
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_collection("people", &file_path);
+    assert!(result.success);
+
+    let mut options = CsvImportOptions {
+        columns: vec![CsvColumn::new("age", "Int32")],
+        dry_run: true,
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .import_csv_to_collection(&file_path, "people", &csv_file_path, &options);
+    assert!(result.success);
+    let report = result.data.unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.imported_count, 2);
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].row, 3);
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, "people", None);
+    assert!(result.data.unwrap().is_empty());
+
+    options.dry_run = false;
+    let result = engine
+        .storage_api()
+        .import_csv_to_collection(&file_path, "people", &csv_file_path, &options);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().imported_count, 2);
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, "people", None);
+    let documents = result.data.unwrap();
+    assert_eq!(documents.len(), 2);
+    assert_eq!(documents[1].data()["age"].data_type, Some(data_type::DataType::Int32(30)));
+    assert_eq!(
+        documents[1].data()["name"].data_type,
+        Some(data_type::DataType::Text(String::from("Jack")))
+    );
+
+    options.columns = vec![CsvColumn::new("age", "Int16")];
+    let result = engine
+        .storage_api()
+        .import_csv_to_collection(&file_path, "people", &csv_file_path, &options);
+    assert!(!result.success);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn import_csv_without_header_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+    let csv_file_path = config_settings.db_dir.path().join("people.csv");
+    fs::write(&csv_file_path, "John,42\nJane,35\n").unwrap();
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_collection("people", &file_path);
+    assert!(result.success);
+
+    let mut options = CsvImportOptions {
+        header: false,
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .import_csv_to_collection(&file_path, "people", &csv_file_path, &options);
+    assert!(!result.success);
+
+    options.columns = vec![CsvColumn::new("name", "Text"), CsvColumn::new("age", "Int64")];
+    let result = engine
+        .storage_api()
+        .import_csv_to_collection(&file_path, "people", &csv_file_path, &options);
+    assert!(result.success);
+    let report = result.data.unwrap();
+    assert_eq!(report.imported_count, 2);
+    assert!(report.errors.is_empty());
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, "people", None);
+    let documents = result.data.unwrap();
+    assert_eq!(documents[0].data()["age"].data_type, Some(data_type::DataType::Int64(42)));
+
+    config_settings.close_temp_dirs();
+}
@@ -0,0 +1,190 @@
+use engine::{
+    Engine,
+    DocumentInputDataField,
+    storage::{
+        DB_FILE_EXTENSION,
+        json::{
+            JsonFormat,
+            JsonMode,
+            JsonOptions,
+        },
+        pb::document::data_type,
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+use std::fs;
+
+#[test]
+fn export_and_import_collection_json_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+    let json_file_path = config_settings.db_dir.path().join("people.json");
+    let options = JsonOptions::default();
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    for collection_name in ["people", "people_copy"] {
+        let result = engine
+            .storage_api()
+            .create_collection(collection_name, &file_path);
+        assert!(result.success);
+    }
+
+    let mut data = create_document_input_data();
+    data.push(DocumentInputDataField::new("balance", "Int64", "9007199254740993"));
+    data.push(DocumentInputDataField::new("price", "Decimal128", "10.50"));
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, "people", data);
+    assert!(result.success);
+    let document = result.data.unwrap();
+
+    let result = engine
+        .storage_api()
+        .export_collection_to_json(&file_path, "people", &json_file_path, &options);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap(), 1);
+
+    let result = engine
+        .storage_api()
+        .import_json_to_collection(&file_path, "people_copy", &json_file_path, &options);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap(), 1);
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, "people_copy", None);
+    let documents = result.data.unwrap();
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents[0].data(), document.data());
+
+    let result = engine
+        .storage_api()
+        .import_json_to_collection(
+            &file_path,
+            "people_copy",
+            &json_file_path,
+            &JsonOptions::new(JsonFormat::Json, JsonMode::Relaxed)
+        );
+    assert!(!result.success);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn export_and_import_database_json_lines_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+    let new_file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test_copy.{}", DB_FILE_EXTENSION));
+    let json_file_path = config_settings.db_dir.path().join("test.jsonl");
+    let options = JsonOptions::new(JsonFormat::from_file_path(&json_file_path), JsonMode::Typed);
+    assert_eq!(options.format, JsonFormat::JsonLines);
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test_copy", &new_file_path);
+    assert!(result.success);
+    for collection_name in ["people", "books"] {
+        let result = engine
+            .storage_api()
+            .create_collection(collection_name, &file_path);
+        assert!(result.success);
+        let result = engine
+            .storage_api()
+            .create_document(&file_path, collection_name, create_document_input_data());
+        assert!(result.success);
+    }
+
+    let result = engine
+        .storage_api()
+        .export_database_to_json(&file_path, &json_file_path, &options);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap(), 2);
+    assert_eq!(fs::read_to_string(&json_file_path).unwrap().lines().count(), 2);
+
+    let result = engine
+        .storage_api()
+        .import_json_to_database(&new_file_path, &json_file_path, &options);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap(), 2);
+
+    let result = engine
+        .storage_api()
+        .find_all_collections(&new_file_path);
+    let collections = result.data.unwrap();
+    assert_eq!(collections.len(), 2);
+    assert_eq!(collections[0].name(), "people");
+    assert_eq!(collections[1].name(), "books");
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn import_invalid_json_imports_nothing() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+    let json_file_path = config_settings.db_dir.path().join("people.json");
+    let options = JsonOptions::new(JsonFormat::Json, JsonMode::Relaxed);
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_collection("people", &file_path);
+    assert!(result.success);
+
+    fs::write(&json_file_path, r#"[{"name": "John", "age": 42}, {"name": ["John"]}]"#).unwrap();
+    let result = engine
+        .storage_api()
+        .import_json_to_collection(&file_path, "people", &json_file_path, &options);
+    assert!(!result.success);
+    assert!(result.error.unwrap().message.contains("document 2"));
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, "people", None);
+    assert!(result.data.unwrap().is_empty());
+
+    fs::write(&json_file_path, r#"[{"name": "John", "age": 42, "score": 1.5}]"#).unwrap();
+    let result = engine
+        .storage_api()
+        .import_json_to_collection(&file_path, "people", &json_file_path, &options);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, "people", None);
+    let documents = result.data.unwrap();
+    assert_eq!(
+        documents[0].data()["age"].data_type,
+        Some(data_type::DataType::Int32(42))
+    );
+    assert_eq!(
+        documents[0].data()["score"].data_type,
+        Some(data_type::DataType::Decimal(1.5))
+    );
+
+    config_settings.close_temp_dirs();
+}
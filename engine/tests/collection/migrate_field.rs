@@ -0,0 +1,158 @@
+use engine::{
+    Engine,
+    DocumentInputDataField,
+    storage::{
+        DB_FILE_EXTENSION,
+        migration::FieldMigration,
+        pb::document::{
+            DataType,
+            data_type,
+        },
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+
+#[test]
+fn migrate_field_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let mut data = create_document_input_data();
+    data.push(DocumentInputDataField::new("score", "Text", "15"));
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, data);
+    let converted_document = result.data.unwrap();
+
+    let mut data = create_document_input_data();
+    data.push(DocumentInputDataField::new("score", "Text", "high"));
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, data);
+    let failed_document = result.data.unwrap();
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .migrate_field(&file_path, collection_name, &FieldMigration::Convert {
+            field: "score".to_string(),
+            data_type: "Int32".to_string(),
+        });
+    assert!(result.success);
+    let migration_result = result.data.unwrap();
+    assert_eq!(migration_result.converted_count, 1);
+    assert_eq!(migration_result.failed_count, 1);
+    assert_eq!(&migration_result.failed_document_ids, &vec![failed_document.id().clone()]);
+
+    let result = engine
+        .storage_api()
+        .find_document_by_id(converted_document.id(), &file_path, collection_name);
+    let document = result.data.unwrap().unwrap();
+    assert_eq!(
+        document.data().get("score"),
+        Some(&DataType { data_type: Some(data_type::DataType::Int32(15)) })
+    );
+
+    let result = engine
+        .storage_api()
+        .find_document_by_id(failed_document.id(), &file_path, collection_name);
+    let document = result.data.unwrap().unwrap();
+    assert_eq!(
+        document.data().get("score"),
+        Some(&DataType { data_type: Some(data_type::DataType::Text("high".to_string())) })
+    );
+
+    let result = engine
+        .storage_api()
+        .migrate_field(&file_path, collection_name, &FieldMigration::Rename {
+            field: "age".to_string(),
+            new_field: "years".to_string(),
+        });
+    assert_eq!(result.data.unwrap().converted_count, 3);
+
+    let result = engine
+        .storage_api()
+        .migrate_field(&file_path, collection_name, &FieldMigration::Drop {
+            field: "score".to_string(),
+        });
+    assert_eq!(result.data.unwrap().converted_count, 2);
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, collection_name, None);
+    for document in result.data.unwrap() {
+        let keys: Vec<&String> = document.data().keys().collect();
+        assert_eq!(keys, vec!["first_name", "last_name", "years"]);
+    }
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn migrate_field_invalid_migration_fails() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .migrate_field(&file_path, collection_name, &FieldMigration::Convert {
+            field: "age".to_string(),
+            data_type: "Integer".to_string(),
+        });
+    assert!(!result.success);
+    assert!(result.error.is_some());
+
+    let result = engine
+        .storage_api()
+        .migrate_field(&file_path, collection_name, &FieldMigration::Rename {
+            field: "age".to_string(),
+            new_field: "my age".to_string(),
+        });
+    assert!(!result.success);
+
+    let result = engine
+        .storage_api()
+        .migrate_field(&file_path, "missing", &FieldMigration::Drop {
+            field: "age".to_string(),
+        });
+    assert!(!result.success);
+
+    config_settings.close_temp_dirs();
+}
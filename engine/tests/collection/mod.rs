@@ -2,4 +2,11 @@
 
 mod create_collection;
 mod delete_collection;
-mod find_collection;
\ No newline at end of file
+mod find_collection;
+mod collection_schema;
+mod modify_collection;
+mod migrate_field;
+mod json;
+mod csv;
+mod ttl;
+mod capped;
\ No newline at end of file
@@ -0,0 +1,164 @@
+use engine::{
+    Engine,
+    storage::DB_FILE_EXTENSION,
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+
+#[test]
+fn rename_collection_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let new_collection_name = "persons";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .rename_collection(collection_name, new_collection_name, &file_path);
+    assert!(result.success);
+    assert!(result.error.is_none());
+
+    let result = engine
+        .storage_api()
+        .find_collection(collection_name, &file_path);
+    assert!(result.data.unwrap().is_none());
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, new_collection_name, None);
+    assert_eq!(result.data.unwrap().len(), 1);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn rename_collection_to_existing_name_fails() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    for collection_name in ["people", "persons"] {
+        let result = engine
+            .storage_api()
+            .create_collection(collection_name, &file_path);
+        assert!(result.success);
+    }
+
+    let result = engine
+        .storage_api()
+        .rename_collection("people", "persons", &file_path);
+    assert!(!result.success);
+    assert!(result.error.is_some());
+
+    // Renaming to the same name does nothing
+    let result = engine
+        .storage_api()
+        .rename_collection("people", "people", &file_path);
+    assert!(result.success);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn copy_collection_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+    let target_file_path = config_settings.db_dir
+        .path()
+        .join(&format!("target.{}", DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("target", &target_file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    let document = result.data.unwrap();
+
+    // Copy within the same database
+    let result = engine
+        .storage_api()
+        .copy_collection(collection_name, &file_path, "people_copy", &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_document_by_id(document.id(), &file_path, "people_copy");
+    let copied_document = result.data.unwrap().unwrap();
+    assert_eq!(copied_document.data(), document.data());
+
+    // Copy to another database
+    let result = engine
+        .storage_api()
+        .copy_collection(collection_name, &file_path, collection_name, &target_file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_document_by_id(document.id(), &target_file_path, collection_name);
+    let copied_document = result.data.unwrap().unwrap();
+    assert_eq!(copied_document.data(), document.data());
+
+    // Copies continue the original document ids
+    let result = engine
+        .storage_api()
+        .create_document(&target_file_path, collection_name, create_document_input_data());
+    assert_eq!(result.data.unwrap().id().to_string(), "2");
+
+    let result = engine
+        .storage_api()
+        .copy_collection(collection_name, &file_path, collection_name, &target_file_path);
+    assert!(!result.success);
+
+    let result = engine
+        .storage_api()
+        .find_all_collections(&file_path);
+    assert_eq!(result.data.unwrap().len(), 2);
+
+    config_settings.close_temp_dirs();
+}
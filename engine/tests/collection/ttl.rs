@@ -0,0 +1,109 @@
+use engine::{
+    Engine,
+    DocumentInputDataField,
+    chrono::Utc,
+    storage::{
+        DB_FILE_EXTENSION,
+        pb::TtlPolicy,
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+
+#[test]
+fn collection_ttl_expires_documents() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "sessions";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+    let now = Utc::now().timestamp();
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let mut data = create_document_input_data();
+    data.push(DocumentInputDataField::new("created_at", "Int64", &(now - 7200).to_string()));
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, data);
+    let expired_document = result.data.unwrap();
+
+    let mut data = create_document_input_data();
+    data.push(DocumentInputDataField::new("created_at", "Int64", &now.to_string()));
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, data);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .set_collection_ttl(collection_name, &file_path, Some(TtlPolicy::new("created_at", 3600)));
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_collection(collection_name, &file_path);
+    let collection = result.data.unwrap().unwrap();
+    assert_eq!(collection.ttl(), Some(&TtlPolicy::new("created_at", 3600)));
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, collection_name, None);
+    assert_eq!(result.data.unwrap().len(), 2);
+
+    let result = engine
+        .storage_api()
+        .find_document_by_id(expired_document.id(), &file_path, collection_name);
+    assert!(result.data.unwrap().is_none());
+
+    let result = engine
+        .storage_api()
+        .purge_expired_documents(&file_path);
+    assert_eq!(result.data, Some(1));
+
+    let result = engine
+        .storage_api()
+        .purge_expired_documents(&file_path);
+    assert_eq!(result.data, Some(0));
+
+    // Creating a document removes expired documents in the same write
+    let mut data = create_document_input_data();
+    data.push(DocumentInputDataField::new("created_at", "Int64", &(now - 7200).to_string()));
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, data);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .purge_expired_documents(&file_path);
+    assert_eq!(result.data, Some(0));
+
+    let result = engine
+        .storage_api()
+        .set_collection_ttl(collection_name, &file_path, Some(TtlPolicy::new("", 60)));
+    assert!(!result.success);
+
+    let result = engine
+        .storage_api()
+        .set_collection_ttl(collection_name, &file_path, None);
+    assert!(result.success);
+
+    config_settings.close_temp_dirs();
+}
@@ -0,0 +1,29 @@
+use engine::{
+    Engine,
+    config::load_config,
+    storage::pb::Compression,
+};
+use crate::common::ConfigSettings;
+use std::fs;
+
+#[test]
+fn set_db_compression_and_load_config() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let config_file = fs::File::create(config_settings.config.config_file_path()).unwrap();
+
+    let result = engine
+        .config_api()
+        .set_db_compression(Compression::Zstd);
+    assert!(result.success);
+    assert!(result.error.is_none());
+    assert!(result.log_error.is_none());
+
+    let config = load_config(config_settings.config.config_file_path()).unwrap();
+    assert_eq!(config.db_compression(), "Zstd");
+    assert_eq!(config.db_options().compression, Compression::Zstd);
+    assert_eq!(config.db_dir_path(), config_settings.db_dir.path());
+    
+    drop(config_file);
+    config_settings.close_temp_dirs();
+}
@@ -1,3 +1,4 @@
 mod load_config;
 mod db_dir;
-mod logs_dir;
\ No newline at end of file
+mod logs_dir;
+mod db_compression;
\ No newline at end of file
@@ -0,0 +1,160 @@
+use engine::{
+    Engine,
+    storage::{
+        DB_FILE_EXTENSION,
+        backup::BACKUP_MANIFEST_FILE_NAME,
+    },
+};
+use crate::common::ConfigSettings;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn backup_and_restore_database() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let backup_dir = tempdir().unwrap();
+    let backup_dir_path = backup_dir.path().join("backup");
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_collection("people", &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .backup_database(&file_path, &backup_dir_path);
+    assert!(result.success);
+    let manifest = result.data.unwrap();
+    assert_eq!(manifest.databases.len(), 1);
+    assert_eq!(manifest.databases[0].name, "test");
+    assert_eq!(manifest.databases[0].checksum.len(), 64);
+    assert!(backup_dir_path.join(BACKUP_MANIFEST_FILE_NAME).is_file());
+
+    let result = engine
+        .storage_api()
+        .verify_backup(&backup_dir_path);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap(), manifest);
+
+    let result = engine
+        .storage_api()
+        .create_collection("books", &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .restore_database(&backup_dir_path, "test");
+    assert!(result.success);
+    assert_eq!(result.data.unwrap(), file_path);
+
+    let result = engine
+        .storage_api()
+        .find_all_collections(&file_path);
+    let collections = result.data.unwrap();
+    assert_eq!(collections.len(), 1);
+    assert_eq!(collections[0].name(), "people");
+
+    let result = engine
+        .storage_api()
+        .backup_database(&file_path, &backup_dir_path);
+    assert!(!result.success);
+
+    backup_dir.close().unwrap();
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn backup_and_restore_all_databases() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let backup_dir = tempdir().unwrap();
+    let db_names = ["test1", "test2"];
+
+    for db_name in db_names {
+        let result = engine
+            .storage_api()
+            .create_database_to_db_dir(db_name);
+        assert!(result.success);
+    }
+
+    let result = engine
+        .storage_api()
+        .backup_all_databases(backup_dir.path());
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().databases.len(), db_names.len());
+
+    for db_name in db_names {
+        let result = engine
+            .storage_api()
+            .delete_database(&config_settings.db_dir.path().join(&format!("{}.{}", db_name, DB_FILE_EXTENSION)));
+        assert!(result.success);
+    }
+
+    let result = engine
+        .storage_api()
+        .restore_all_databases(backup_dir.path());
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().len(), db_names.len());
+
+    let result = engine
+        .storage_api()
+        .find_all_databases();
+    assert_eq!(result.data.unwrap().databases.len(), db_names.len());
+
+    backup_dir.close().unwrap();
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn restore_corrupted_backup_fails() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let backup_dir = tempdir().unwrap();
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .backup_database(&file_path, backup_dir.path());
+    assert!(result.success);
+
+    let backup_file_path = backup_dir.path().join(&format!("test.{}", DB_FILE_EXTENSION));
+    let mut buf = fs::read(&backup_file_path).unwrap();
+    let last = buf.len() - 1;
+    buf[last] ^= 1;
+    fs::write(&backup_file_path, buf).unwrap();
+    let original_buf = fs::read(&file_path).unwrap();
+
+    let result = engine
+        .storage_api()
+        .verify_backup(backup_dir.path());
+    assert!(!result.success);
+    assert!(result.error.unwrap().message.contains("checksum"));
+
+    let result = engine
+        .storage_api()
+        .restore_database(backup_dir.path(), "test");
+    assert!(!result.success);
+    assert_eq!(fs::read(&file_path).unwrap(), original_buf);
+
+    let result = engine
+        .storage_api()
+        .restore_database(backup_dir.path(), "missing");
+    assert!(!result.success);
+
+    backup_dir.close().unwrap();
+    config_settings.close_temp_dirs();
+}
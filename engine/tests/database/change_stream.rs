@@ -0,0 +1,151 @@
+use engine::{
+    Engine,
+    storage::{
+        DB_FILE_EXTENSION,
+        pb::document::Id,
+        migration::FieldMigration,
+        change_stream::{
+            ChangeOperation,
+            ChangeStreamOptions,
+        },
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+
+#[test]
+fn watch_database_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    for collection_name in ["people", "items"] {
+        let result = engine
+            .storage_api()
+            .create_collection(collection_name, &file_path);
+        assert!(result.success);
+    }
+
+    let options = ChangeStreamOptions {
+        include_documents: true,
+        ..Default::default()
+    };
+    let change_stream = engine
+        .storage_api()
+        .watch_database(&file_path, &options)
+        .data
+        .unwrap();
+    let options = ChangeStreamOptions {
+        collection: Some("items".to_string()),
+        ..Default::default()
+    };
+    let items_change_stream = engine
+        .storage_api()
+        .watch_database(&file_path, &options)
+        .data
+        .unwrap();
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, "people", create_document_input_data());
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .replace_document(&file_path, &Id::Integer(1), "people", create_document_input_data().into_iter().take(1).collect());
+    assert!(result.success);
+    let migration = FieldMigration::Rename {
+        field: "first_name".to_string(),
+        new_field: "name".to_string(),
+    };
+    let result = engine
+        .storage_api()
+        .migrate_field(&file_path, "people", &migration);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .delete_document(&file_path, &Id::Integer(1), "people");
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, "items", create_document_input_data());
+    assert!(result.success);
+
+    let events: Vec<_> = change_stream.try_iter().collect();
+    let operations: Vec<_> = events.iter().map(|event| event.operation).collect();
+    assert_eq!(operations, vec![
+        ChangeOperation::Insert,
+        ChangeOperation::Replace,
+        ChangeOperation::Update,
+        ChangeOperation::Delete,
+        ChangeOperation::Insert,
+    ]);
+    let sequences: Vec<_> = events.iter().map(|event| event.sequence).collect();
+    assert_eq!(sequences, vec![1, 2, 3, 4, 5]);
+    assert_eq!(events[0].document_id, Id::Integer(1));
+    assert!(events[0].before.is_none());
+    assert_eq!(events[0].after.as_ref().unwrap().data().len(), 3);
+    assert_eq!(events[1].before.as_ref().unwrap().data().len(), 3);
+    assert_eq!(events[1].after.as_ref().unwrap().data().len(), 1);
+    assert!(events[3].after.is_none());
+
+    let items_events: Vec<_> = items_change_stream.try_iter().collect();
+    assert_eq!(items_events.len(), 1);
+    assert_eq!(items_events[0].collection, "items");
+    assert_eq!(items_events[0].sequence, 5);
+    assert!(items_events[0].after.is_none());
+
+    // Resume after the last received event
+    drop(change_stream);
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, "people", create_document_input_data());
+    assert!(result.success);
+    let options = ChangeStreamOptions {
+        resume_after: Some(5),
+        ..Default::default()
+    };
+    let change_stream = engine
+        .storage_api()
+        .watch_database(&file_path, &options)
+        .data
+        .unwrap();
+    let events: Vec<_> = change_stream.try_iter().collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].sequence, 6);
+    assert_eq!(events[0].document_id, Id::Integer(2));
+
+    let options = ChangeStreamOptions {
+        resume_after: Some(100),
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .watch_database(&file_path, &options);
+    assert!(!result.success);
+
+    // Events are not kept after all change streams are dropped
+    drop(change_stream);
+    drop(items_change_stream);
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, "people", create_document_input_data());
+    assert!(result.success);
+    let options = ChangeStreamOptions {
+        resume_after: Some(6),
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .watch_database(&file_path, &options);
+    assert!(!result.success);
+
+    config_settings.close_temp_dirs();
+}
@@ -0,0 +1,118 @@
+use engine::{
+    Engine,
+    storage::{
+        DB_FILE_EXTENSION,
+        file_header::FileHeader,
+        database::DatabaseOptions,
+        pb::Compression,
+        pb::document::Id,
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+use std::fs;
+
+#[test]
+fn create_compressed_database_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let collection_name = "people";
+
+    for compression in [Compression::Zstd, Compression::Lz4] {
+        let db_name = compression.to_string();
+        let file_path = config_settings.db_dir
+            .path()
+            .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+        let options = DatabaseOptions {
+            compression,
+            ..Default::default()
+        };
+
+        let result = engine
+            .storage_api()
+            .create_database_by_file_path_with_options(&db_name, &file_path, &options);
+        assert!(result.success);
+        assert!(result.error.is_none());
+
+        let result = engine
+            .storage_api()
+            .create_collection(collection_name, &file_path);
+        assert!(result.success);
+
+        let data = create_document_input_data();
+        let data_field_count = data.len();
+        let result = engine
+            .storage_api()
+            .create_document(&file_path, collection_name, data);
+        assert!(result.success);
+
+        let buf = fs::read(&file_path).unwrap();
+        let (header, _) = FileHeader::read(&buf).unwrap().unwrap();
+        assert_eq!(header.compression, compression);
+
+        let result = engine
+            .storage_api()
+            .find_document_by_id(&Id::Integer(1), &file_path, collection_name);
+        assert!(result.success);
+        let document = result.data.unwrap().unwrap();
+        assert_eq!(document.data().len(), data_field_count);
+
+        let result = engine
+            .storage_api()
+            .find_database_by_file_path(&file_path);
+        assert!(result.success);
+        assert_eq!(result.data.unwrap().unwrap().compression(), compression);
+    }
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn create_database_uses_configured_compression() {
+    let mut config_settings = ConfigSettings::new();
+    config_settings.config.db_compression = Compression::Zstd.to_string();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+
+    let result = engine
+        .storage_api()
+        .create_database_to_db_dir(db_name);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_database(db_name);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().unwrap().compression(), Compression::Zstd);
+
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+    let buf = fs::read(&file_path).unwrap();
+    let (header, _) = FileHeader::read(&buf).unwrap().unwrap();
+    assert_eq!(header.compression, Compression::Zstd);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn create_uncompressed_database_by_default() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+
+    let result = engine
+        .storage_api()
+        .create_database_to_db_dir(db_name);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_database(db_name);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().unwrap().compression(), Compression::None);
+
+    config_settings.close_temp_dirs();
+}
@@ -0,0 +1,113 @@
+use engine::{
+    Engine,
+    storage::{
+        DB_FILE_EXTENSION,
+        pb::document::Id,
+        pb::IdStrategy,
+        collection::CollectionOptions,
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+
+#[test]
+fn dump_and_restore_all_databases_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let dump_dir = tempfile::tempdir().unwrap();
+    let dump_file_path = dump_dir.path().join("dump.json");
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_collection("people", &file_path);
+    assert!(result.success);
+    for _ in 0..3 {
+        let result = engine
+            .storage_api()
+            .create_document(&file_path, "people", create_document_input_data());
+        assert!(result.success);
+    }
+    // Deleted ids are not used again after restore
+    let result = engine
+        .storage_api()
+        .delete_document(&file_path, &Id::Integer(3), "people");
+    assert!(result.success);
+
+    let options = CollectionOptions {
+        id_strategy: IdStrategy::ClientInteger,
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .create_collection_with_options("items", &file_path, &options);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_document_with_id(&file_path, "items", create_document_input_data(), Some(Id::Integer(1000)));
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .dump_all_databases(&dump_file_path);
+    assert!(result.success);
+    let report = result.data.unwrap();
+    assert_eq!(report.databases, vec!["test".to_string()]);
+    assert_eq!(report.collection_count, 2);
+    assert_eq!(report.document_count, 3);
+
+    // Dump file is not overwritten
+    let result = engine
+        .storage_api()
+        .dump_all_databases(&dump_file_path);
+    assert!(!result.success);
+
+    let restore_config_settings = ConfigSettings::new();
+    let restore_engine = Engine::build(&restore_config_settings.config);
+    let restored_file_path = restore_config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+
+    let result = restore_engine
+        .storage_api()
+        .restore_dump(&dump_file_path);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().document_count, 3);
+
+    let result = restore_engine
+        .storage_api()
+        .find_all_documents(&restored_file_path, "people", None);
+    let documents = result.data.unwrap();
+    assert_eq!(documents.len(), 2);
+    assert_eq!(documents[0].id(), &Id::Integer(1));
+    assert_eq!(documents[1].id(), &Id::Integer(2));
+    assert_eq!(documents[1].data().len(), create_document_input_data().len());
+
+    let result = restore_engine
+        .storage_api()
+        .create_document(&restored_file_path, "people", create_document_input_data());
+    assert_eq!(result.data.unwrap().id(), &Id::Integer(4));
+
+    let result = restore_engine
+        .storage_api()
+        .find_all_documents(&restored_file_path, "items", None);
+    assert_eq!(result.data.unwrap()[0].id(), &Id::Integer(1000));
+
+    // Databases in the dump already exist
+    let result = restore_engine
+        .storage_api()
+        .restore_dump(&dump_file_path);
+    assert!(!result.success);
+
+    dump_dir.close().unwrap();
+    config_settings.close_temp_dirs();
+    restore_config_settings.close_temp_dirs();
+}
@@ -0,0 +1,268 @@
+use engine::{
+    Engine,
+    storage::{
+        DB_FILE_EXTENSION,
+        database::DatabaseOptions,
+        encryption::{
+            is_encrypted,
+            Cipher,
+            EncryptionKey,
+            EncryptionOptions,
+        },
+        pb::Compression,
+        pb::document::Id,
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+use std::fs;
+
+fn passphrase(passphrase: &str) -> EncryptionKey {
+    EncryptionKey::Passphrase(passphrase.to_string())
+}
+
+#[test]
+fn create_encrypted_database_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let collection_name = "people";
+
+    for (cipher, compression) in [
+        (Cipher::Aes256Gcm, Compression::None),
+        (Cipher::ChaCha20Poly1305, Compression::Zstd),
+    ] {
+        let db_name = cipher.to_string();
+        let file_path = config_settings.db_dir
+            .path()
+            .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+        let options = DatabaseOptions {
+            compression,
+            encryption: Some(EncryptionOptions {
+                key: passphrase("secret"),
+                cipher,
+            }),
+        };
+
+        let result = engine
+            .storage_api()
+            .create_database_by_file_path_with_options(&db_name, &file_path, &options);
+        assert!(result.success);
+        assert!(result.error.is_none());
+
+        let result = engine
+            .storage_api()
+            .create_collection(collection_name, &file_path);
+        assert!(result.success);
+
+        let result = engine
+            .storage_api()
+            .create_document(&file_path, collection_name, create_document_input_data());
+        assert!(result.success);
+
+        let buf = fs::read(&file_path).unwrap();
+        assert!(is_encrypted(&buf));
+        assert!(!buf.windows(collection_name.len()).any(|w| w == collection_name.as_bytes()));
+
+        let result = engine
+            .storage_api()
+            .find_database_by_file_path(&file_path);
+        assert!(result.success);
+        let db = result.data.unwrap().unwrap();
+        assert_eq!(db.encryption(), Some(cipher));
+        assert_eq!(db.compression(), compression);
+        assert!(!db.locked());
+    }
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn lock_and_unlock_database() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+    let options = DatabaseOptions {
+        encryption: Some(EncryptionOptions::new(passphrase("secret"))),
+        ..Default::default()
+    };
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path_with_options(db_name, &file_path, &options);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .lock_database(&file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_database(db_name);
+    assert!(result.success);
+    let db = result.data.unwrap().unwrap();
+    assert!(db.locked());
+    assert_eq!(db.name(), db_name);
+    assert_eq!(db.encryption(), Some(Cipher::Aes256Gcm));
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    assert!(!result.success);
+    assert!(result.error.is_some());
+
+    let result = engine
+        .storage_api()
+        .unlock_database(&file_path, &passphrase("wrong"));
+    assert!(!result.success);
+    assert!(result.error.is_some());
+
+    let result = engine
+        .storage_api()
+        .unlock_database(&file_path, &passphrase("secret"));
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    assert!(result.success);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn encrypt_rotate_and_decrypt_database() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+    let key_file_path = config_settings.db_dir.path().join("test.key");
+    let key_file = EncryptionKey::KeyFile(key_file_path.clone());
+
+    let result = engine
+        .storage_api()
+        .create_key_file(&key_file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .rotate_database_key(&file_path, &EncryptionOptions::new(key_file.clone()));
+    assert!(!result.success);
+
+    let result = engine
+        .storage_api()
+        .encrypt_database(&file_path, &EncryptionOptions::new(key_file.clone()));
+    assert!(result.success);
+    assert!(is_encrypted(&fs::read(&file_path).unwrap()));
+
+    let result = engine
+        .storage_api()
+        .encrypt_database(&file_path, &EncryptionOptions::new(key_file.clone()));
+    assert!(!result.success);
+
+    let result = engine
+        .storage_api()
+        .rotate_database_key(&file_path, &EncryptionOptions::new(passphrase("secret")));
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .lock_database(&file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .unlock_database(&file_path, &key_file);
+    assert!(!result.success);
+
+    let result = engine
+        .storage_api()
+        .unlock_database(&file_path, &passphrase("secret"));
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .decrypt_database(&file_path);
+    assert!(result.success);
+    assert!(!is_encrypted(&fs::read(&file_path).unwrap()));
+
+    let result = engine
+        .storage_api()
+        .find_document_by_id(&Id::Integer(1), &file_path, collection_name);
+    assert!(result.success);
+    assert!(result.data.unwrap().is_some());
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn rename_and_clone_encrypted_database() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+    let options = DatabaseOptions {
+        encryption: Some(EncryptionOptions::new(passphrase("secret"))),
+        ..Default::default()
+    };
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path_with_options("test", &file_path, &options);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .rename_database(&file_path, "renamed");
+    assert!(result.success);
+    let renamed_file_path = result.data.unwrap();
+
+    let result = engine
+        .storage_api()
+        .clone_database(&renamed_file_path, "cloned");
+    assert!(result.success);
+    let cloned_file_path = result.data.unwrap();
+    assert!(is_encrypted(&fs::read(&cloned_file_path).unwrap()));
+
+    for file_path in [renamed_file_path, cloned_file_path] {
+        let result = engine
+            .storage_api()
+            .find_database_by_file_path(&file_path);
+        let db = result.data.unwrap().unwrap();
+        assert!(!db.locked());
+
+        let result = engine
+            .storage_api()
+            .create_collection("people", &file_path);
+        assert!(result.success);
+    }
+
+    config_settings.close_temp_dirs();
+}
@@ -0,0 +1,73 @@
+use engine::{
+    Engine,
+    storage::{
+        DB_FILE_EXTENSION,
+        file_header::{
+            FileHeader,
+            FORMAT_VERSION,
+        },
+        pb::Database,
+    },
+};
+use crate::common::ConfigSettings;
+use prost::Message;
+use std::fs;
+
+#[test]
+fn upgrade_legacy_database_file() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+    fs::write(&file_path, Database::from(db_name).encode_to_vec()).unwrap();
+
+    let result = engine
+        .storage_api()
+        .find_database(db_name);
+    assert!(result.success);
+    assert!(result.data.unwrap().is_some());
+    assert!(FileHeader::read(&fs::read(&file_path).unwrap()).unwrap().is_none());
+
+    let result = engine
+        .storage_api()
+        .create_collection("people", &file_path);
+    assert!(result.success);
+
+    let buf = fs::read(&file_path).unwrap();
+    let (header, payload) = FileHeader::read(&buf).unwrap().unwrap();
+    assert_eq!(header.format_version, FORMAT_VERSION);
+    assert_eq!(header.engine_version(), engine.version());
+    assert!(header.verify(payload).is_ok());
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn find_corrupted_database_fails() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let mut buf = fs::read(&file_path).unwrap();
+    let last = buf.len() - 1;
+    buf[last] ^= 1;
+    fs::write(&file_path, buf).unwrap();
+
+    let result = engine
+        .storage_api()
+        .find_database_by_file_path(&file_path);
+    assert!(!result.success);
+    assert!(result.error.unwrap().message.contains("checksum"));
+
+    config_settings.close_temp_dirs();
+}
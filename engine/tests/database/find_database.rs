@@ -1,8 +1,14 @@
 use engine::{
     Engine,
-    storage::DB_FILE_EXTENSION,
+    storage::{
+        DB_FILE_EXTENSION,
+        database::DatabaseFileProblemKind,
+        pb::Database,
+    },
 };
 use crate::common::ConfigSettings;
+use prost::Message;
+use std::fs;
 
 #[test]
 fn find_all_databases_success() {
@@ -26,10 +32,46 @@ fn find_all_databases_success() {
     assert!(result.error.is_none());
     assert!(result.log_error.is_none());
 
-    let databases = result.data.unwrap();
-    let first_db = databases.get(0).unwrap();
-    assert_eq!(databases.len(), 1);
+    let database_list = result.data.unwrap();
+    let first_db = database_list.databases.get(0).unwrap();
+    assert_eq!(database_list.databases.len(), 1);
     assert_eq!(first_db.name(), db_name);
+    assert!(database_list.problems.is_empty());
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn find_all_databases_reports_problem_files() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_dir = config_settings.db_dir.path();
+    let file_path = db_dir.join(&format!("test.{}", DB_FILE_EXTENSION));
+    let corrupted_file_path = db_dir.join(&format!("corrupted.{}", DB_FILE_EXTENSION));
+    let invalid_file_path = db_dir.join(&format!("invalid.{}", DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    fs::write(&corrupted_file_path, [0xff, 0xff, 0xff]).unwrap();
+    fs::write(&invalid_file_path, Database::from("invalid name").encode_to_vec()).unwrap();
+
+    let result = engine
+        .storage_api()
+        .find_all_databases();
+    assert!(result.success);
+
+    let mut database_list = result.data.unwrap();
+    assert_eq!(database_list.databases.len(), 1);
+    assert_eq!(database_list.databases[0].name(), "test");
+    assert_eq!(database_list.problems.len(), 2);
+
+    database_list.problems.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    assert_eq!(database_list.problems[0].file_path, corrupted_file_path);
+    assert_eq!(database_list.problems[0].kind, DatabaseFileProblemKind::Decode);
+    assert_eq!(database_list.problems[1].file_path, invalid_file_path);
+    assert_eq!(database_list.problems[1].kind, DatabaseFileProblemKind::InvalidName);
 
     config_settings.close_temp_dirs();
 }
@@ -0,0 +1,223 @@
+use engine::{
+    Engine,
+    storage::{
+        DB_FILE_EXTENSION,
+        pb::{
+            CappedLimits,
+            Collection,
+            Database,
+            Document,
+            DocumentRevision,
+            TtlPolicy,
+            document::Id,
+        },
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+use prost::Message;
+use std::{
+    fs,
+    path::Path,
+};
+
+/// Writes a database with a corrupted document, a duplicate document id,
+/// a too low id count and a collection without a name.
+fn write_damaged_database(file_path: &Path) {
+    let document = |id: u64| Document {
+        id: Some(Id::Integer(id)),
+        data: Vec::new(),
+    };
+    let mut collection = Collection::from("people");
+    collection.id_count = 1;
+    collection.documents = vec![document(2), document(2)];
+    let mut collection_buf = collection.encode_to_vec();
+    // Document with a field longer than the document
+    collection_buf.extend_from_slice(&[0x1a, 0x02, 0x12, 0x7f]);
+
+    let mut buf = Database::from("test").encode_to_vec();
+    for collection_buf in [collection_buf, Collection::from("").encode_to_vec()] {
+        buf.push(0x1a);
+        buf.push(collection_buf.len() as u8);
+        buf.extend_from_slice(&collection_buf);
+    }
+    fs::write(file_path, buf).unwrap();
+}
+
+#[test]
+fn check_valid_database() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+    let collection_name = "people";
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .check_database(&file_path);
+    assert!(result.success);
+    let report = result.data.unwrap();
+    assert!(report.is_ok());
+    assert_eq!(report.collection_count, 1);
+    assert_eq!(report.document_count, 1);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn check_damaged_database_reports_all_problems() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+    write_damaged_database(&file_path);
+
+    let result = engine
+        .storage_api()
+        .find_database_by_file_path(&file_path);
+    assert!(!result.success);
+
+    let result = engine
+        .storage_api()
+        .check_database(&file_path);
+    assert!(result.success);
+    let report = result.data.unwrap();
+    assert_eq!(report.collection_count, 1);
+    assert_eq!(report.document_count, 1);
+    assert_eq!(report.problems.len(), 4);
+
+    let messages: Vec<String> = report.problems
+        .iter()
+        .map(|problem| problem.to_string())
+        .collect();
+    assert!(messages.iter().any(|m| m.starts_with("Collection 'people', document #3: Cannot decode document")));
+    assert!(messages.iter().any(|m| m == "Collection 'people', document 2: Duplicate document id"));
+    assert!(messages.iter().any(|m| m.starts_with("Collection 'people': Id count 1 is lower")));
+    assert!(messages.iter().any(|m| m.starts_with("Collection '#2'")));
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn repair_damaged_database() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+    let collection_name = "people";
+    write_damaged_database(&file_path);
+    let original_buf = fs::read(&file_path).unwrap();
+
+    let result = engine
+        .storage_api()
+        .repair_database(&file_path, "repaired");
+    assert!(result.success);
+    let repair = result.data.unwrap();
+    assert_eq!(repair.report.collection_count, 1);
+    assert_eq!(repair.report.document_count, 1);
+    assert_eq!(fs::read(&file_path).unwrap(), original_buf);
+
+    let result = engine
+        .storage_api()
+        .check_database(&repair.file_path);
+    assert!(result.data.unwrap().is_ok());
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&repair.file_path, collection_name, None);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().len(), 1);
+
+    let result = engine
+        .storage_api()
+        .create_document(&repair.file_path, collection_name, create_document_input_data());
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().id(), &Id::Integer(3));
+
+    let result = engine
+        .storage_api()
+        .repair_database(&file_path, "repaired");
+    assert!(!result.success);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn repair_database_keeps_collection_settings_and_revisions() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(format!("test.{}", DB_FILE_EXTENSION));
+    let collection_name = "people";
+
+    let document = |id: u64| Document {
+        id: Some(Id::Integer(id)),
+        data: Vec::new(),
+    };
+    let revision = |revision: u64, deleted: bool| DocumentRevision {
+        revision,
+        timestamp: 0,
+        document: Some(document(1)),
+        deleted,
+    };
+    let mut collection = Collection::from(collection_name);
+    collection.id_count = 1;
+    collection.documents = vec![document(1)];
+    collection.ttl = Some(TtlPolicy::new("created_at", 3600));
+    collection.capped = Some(CappedLimits::new(10, 1000));
+    collection.history = true;
+    collection.revisions = vec![revision(1, false), revision(2, true)];
+    let mut collection_buf = collection.encode_to_vec();
+    // Document with a field longer than the document
+    collection_buf.extend_from_slice(&[0x1a, 0x02, 0x12, 0x7f]);
+
+    let mut buf = Database::from("test").encode_to_vec();
+    buf.push(0x1a);
+    buf.push(collection_buf.len() as u8);
+    buf.extend_from_slice(&collection_buf);
+    fs::write(&file_path, buf).unwrap();
+
+    let result = engine
+        .storage_api()
+        .repair_database(&file_path, "repaired");
+    assert!(result.success);
+    let repair = result.data.unwrap();
+    assert_eq!(repair.report.document_count, 1);
+    assert_eq!(repair.report.problems.len(), 1);
+
+    let result = engine
+        .storage_api()
+        .find_collection(collection_name, &repair.file_path);
+    let collection = result.data.unwrap().unwrap();
+    assert_eq!(collection.ttl(), Some(&TtlPolicy::new("created_at", 3600)));
+    assert_eq!(collection.capped(), Some(&CappedLimits::new(10, 1000)));
+    assert!(collection.history());
+
+    let result = engine
+        .storage_api()
+        .find_document_revisions(&repair.file_path, collection_name, &Id::Integer(1));
+    let revisions = result.data.unwrap();
+    assert_eq!(revisions.len(), 2);
+    assert!(revisions[1].deleted);
+
+    config_settings.close_temp_dirs();
+}
@@ -0,0 +1,195 @@
+use engine::{
+    Engine,
+    storage::{
+        DB_FILE_EXTENSION,
+        encryption::{
+            EncryptionKey,
+            EncryptionOptions,
+        },
+        journal::{
+            journal_file_path,
+            JournalTarget,
+        },
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+
+#[test]
+fn restore_database_from_journal_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .enable_journal(&file_path);
+    assert!(result.success);
+    assert!(journal_file_path(&file_path).is_file());
+
+    let result = engine
+        .storage_api()
+        .enable_journal(&file_path);
+    assert!(!result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+    for _ in 0..2 {
+        let result = engine
+            .storage_api()
+            .create_document(&file_path, collection_name, create_document_input_data());
+        assert!(result.success);
+    }
+
+    let result = engine
+        .storage_api()
+        .find_journal_entries(&file_path);
+    let entries = result.data.unwrap();
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[0].sequence, 0);
+    let restore_sequence = entries[3].sequence;
+    let restore_timestamp = entries[3].timestamp;
+
+    let result = engine
+        .storage_api()
+        .delete_all_documents(&file_path, collection_name);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .restore_database_from_journal(&file_path, JournalTarget::Sequence(restore_sequence));
+    assert!(result.success);
+    assert_eq!(result.data.unwrap(), restore_sequence);
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, collection_name, None);
+    assert_eq!(result.data.unwrap().len(), 2);
+
+    let result = engine
+        .storage_api()
+        .restore_database_from_journal(&file_path, JournalTarget::Sequence(2));
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, collection_name, None);
+    assert_eq!(result.data.unwrap().len(), 1);
+
+    let result = engine
+        .storage_api()
+        .restore_database_from_journal(&file_path, JournalTarget::Timestamp(restore_timestamp));
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, collection_name, None);
+    assert_eq!(result.data.unwrap().len(), 2);
+
+    let result = engine
+        .storage_api()
+        .restore_database_from_journal(&file_path, JournalTarget::Sequence(1000));
+    assert!(!result.success);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn rename_and_delete_database_with_journal() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .enable_journal(&file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .rename_database(&file_path, "renamed");
+    assert!(result.success);
+    let new_file_path = result.data.unwrap();
+    assert!(!journal_file_path(&file_path).exists());
+    assert!(journal_file_path(&new_file_path).is_file());
+
+    let result = engine
+        .storage_api()
+        .find_journal_entries(&new_file_path);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().len(), 2);
+
+    let result = engine
+        .storage_api()
+        .restore_database_from_journal(&new_file_path, JournalTarget::Sequence(0));
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .find_database_by_file_path(&new_file_path);
+    let database = result.data.unwrap().unwrap();
+    assert_eq!(database.name(), "renamed");
+    assert!(database.journal());
+
+    let result = engine
+        .storage_api()
+        .delete_database(&new_file_path);
+    assert!(result.success);
+    assert!(!journal_file_path(&new_file_path).exists());
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn encrypt_database_with_journal_fails() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .enable_journal(&file_path);
+    assert!(result.success);
+
+    let options = EncryptionOptions::new(EncryptionKey::Passphrase(String::from("secret")));
+    let result = engine
+        .storage_api()
+        .encrypt_database(&file_path, &options);
+    assert!(!result.success);
+
+    let result = engine
+        .storage_api()
+        .disable_journal(&file_path);
+    assert!(result.success);
+    assert!(!journal_file_path(&file_path).exists());
+
+    let result = engine
+        .storage_api()
+        .encrypt_database(&file_path, &options);
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .enable_journal(&file_path);
+    assert!(!result.success);
+
+    config_settings.close_temp_dirs();
+}
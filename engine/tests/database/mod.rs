@@ -4,3 +4,11 @@ mod create_database;
 mod delete_database;
 mod modify_database;
 mod find_database;
+mod compression;
+mod encryption;
+mod file_header;
+mod integrity;
+mod backup;
+mod journal;
+mod dump;
+mod change_stream;
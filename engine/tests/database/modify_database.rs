@@ -29,3 +29,105 @@ fn change_database_description_success() {
 
     config_settings.close_temp_dirs();
 }
+
+#[test]
+fn rename_database_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let new_db_name = "renamed";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+    let new_file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", new_db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .rename_database(&file_path, new_db_name);
+    assert!(result.success);
+    assert!(result.error.is_none());
+    assert_eq!(result.data.unwrap(), new_file_path);
+    assert!(!file_path.is_file());
+
+    let result = engine
+        .storage_api()
+        .find_database(new_db_name);
+    assert_eq!(result.data.unwrap().unwrap().file_path(), &new_file_path);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn rename_database_to_existing_name_fails() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("test.{}", DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path("test", &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_database_to_db_dir("other");
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .rename_database(&file_path, "other");
+    assert!(!result.success);
+    assert!(result.error.is_some());
+    assert!(file_path.is_file());
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn clone_database_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .clone_database(&file_path, "cloned");
+    assert!(result.success);
+    let cloned_file_path = result.data.unwrap();
+    assert!(file_path.is_file());
+
+    let result = engine
+        .storage_api()
+        .find_database_by_file_path(&cloned_file_path);
+    assert_eq!(result.data.unwrap().unwrap().name(), "cloned");
+
+    let result = engine
+        .storage_api()
+        .find_collection(collection_name, &cloned_file_path);
+    assert!(result.data.unwrap().is_some());
+
+    config_settings.close_temp_dirs();
+}
@@ -1,6 +1,11 @@
 use engine::{
     Engine,
-    storage::DB_FILE_EXTENSION,
+    storage::{
+        DB_FILE_EXTENSION,
+        pb::document::Id,
+        pb::IdStrategy,
+        collection::CollectionOptions,
+    },
 };
 use crate::common::{
     ConfigSettings,
@@ -38,7 +43,7 @@ fn create_document_success() {
     assert!(result.log_error.is_none());
 
     let document = result.data.unwrap();
-    assert_eq!(document.id(), &1);
+    assert_eq!(document.id(), &Id::Integer(1));
     assert_eq!(document.data().len(), data_field_count);
 
     let result = engine
@@ -49,3 +54,130 @@ fn create_document_success() {
 
     config_settings.close_temp_dirs();
 }
+
+#[test]
+fn create_document_uuid_id_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let options = CollectionOptions {
+        id_strategy: IdStrategy::UuidV4,
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .create_collection_with_options(collection_name, &file_path, &options);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    assert!(result.success);
+
+    let document = result.data.unwrap();
+    match document.id() {
+        Id::Text(id) => assert_eq!(id.len(), 36),
+        Id::Integer(_) => panic!("Expected text document id"),
+    }
+
+    let result = engine
+        .storage_api()
+        .find_document_by_id(document.id(), &file_path, collection_name);
+    assert!(result.success);
+    assert!(result.data.unwrap().is_some());
+
+    // Ids are generated so the client cannot give one
+    let result = engine
+        .storage_api()
+        .create_document_with_id(
+            &file_path,
+            collection_name,
+            create_document_input_data(),
+            Some(Id::from("abc"))
+        );
+    assert!(!result.success);
+    assert!(result.error.is_some());
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn create_document_client_id_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let options = CollectionOptions {
+        id_strategy: IdStrategy::ClientInteger,
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .create_collection_with_options(collection_name, &file_path, &options);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_document_with_id(
+            &file_path,
+            collection_name,
+            create_document_input_data(),
+            Some(Id::Integer(1000))
+        );
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().id(), &Id::Integer(1000));
+
+    // Duplicate id
+    let result = engine
+        .storage_api()
+        .create_document_with_id(
+            &file_path,
+            collection_name,
+            create_document_input_data(),
+            Some(Id::Integer(1000))
+        );
+    assert!(!result.success);
+
+    // Missing id
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    assert!(!result.success);
+
+    // Wrong id type
+    let result = engine
+        .storage_api()
+        .create_document_with_id(
+            &file_path,
+            collection_name,
+            create_document_input_data(),
+            Some(Id::from("1001"))
+        );
+    assert!(!result.success);
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, collection_name, None);
+    assert_eq!(result.data.unwrap().len(), 1);
+
+    config_settings.close_temp_dirs();
+}
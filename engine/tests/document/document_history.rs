@@ -0,0 +1,238 @@
+use std::{
+    thread,
+    time::Duration,
+};
+use engine::{
+    Engine,
+    DocumentInputDataField,
+    storage::{
+        DB_FILE_EXTENSION,
+        collection::CollectionOptions,
+        pb::CappedLimits,
+        pb::document::{
+            DataType,
+            Id,
+            data_type,
+        },
+    },
+};
+use crate::common::{
+    ConfigSettings,
+    create_document_input_data,
+};
+
+#[test]
+fn document_history_restore_revision_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+    let first_age = DataType { data_type: Some(data_type::DataType::Int32(42)) };
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let options = CollectionOptions {
+        history: true,
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .create_collection_with_options(collection_name, &file_path, &options);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    let document = result.data.unwrap();
+
+    let mut data = create_document_input_data();
+    data[2] = DocumentInputDataField::new("age", "Int32", "43");
+    let result = engine
+        .storage_api()
+        .replace_document(&file_path, document.id(), collection_name, data);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .delete_document(&file_path, document.id(), collection_name);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_document_revisions(&file_path, collection_name, document.id());
+    let revisions = result.data.unwrap();
+    let revision_numbers: Vec<(u64, bool)> = revisions
+        .iter()
+        .map(|revision| (revision.revision, revision.deleted))
+        .collect();
+    assert_eq!(revision_numbers, vec![(1, false), (2, true)]);
+    assert_eq!(revisions[0].document.id(), document.id());
+    assert_eq!(revisions[0].document.data().get("age"), Some(&first_age));
+
+    let result = engine
+        .storage_api()
+        .find_document_revision(&file_path, collection_name, document.id(), 3);
+    assert!(result.data.unwrap().is_none());
+
+    // Restore the deleted document to its first version
+    let result = engine
+        .storage_api()
+        .restore_document_revision(&file_path, collection_name, document.id(), 1);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_document_by_id(document.id(), &file_path, collection_name);
+    let restored_document = result.data.unwrap().unwrap();
+    assert_eq!(restored_document.data().get("age"), Some(&first_age));
+
+    // Restoring an existing document keeps its current version
+    let result = engine
+        .storage_api()
+        .restore_document_revision(&file_path, collection_name, document.id(), 2);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_document_revision(&file_path, collection_name, document.id(), 3);
+    let revision = result.data.unwrap().unwrap();
+    assert!(!revision.deleted);
+    assert_eq!(revision.document.data().get("age"), Some(&first_age));
+
+    let result = engine
+        .storage_api()
+        .restore_document_revision(&file_path, collection_name, document.id(), 10);
+    assert!(!result.success);
+
+    let result = engine
+        .storage_api()
+        .purge_document_history(&file_path, collection_name, 3600);
+    assert_eq!(result.data, Some(0));
+
+    thread::sleep(Duration::from_millis(10));
+    let result = engine
+        .storage_api()
+        .purge_document_history(&file_path, collection_name, 0);
+    assert_eq!(result.data, Some(3));
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn document_history_disabled_keeps_no_revisions() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "people";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_collection(collection_name, &file_path);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, create_document_input_data());
+    let document = result.data.unwrap();
+
+    let result = engine
+        .storage_api()
+        .delete_document(&file_path, document.id(), collection_name);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_document_revisions(&file_path, collection_name, document.id());
+    assert!(result.data.unwrap().is_empty());
+
+    let result = engine
+        .storage_api()
+        .set_collection_history(collection_name, &file_path, true);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_collection(collection_name, &file_path);
+    assert!(result.data.unwrap().unwrap().history());
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn document_history_restore_revision_evicts_capped_documents() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "events";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(format!("{}.{}", db_name, DB_FILE_EXTENSION));
+    let message = |length: usize| vec![DocumentInputDataField::new("message", "Text", &"x".repeat(length))];
+
+    let result = engine
+        .storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    assert!(result.success);
+
+    let options = CollectionOptions {
+        capped: Some(CappedLimits::new(0, 100)),
+        history: true,
+        ..Default::default()
+    };
+    let result = engine
+        .storage_api()
+        .create_collection_with_options(collection_name, &file_path, &options);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .create_document(&file_path, collection_name, message(60));
+    assert!(result.success);
+    let result = engine
+        .storage_api()
+        .replace_document(&file_path, &Id::Integer(1), collection_name, message(1));
+    assert!(result.success);
+    for _ in 0..2 {
+        let result = engine
+            .storage_api()
+            .create_document(&file_path, collection_name, message(1));
+        assert!(result.success);
+    }
+
+    // Restored revision grows the collection past its max size
+    let result = engine
+        .storage_api()
+        .restore_document_revision(&file_path, collection_name, &Id::Integer(1), 1);
+    assert!(result.success);
+
+    let result = engine
+        .storage_api()
+        .find_all_documents(&file_path, collection_name, None);
+    let ids: Vec<Id> = result.data
+        .unwrap()
+        .iter()
+        .map(|document| document.id().clone())
+        .collect();
+    assert_eq!(ids, vec![Id::Integer(1), Id::Integer(3)]);
+
+    let result = engine
+        .storage_api()
+        .find_document_revisions(&file_path, collection_name, &Id::Integer(2));
+    assert!(result.data.unwrap()[0].deleted);
+
+    config_settings.close_temp_dirs();
+}
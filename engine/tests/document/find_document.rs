@@ -1,7 +1,11 @@
 use engine::{
     Engine,
-    storage::DB_FILE_EXTENSION,
+    storage::{
+        DB_FILE_EXTENSION,
+        document::FindOptions,
+    },
     DocumentInputDataField,
+    rust_decimal::Decimal,
 };
 use crate::common::{
     ConfigSettings,
@@ -203,15 +207,13 @@ fn find_documents_with_limit_success() {
         .create_collection(collection_name, &file_path);
 
     for _ in 1..=max_documents {
-        let mut data = Vec::new();
-        data.push(DocumentInputDataField::new("age", "Int32", "35"));
+        let data = vec![DocumentInputDataField::new("age", "Int32", "35")];
 
         engine.storage_api()
             .create_document(&file_path, collection_name, data);
     }
 
-    let mut query = Vec::new();
-    query.push(DocumentInputDataField::new("age", "Int32", "35"));
+    let query = vec![DocumentInputDataField::new("age", "Int32", "35")];
     
     let result = engine
         .storage_api()
@@ -229,3 +231,140 @@ fn find_documents_with_limit_success() {
 
     config_settings.close_temp_dirs();
 }
+
+#[test]
+fn find_documents_decimal128_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "invoices";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    engine.storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    
+    engine.storage_api()
+        .create_collection(collection_name, &file_path);
+
+    let amount = Decimal::from_str_exact("0.1").unwrap() + Decimal::from_str_exact("0.2").unwrap();
+    let data = vec![DocumentInputDataField::new("amount", "Decimal128", &amount.to_string())];
+    engine.storage_api()
+        .create_document(&file_path, collection_name, data);
+
+    let query = vec![DocumentInputDataField::new("amount", "Decimal128", "0.30")];
+    
+    let result = engine
+        .storage_api()
+        .find_documents(&file_path, collection_name, &query, None);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().len(), 1);
+
+    let query = vec![DocumentInputDataField::new("amount", "Decimal128", "0.30000000000000004")];
+
+    let result = engine
+        .storage_api()
+        .find_documents(&file_path, collection_name, &query, None);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().len(), 0);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn find_documents_integer_widths_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "accounts";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+    let external_id = u64::MAX.to_string();
+
+    engine.storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    
+    engine.storage_api()
+        .create_collection(collection_name, &file_path);
+
+    let data = vec![
+        DocumentInputDataField::new("external_id", "UInt64", &external_id),
+        DocumentInputDataField::new("counter", "UInt128", &u128::MAX.to_string()),
+    ];
+    let result = engine.storage_api()
+        .create_document(&file_path, collection_name, data);
+    assert!(result.success);
+
+    let query = vec![DocumentInputDataField::new("external_id", "UInt128", &external_id)];
+    
+    let result = engine
+        .storage_api()
+        .find_documents(&file_path, collection_name, &query, None);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().len(), 1);
+
+    let query = vec![DocumentInputDataField::new("external_id", "Int64", "-1")];
+
+    let result = engine
+        .storage_api()
+        .find_documents(&file_path, collection_name, &query, None);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().len(), 0);
+
+    config_settings.close_temp_dirs();
+}
+
+#[test]
+fn find_documents_strict_types_success() {
+    let config_settings = ConfigSettings::new();
+    let engine = Engine::build(&config_settings.config);
+    let db_name = "test";
+    let collection_name = "books";
+    let file_path = config_settings.db_dir
+        .path()
+        .join(&format!("{}.{}", db_name, DB_FILE_EXTENSION));
+
+    engine.storage_api()
+        .create_database_by_file_path(db_name, &file_path);
+    
+    engine.storage_api()
+        .create_collection(collection_name, &file_path);
+
+    let data = vec![DocumentInputDataField::new("year", "Int32", "1999")];
+    engine.storage_api()
+        .create_document(&file_path, collection_name, data);
+
+    let data = vec![DocumentInputDataField::new("year", "Decimal", "1999.0")];
+    engine.storage_api()
+        .create_document(&file_path, collection_name, data);
+
+    let query = vec![DocumentInputDataField::new("year", "Int64", "1999")];
+    
+    let result = engine
+        .storage_api()
+        .find_documents(&file_path, collection_name, &query, None);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().len(), 2);
+
+    let options = FindOptions {
+        limit: None,
+        strict_types: true,
+    };
+    let result = engine
+        .storage_api()
+        .find_documents_with_options(&file_path, collection_name, &query, &options);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().len(), 0);
+
+    let query = vec![DocumentInputDataField::new("year", "Int32", "1999")];
+
+    let result = engine
+        .storage_api()
+        .find_documents_with_options(&file_path, collection_name, &query, &options);
+    assert!(result.success);
+    assert_eq!(result.data.unwrap().len(), 1);
+
+    config_settings.close_temp_dirs();
+}
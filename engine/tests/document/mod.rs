@@ -3,4 +3,5 @@
 mod create_document;
 mod replace_document;
 mod delete_document;
-mod find_document;
\ No newline at end of file
+mod find_document;
+mod document_history;
\ No newline at end of file
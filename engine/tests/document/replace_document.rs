@@ -1,6 +1,9 @@
 use engine::{
     Engine,
-    storage::DB_FILE_EXTENSION,
+    storage::{
+        DB_FILE_EXTENSION,
+        pb::document::Id,
+    },
     DocumentInputDataField,
 };
 use crate::common::{
@@ -36,7 +39,7 @@ fn replace_document_success() {
     assert!(result.success);
 
     let document = result.data.unwrap();
-    assert_eq!(document.id(), &1);
+    assert_eq!(document.id(), &Id::Integer(1));
     assert_eq!(document.data().len(), data_field_count);
 
     let result = engine
@@ -46,7 +46,7 @@ fn main() {
                 },
                 Some(BookCommands::Find(args)) => {
                     let book = book_db_context.book_collection
-                        .find_one_by_id(&DocumentId(args.id))
+                        .find_one_by_id(&DocumentId::Integer(args.id))
                         .unwrap();
 
                     if let Some(book) = book {
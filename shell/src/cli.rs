@@ -114,13 +114,45 @@ pub fn run() {
   /create db                     Create a new database
   /delete db                     Delete the connected database
   /change db desc                Change description of the connected database
+  /rename db                     Rename the connected database and its database file
+  /clone db                      Clone the connected database to a new database
   /db details                    Show details of the connected database
+  /encrypt db                    Encrypt the connected database or rotate its key
+  /decrypt db                    Decrypt the connected database
+  /create key file               Create a key file with a random key for database encryption
+  /check db                      Check the integrity of a database and report all problems
+  /repair db                     Salvage a damaged database to a new database
+  /backup db                     Back up the connected database to a backup directory
+  /backup all dbs                Back up all databases in the database directory
+  /verify backup                 Verify that a backup is complete and not corrupted
+  /restore db                    Restore a database from a backup to the database directory
+  /restore all dbs               Restore all databases from a backup to the database directory
+  /enable journal                Record all changes to the connected database in a journal
+  /disable journal               Delete the journal of the connected database
+  /get journal                   List all entries in the journal of the connected database
+  /restore db to point           Restore the connected database to a point in its journal
+  /export db                     Export all documents in the connected database to a JSON file
+  /import db                     Import documents from a JSON file to the connected database
+  /dump all dbs                  Dump all databases in the database directory to a portable dump file
+  /restore dump                  Restore all databases in a dump file to the database directory
 
   ** COLLECTION COMMANDS **
 
   /get collections               List all collections in the connected database
+  /get collection schema         Report the implicit schema of a collection
   /create collection             Create a new collection to the connected database
   /delete collection             Delete a collection from the connected database. The collection has to be empty
+  /rename collection             Rename a collection in the connected database
+  /copy collection               Copy a collection to the connected database or another database
+  /set collection ttl            Set or remove the time-to-live policy of a collection
+  /purge expired                 Remove expired documents from all collections in the connected database
+  /set collection history        Enable or disable keeping revisions of replaced, updated and deleted documents
+  /purge document history        Remove document revisions older than given seconds from a collection
+  /migrate field                 Rename, drop or convert a field in all documents of a collection
+  /export collection             Export all documents in a collection to a JSON file
+  /import collection             Import documents from a JSON file to a collection
+  /export collection csv         Export documents in a collection to a CSV file
+  /import collection csv         Import documents from a CSV file to a collection
 
   ** DOCUMENT COMMANDS **
 
@@ -133,6 +165,9 @@ pub fn run() {
   /replace document              Replace a document with new data
   /delete document               Delete a document from a collection
   /delete all documents          Delete all documents from a collection
+  /get document revisions        List revisions of a document in a collection with history mode
+  /get document revision         List a single revision of a document
+  /restore document revision     Restore a document to a revision. Restores deleted documents too
 
   ** CONFIG COMMANDS **
 
@@ -141,6 +176,8 @@ pub fn run() {
   /config set db_dir_path        Set directory where databases will be created
   /config get logs_dir_path      Get directory where logs are created
   /config set logs_dir_path      Set directory where logs will be created
+  /config get db_compression     Get compression of new databases
+  /config set db_compression     Set compression of new databases
 
 More commands in the future...");
             },
@@ -174,18 +211,114 @@ More commands in the future...");
             "/change db desc" => {
                 cli.change_database_description();
             },
+            "/rename db" => {
+                cli.rename_database();
+            },
+            "/clone db" => {
+                cli.clone_database();
+            },
             "/db details" => {
                 cli.show_database_details();
             }
+            "/encrypt db" => {
+                cli.encrypt_database();
+            },
+            "/decrypt db" => {
+                cli.decrypt_database();
+            },
+            "/create key file" => {
+                cli.create_key_file();
+            },
+            "/check db" => {
+                cli.check_database();
+            },
+            "/repair db" => {
+                cli.repair_database();
+            },
+            "/backup db" => {
+                cli.backup_database();
+            },
+            "/backup all dbs" => {
+                cli.backup_all_databases();
+            },
+            "/verify backup" => {
+                cli.verify_backup();
+            },
+            "/restore db" => {
+                cli.restore_database();
+            },
+            "/restore all dbs" => {
+                cli.restore_all_databases();
+            },
+            "/enable journal" => {
+                cli.enable_journal();
+            },
+            "/disable journal" => {
+                cli.disable_journal();
+            },
+            "/get journal" => {
+                cli.list_journal_entries();
+            },
+            "/restore db to point" => {
+                cli.restore_database_from_journal();
+            },
+            "/export db" => {
+                cli.export_database_to_json();
+            },
+            "/import db" => {
+                cli.import_json_to_database();
+            },
+            "/dump all dbs" => {
+                cli.dump_all_databases();
+            },
+            "/restore dump" => {
+                cli.restore_dump();
+            },
             "/get collections" => {
                 cli.list_all_collections();
             },
+            "/get collection schema" => {
+                cli.show_collection_schema();
+            },
             "/create collection" => {
                 cli.create_collection();
             },
             "/delete collection" => {
                 cli.delete_collection();
             },
+            "/rename collection" => {
+                cli.rename_collection();
+            },
+            "/copy collection" => {
+                cli.copy_collection();
+            },
+            "/set collection ttl" => {
+                cli.set_collection_ttl();
+            },
+            "/purge expired" => {
+                cli.purge_expired_documents();
+            },
+            "/set collection history" => {
+                cli.set_collection_history();
+            },
+            "/purge document history" => {
+                cli.purge_document_history();
+            },
+            "/migrate field" => {
+                cli.migrate_field();
+            },
+            "/export collection" => {
+                cli.export_collection_to_json();
+            },
+            "/import collection" => {
+                cli.import_json_to_collection();
+            },
+            "/export collection csv" => {
+                cli.export_collection_to_csv();
+            },
+            "/import collection csv" => {
+                cli.import_csv_to_collection();
+            },
             "/get all documents" => {
                 cli.list_all_documents(false);
             },
@@ -213,6 +346,15 @@ More commands in the future...");
             "/delete all documents" => {
                 cli.delete_all_documents();
             },
+            "/get document revisions" => {
+                cli.list_document_revisions();
+            },
+            "/get document revision" => {
+                cli.list_document_revision();
+            },
+            "/restore document revision" => {
+                cli.restore_document_revision();
+            },
             "/config get all" => {
                 Cli::list_all_configs(&config);
             },
@@ -228,6 +370,12 @@ More commands in the future...");
             "/config get logs_dir_path" => {
                 println!("{}", config.logs_dir_path().display());
             },
+            "/config set db_compression" => {
+                cli.set_db_compression();
+            },
+            "/config get db_compression" => {
+                println!("{}", config.db_compression());
+            },
             _ => {
                 println!("Command not found\n{}", help_message);
                 continue
@@ -4,10 +4,23 @@ use crate::{
     ask_action_confirm,
     cli::CONFIRM_OPTION_YES,
     database::ConnectedDatabase,
+    transfer::ask_yes_or_no,
     db_not_connected,
     event_log_failed,
     error_log_failed,
 };
+use engine::storage::{
+    collection::CollectionOptions,
+    document::format_value,
+    migration::FieldMigration,
+    pb::IdStrategy,
+    pb::TtlPolicy,
+    pb::CappedLimits,
+};
+use std::{
+    path::PathBuf,
+    str::FromStr,
+};
 
 impl Cli {
     /// Checks if collection exists.
@@ -52,9 +65,33 @@ impl Cli {
             Ok(collection_name) => collection_name,
             Err(_) => return,
         };
+        let id_strategy = match ask_user_input(
+            "Document ID strategy (AutoIncrement, UuidV4, UuidV7, ClientInteger, ClientText; empty for AutoIncrement): "
+        ) {
+            Ok(id_strategy) => id_strategy,
+            Err(_) => return,
+        };
+        let mut options = CollectionOptions::default();
+        if !id_strategy.is_empty() {
+            options.id_strategy = match IdStrategy::from_str(&id_strategy) {
+                Ok(id_strategy) => id_strategy,
+                Err(e) => return eprintln!("Error: {}", e),
+            };
+        }
+        let max_documents = match ask_capped_limit("Max documents (empty for no limit): ") {
+            Some(max_documents) => max_documents,
+            None => return,
+        };
+        let max_size_bytes = match ask_capped_limit("Max size in bytes (empty for no limit): ") {
+            Some(max_size_bytes) => max_size_bytes,
+            None => return,
+        };
+        if max_documents > 0 || max_size_bytes > 0 {
+            options.capped = Some(CappedLimits::new(max_documents, max_size_bytes));
+        }
         let result = self.engine
             .storage_api()
-            .create_collection(&collection_name, connected_db.file_path());
+            .create_collection_with_options(&collection_name, connected_db.file_path(), &options);
 
         if result.success {
             event_log_failed(result.log_error);
@@ -109,6 +146,85 @@ impl Cli {
 
     }
 
+    /// Show menu to rename a collection in the connected database.
+    pub fn rename_collection(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+        let new_collection_name = match ask_user_input("New collection name: ") {
+            Ok(new_collection_name) => new_collection_name,
+            Err(_) => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .rename_collection(&collection_name, &new_collection_name, connected_db.file_path());
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            println!("Collection renamed");
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to copy a collection in the connected database.
+    /// 
+    /// The collection can be copied to the connected database or another database file.
+    pub fn copy_collection(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+        let new_collection_name = match ask_user_input("New collection name: ") {
+            Ok(new_collection_name) => new_collection_name,
+            Err(_) => return,
+        };
+        let target_file_path = match ask_user_input("Target database file path (empty for connected database): ") {
+            Ok(target_file_path) => target_file_path,
+            Err(_) => return,
+        };
+        let target_file_path = if target_file_path.is_empty() {
+            PathBuf::from(connected_db.file_path())
+        } else {
+            PathBuf::from(target_file_path)
+        };
+
+        let result = self.engine
+            .storage_api()
+            .copy_collection(
+                &collection_name,
+                connected_db.file_path(),
+                &new_collection_name,
+                &target_file_path
+            );
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            println!("Collection copied");
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
     /// List all collections in the connected database.
     pub fn list_all_collections(&self) {
         let connected_db = match &self.connected_db {
@@ -126,7 +242,22 @@ impl Cli {
                 println!("Number of collections: {}", collections.len());
 
                 for collection in collections {
-                    println!("{}", collection.name());
+                    let mut details = Vec::new();
+                    if let Some(ttl) = collection.ttl() {
+                        details.push(ttl.to_string());
+                    }
+                    if let Some(capped) = collection.capped() {
+                        details.push(capped.to_string());
+                    }
+                    if collection.history() {
+                        details.push("History mode".to_string());
+                    }
+
+                    if details.is_empty() {
+                        println!("{}", collection.name());
+                    } else {
+                        println!("{} ({})", collection.name(), details.join(", "));
+                    }
                 }
             }
         } else {
@@ -137,4 +268,296 @@ impl Cli {
             }
         }
     }
+
+    /// Show menu to set the time-to-live policy of a collection.
+    /// 
+    /// Empty field removes the policy.
+    pub fn set_collection_ttl(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+        let field = match ask_user_input("Time field (empty to remove TTL): ") {
+            Ok(field) => field,
+            Err(_) => return,
+        };
+        let ttl = if field.is_empty() {
+            None
+        } else {
+            let expire_after_seconds = match ask_user_input(
+                "Expire after seconds (0 if the field has the expiry time): "
+            ) {
+                Ok(expire_after_seconds) => expire_after_seconds,
+                Err(_) => return,
+            };
+            let expire_after_seconds = match expire_after_seconds.parse::<u64>() {
+                Ok(expire_after_seconds) => expire_after_seconds,
+                Err(e) => return eprintln!("Invalid seconds: {}", e),
+            };
+            Some(TtlPolicy::new(&field, expire_after_seconds))
+        };
+
+        let result = self.engine
+            .storage_api()
+            .set_collection_ttl(&collection_name, connected_db.file_path(), ttl);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            println!("Collection TTL set");
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to enable or disable history mode of a collection.
+    pub fn set_collection_history(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+        let enabled = match ask_yes_or_no("Keep document revisions (yes, no, empty for yes): ", true) {
+            Some(enabled) => enabled,
+            None => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .set_collection_history(&collection_name, connected_db.file_path(), enabled);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if enabled {
+                println!("History mode enabled");
+            } else {
+                println!("History mode disabled");
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to remove old document revisions from a collection.
+    pub fn purge_document_history(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+        let older_than_seconds = match ask_user_input("Remove revisions older than seconds: ") {
+            Ok(older_than_seconds) => older_than_seconds,
+            Err(_) => return,
+        };
+        let older_than_seconds = match older_than_seconds.parse::<u64>() {
+            Ok(older_than_seconds) => older_than_seconds,
+            Err(e) => return eprintln!("Invalid seconds: {}", e),
+        };
+        let result = self.engine
+            .storage_api()
+            .purge_document_history(connected_db.file_path(), &collection_name, older_than_seconds);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(removed_count) = result.data {
+                println!("Purged {} revisions", removed_count);
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Remove expired documents from all collections in the connected database.
+    pub fn purge_expired_documents(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let result = self.engine
+            .storage_api()
+            .purge_expired_documents(connected_db.file_path());
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(removed_count) = result.data {
+                println!("Purged {} expired documents", removed_count);
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to report the implicit schema of a collection.
+    pub fn show_collection_schema(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+
+        let result = self.engine
+            .storage_api()
+            .infer_collection_schema(&collection_name, connected_db.file_path());
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(report) = result.data {
+                println!("Number of documents: {}", report.document_count);
+                println!("Number of fields: {}", report.fields.len());
+
+                for field in report.fields {
+                    let data_types: Vec<String> = field.data_types
+                        .iter()
+                        .map(|(data_type, count)| format!("{} ({})", data_type, count))
+                        .collect();
+                    let sample_values: Vec<String> = field.sample_values
+                        .iter()
+                        .map(format_value)
+                        .collect();
+                    // Fields are only found in documents, so document count is never 0 here
+                    let percent = field.present_count * 100 / report.document_count;
+
+                    println!("\"{}\"", field.path);
+                    println!("  Present: {}/{} ({}%)", field.present_count, report.document_count, percent);
+                    println!("  Null: {}", field.null_count);
+                    println!("  Data types: {}", data_types.join(", "));
+                    println!("  Samples: {}", sample_values.join(", "));
+                }
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to migrate a field in every document of a collection.
+    /// 
+    /// The field can be renamed, dropped or converted to another data type.
+    pub fn migrate_field(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+        let field = match ask_user_input("Field: ") {
+            Ok(field) => field,
+            Err(_) => return,
+        };
+        let operation = match ask_user_input("Operation (rename, drop, convert): ") {
+            Ok(operation) => operation,
+            Err(_) => return,
+        };
+
+        let migration = match operation.as_str() {
+            "rename" => {
+                let new_field = match ask_user_input("New field name: ") {
+                    Ok(new_field) => new_field,
+                    Err(_) => return,
+                };
+                FieldMigration::Rename { field, new_field }
+            },
+            "drop" => {
+                let confirm = match ask_action_confirm(
+                    &format!("Drop field '{}' from all documents?", field)
+                ) {
+                    Ok(confirm) => confirm,
+                    Err(_) => return,
+                };
+                if confirm.as_str() != CONFIRM_OPTION_YES {
+                    return println!("Canceled action");
+                }
+                FieldMigration::Drop { field }
+            },
+            "convert" => {
+                let data_type = match ask_user_input("Data type: ") {
+                    Ok(data_type) => data_type,
+                    Err(_) => return,
+                };
+                FieldMigration::Convert { field, data_type }
+            },
+            _ => return eprintln!("Invalid operation. Operation must be 'rename', 'drop' or 'convert'"),
+        };
+
+        let result = self.engine
+            .storage_api()
+            .migrate_field(connected_db.file_path(), &collection_name, &migration);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(migration_result) = result.data {
+                println!("Migrated documents: {}", migration_result.converted_count);
+                println!("Failed documents: {}", migration_result.failed_count);
+
+                for document_id in migration_result.failed_document_ids {
+                    println!("  _id: {}", document_id);
+                }
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+}
+
+/// Asks a limit of a capped collection. Empty input means no limit.
+/// 
+/// Returns `None` if the limit is not valid.
+fn ask_capped_limit(text_to_ask: &str) -> Option<u64> {
+    let limit = match ask_user_input(text_to_ask) {
+        Ok(limit) => limit,
+        Err(_) => return None,
+    };
+    if limit.is_empty() {
+        return Some(0);
+    }
+
+    match limit.parse::<u64>() {
+        Ok(limit) => Some(limit),
+        Err(e) => {
+            eprintln!("Invalid limit: {}", e);
+            None
+        },
+    }
 }
@@ -1,11 +1,17 @@
-use engine::config::Config;
+use engine::{
+    config::Config,
+    storage::pb::Compression,
+};
 use crate::{
     cli::Cli,
     ask_user_input,
     event_log_failed,
     error_log_failed,
 };
-use std::path::Path;
+use std::{
+    path::Path,
+    str::FromStr,
+};
 
 fn config_save_success() {
     println!("Restart the program for the changes to take effect");
@@ -14,8 +20,9 @@ fn config_save_success() {
 impl Cli {
     /// Lists the current configurations the program is using.
     pub fn list_all_configs(config: &Config) {
-        println!("  Database directory: {}", config.db_dir_path().display());
-        println!("  Logs directory:     {}", config.logs_dir_path().display());
+        println!("  Database directory:   {}", config.db_dir_path().display());
+        println!("  Logs directory:       {}", config.logs_dir_path().display());
+        println!("  Database compression: {}", config.db_compression());
     }
 
     /// Sets new `db_dir_path` configuration.
@@ -77,4 +84,33 @@ impl Cli {
             }
         }
     }
+
+    /// Sets new `db_compression` configuration.
+    pub fn set_db_compression(&self) {
+        let input = match ask_user_input("New database compression (None, Zstd, Lz4): ") {
+            Ok(input) => input,
+            Err(_) => return,
+        };
+        let compression = match Compression::from_str(&input) {
+            Ok(compression) => compression,
+            Err(e) => return eprintln!("Error: {}", e),
+        };
+
+        let result = self.engine
+            .config_api()
+            .set_db_compression(compression);
+
+        if result.success {
+            event_log_failed(result.log_error);
+            
+            println!("Database compression set successfully");
+            config_save_success();
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: Failed to set database compression: {}", e);
+            }
+        }
+    }
 }
@@ -1,10 +1,24 @@
-use std::path::{
-    Path,
-    PathBuf,
+use std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    str::FromStr,
+};
+use engine::storage::{
+    database::DatabaseDto,
+    encryption::{
+        Cipher,
+        EncryptionKey,
+        EncryptionOptions,
+    },
+    journal::JournalTarget,
+    pb::Compression,
 };
 use crate::{
     cli::Cli,
     ask_user_input,
+    ask_secret_input,
     ask_action_confirm,
     cli::CONFIRM_OPTION_YES,
     db_not_connected,
@@ -119,10 +133,16 @@ impl Cli {
 
             if let Some(db) = result.data {
                 if let Some(db) = db {
+                    if db.locked() && !self.unlock_database(&db) {
+                        return;
+                    }
                     let _ = &self.connected_db.replace(ConnectedDatabase::new(
                         db.name(),
                         db.file_path()
                     ));
+                    self.engine
+                        .storage_api()
+                        .sweep_expired_documents(db.file_path());
                     println!("Connected to database");
                 } else {
                     println!("Cannot find database");
@@ -153,10 +173,16 @@ impl Cli {
 
             if let Some(db) = result.data {
                 if let Some(db) = db {
+                    if db.locked() && !self.unlock_database(&db) {
+                        return;
+                    }
                     let _ = &self.connected_db.replace(ConnectedDatabase::new(
                         db.name(),
                         db.file_path()
                     ));
+                    self.engine
+                        .storage_api()
+                        .sweep_expired_documents(db.file_path());
                     println!("Connected to database");
                 } else {
                     println!("Cannot find database");
@@ -177,9 +203,50 @@ impl Cli {
             Ok(db_name) => db_name,
             Err(_) => return,
         };
-        let result = self.engine
-            .storage_api()
-            .create_database_to_db_dir(&db_name);
+        let compression = match ask_user_input(
+            "Compression (None, Zstd, Lz4; empty for default): "
+        ) {
+            Ok(compression) => compression,
+            Err(_) => return,
+        };
+        let cipher = match ask_user_input(
+            "Encryption (Aes256Gcm, ChaCha20Poly1305; empty for no encryption): "
+        ) {
+            Ok(cipher) => cipher,
+            Err(_) => return,
+        };
+        let encryption = if cipher.is_empty() {
+            None
+        } else {
+            let cipher = match Cipher::from_str(&cipher) {
+                Ok(cipher) => cipher,
+                Err(e) => return eprintln!("Error: {}", e),
+            };
+            match ask_encryption_key() {
+                Some(key) => Some(EncryptionOptions { key, cipher }),
+                None => return,
+            }
+        };
+        let result = if compression.is_empty() && encryption.is_none() {
+            self.engine
+                .storage_api()
+                .create_database_to_db_dir(&db_name)
+        } else {
+            let mut options = self.engine
+                .storage_api()
+                .default_db_options()
+                .clone();
+            if !compression.is_empty() {
+                options.compression = match Compression::from_str(&compression) {
+                    Ok(compression) => compression,
+                    Err(e) => return eprintln!("Error: {}", e),
+                };
+            }
+            options.encryption = encryption;
+            self.engine
+                .storage_api()
+                .create_database_to_db_dir_with_options(&db_name, &options)
+        };
 
         if result.success {
             event_log_failed(result.log_error);
@@ -241,12 +308,20 @@ impl Cli {
         if result.success {
             event_log_failed(result.log_error);
 
-            if let Some(databases) = result.data {
-                println!("Number of databases: {}", databases.len());
+            if let Some(database_list) = result.data {
+                println!("Number of databases: {}", database_list.databases.len());
 
-                for db in databases {
+                for db in database_list.databases {
                     println!("{}", &db);
                 }
+
+                if !database_list.problems.is_empty() {
+                    println!("\nDatabase files that could not be listed: {}", database_list.problems.len());
+
+                    for problem in database_list.problems {
+                        println!("  {}", problem);
+                    }
+                }
             }
         } else {
             error_log_failed(result.log_error);
@@ -312,4 +387,626 @@ impl Cli {
             }
         }
     }
+
+    /// Show menu to rename the connected database.
+    /// 
+    /// The database file is renamed too, so the database is reconnected.
+    pub fn rename_database(&mut self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let new_db_name = match ask_user_input("New database name: ") {
+            Ok(new_db_name) => new_db_name,
+            Err(_) => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .rename_database(connected_db.file_path(), &new_db_name);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(file_path) = result.data {
+                self.connected_db = Some(ConnectedDatabase::new(&new_db_name, &file_path));
+                println!("Database renamed");
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to clone the connected database to a new database.
+    pub fn clone_database(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let new_db_name = match ask_user_input("New database name: ") {
+            Ok(new_db_name) => new_db_name,
+            Err(_) => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .clone_database(connected_db.file_path(), &new_db_name);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(file_path) = result.data {
+                println!("Database cloned to '{}'", file_path.display());
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Asks the file path of a database to check or repair.
+    /// 
+    /// Damaged databases may not be connectable,
+    /// so any database file can be given instead of the connected database.
+    fn ask_check_file_path(&self) -> Option<PathBuf> {
+        let file_path = match ask_user_input("Database file path (empty for the connected database): ") {
+            Ok(file_path) => file_path,
+            Err(_) => return None,
+        };
+        if !file_path.is_empty() {
+            return Some(PathBuf::from(file_path));
+        }
+
+        match &self.connected_db {
+            Some(db) => return Some(db.file_path().to_path_buf()),
+            None => {
+                db_not_connected();
+                return None;
+            },
+        }
+    }
+
+    /// Show menu to check the integrity of a database.
+    pub fn check_database(&self) {
+        let file_path = match self.ask_check_file_path() {
+            Some(file_path) => file_path,
+            None => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .check_database(&file_path);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(report) = result.data {
+                println!(
+                    "Checked {} collections and {} documents",
+                    report.collection_count,
+                    report.document_count
+                );
+                if report.is_ok() {
+                    println!("No problems found");
+                } else {
+                    println!("{} problems found:", report.problems.len());
+                    for problem in report.problems {
+                        println!("  {}", problem);
+                    }
+                    println!("Use /repair db to salvage the database to a new database");
+                }
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to repair a database by salvaging it to a new database.
+    pub fn repair_database(&self) {
+        let file_path = match self.ask_check_file_path() {
+            Some(file_path) => file_path,
+            None => return,
+        };
+        let new_db_name = match ask_user_input("New database name: ") {
+            Ok(new_db_name) => new_db_name,
+            Err(_) => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .repair_database(&file_path, &new_db_name);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(repair) = result.data {
+                for problem in repair.report.problems.iter() {
+                    println!("  {}", problem);
+                }
+                println!(
+                    "Salvaged {} collections and {} documents to '{}'",
+                    repair.report.collection_count,
+                    repair.report.document_count,
+                    repair.file_path.display()
+                );
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to back up the connected database.
+    pub fn backup_database(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let backup_dir_path = match ask_user_input("Backup directory path: ") {
+            Ok(backup_dir_path) => backup_dir_path,
+            Err(_) => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .backup_database(connected_db.file_path(), Path::new(&backup_dir_path));
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            println!("Database backed up to '{}'", backup_dir_path);
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to back up all databases in the database directory.
+    pub fn backup_all_databases(&self) {
+        let backup_dir_path = match ask_user_input("Backup directory path: ") {
+            Ok(backup_dir_path) => backup_dir_path,
+            Err(_) => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .backup_all_databases(Path::new(&backup_dir_path));
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(manifest) = result.data {
+                println!("{} databases backed up to '{}'", manifest.databases.len(), backup_dir_path);
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to verify a backup.
+    pub fn verify_backup(&self) {
+        let backup_dir_path = match ask_user_input("Backup directory path: ") {
+            Ok(backup_dir_path) => backup_dir_path,
+            Err(_) => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .verify_backup(Path::new(&backup_dir_path));
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(manifest) = result.data {
+                println!(
+                    "Backup is valid. Created at {} by engine version {}",
+                    manifest.created_at,
+                    manifest.engine_version
+                );
+                for entry in manifest.databases {
+                    println!("  {} ({} bytes)", entry.name, entry.size);
+                }
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to restore a database from a backup to the database directory.
+    pub fn restore_database(&self) {
+        let backup_dir_path = match ask_user_input("Backup directory path: ") {
+            Ok(backup_dir_path) => backup_dir_path,
+            Err(_) => return,
+        };
+        let db_name = match ask_user_input("Database name: ") {
+            Ok(db_name) => db_name,
+            Err(_) => return,
+        };
+        let confirm = match ask_action_confirm(
+            &format!("Database '{}' will be replaced with the backup. Restore database?", db_name)
+        ) {
+            Ok(confirm) => confirm,
+            Err(_) => return,
+        };
+
+        match confirm.as_str() {
+            CONFIRM_OPTION_YES => {
+                let result = self.engine
+                    .storage_api()
+                    .restore_database(Path::new(&backup_dir_path), &db_name);
+
+                if result.success {
+                    event_log_failed(result.log_error);
+
+                    if let Some(file_path) = result.data {
+                        println!("Database restored to '{}'", file_path.display());
+                    }
+                } else {
+                    error_log_failed(result.log_error);
+
+                    if let Some(e) = result.error {
+                        eprintln!("Error: {}", e);
+                    }
+                }
+            },
+            _ => return println!("Canceled action"),
+        }
+    }
+
+    /// Show menu to restore all databases from a backup to the database directory.
+    pub fn restore_all_databases(&self) {
+        let backup_dir_path = match ask_user_input("Backup directory path: ") {
+            Ok(backup_dir_path) => backup_dir_path,
+            Err(_) => return,
+        };
+        let confirm = match ask_action_confirm(
+            "All databases in the backup will replace the databases in the database directory. Restore databases?"
+        ) {
+            Ok(confirm) => confirm,
+            Err(_) => return,
+        };
+
+        match confirm.as_str() {
+            CONFIRM_OPTION_YES => {
+                let result = self.engine
+                    .storage_api()
+                    .restore_all_databases(Path::new(&backup_dir_path));
+
+                if result.success {
+                    event_log_failed(result.log_error);
+
+                    if let Some(file_paths) = result.data {
+                        println!("{} databases restored", file_paths.len());
+                    }
+                } else {
+                    error_log_failed(result.log_error);
+
+                    if let Some(e) = result.error {
+                        eprintln!("Error: {}", e);
+                    }
+                }
+            },
+            _ => return println!("Canceled action"),
+        }
+    }
+
+    /// Enable the journal of the connected database.
+    pub fn enable_journal(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let result = self.engine
+            .storage_api()
+            .enable_journal(connected_db.file_path());
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            println!("Journal enabled");
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to disable the journal of the connected database.
+    pub fn disable_journal(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let confirm = match ask_action_confirm(
+            "The journal will be deleted and the database cannot be restored from it. Disable journal?"
+        ) {
+            Ok(confirm) => confirm,
+            Err(_) => return,
+        };
+
+        match confirm.as_str() {
+            CONFIRM_OPTION_YES => {
+                let result = self.engine
+                    .storage_api()
+                    .disable_journal(connected_db.file_path());
+
+                if result.success {
+                    event_log_failed(result.log_error);
+
+                    println!("Journal disabled");
+                } else {
+                    error_log_failed(result.log_error);
+
+                    if let Some(e) = result.error {
+                        eprintln!("Error: {}", e);
+                    }
+                }
+            },
+            _ => return println!("Canceled action"),
+        }
+    }
+
+    /// List all entries in the journal of the connected database.
+    pub fn list_journal_entries(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let result = self.engine
+            .storage_api()
+            .find_journal_entries(connected_db.file_path());
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(entries) = result.data {
+                println!("Number of journal entries: {}", entries.len());
+
+                for entry in entries {
+                    println!("  {}", entry);
+                }
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to restore the connected database to a point in its journal.
+    pub fn restore_database_from_journal(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let target = match ask_user_input("Journal sequence number or time (e.g. 2024-01-31 12:00:00): ") {
+            Ok(target) => target,
+            Err(_) => return,
+        };
+        let target = match JournalTarget::from_str(&target) {
+            Ok(target) => target,
+            Err(e) => return eprintln!("Error: {}", e),
+        };
+        let confirm = match ask_action_confirm(
+            "The database will be replaced with its state at the given point. Restore database?"
+        ) {
+            Ok(confirm) => confirm,
+            Err(_) => return,
+        };
+
+        match confirm.as_str() {
+            CONFIRM_OPTION_YES => {
+                let result = self.engine
+                    .storage_api()
+                    .restore_database_from_journal(connected_db.file_path(), target);
+
+                if result.success {
+                    event_log_failed(result.log_error);
+
+                    if let Some(sequence) = result.data {
+                        println!("Database restored to journal entry {}", sequence);
+                    }
+                } else {
+                    error_log_failed(result.log_error);
+
+                    if let Some(e) = result.error {
+                        eprintln!("Error: {}", e);
+                    }
+                }
+            },
+            _ => return println!("Canceled action"),
+        }
+    }
+
+    /// Asks the key of a locked database and unlocks it.
+    /// 
+    /// Returns true if the database was unlocked.
+    fn unlock_database(&self, db: &DatabaseDto) -> bool {
+        println!("Database is encrypted");
+        let key = match ask_encryption_key() {
+            Some(key) => key,
+            None => return false,
+        };
+        let result = self.engine
+            .storage_api()
+            .unlock_database(db.file_path(), &key);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            println!("Database unlocked");
+            return true;
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+            return false;
+        }
+    }
+
+    /// Show menu to encrypt the connected database or rotate its key.
+    pub fn encrypt_database(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let cipher = match ask_user_input(
+            "Encryption (Aes256Gcm, ChaCha20Poly1305; empty for Aes256Gcm): "
+        ) {
+            Ok(cipher) => cipher,
+            Err(_) => return,
+        };
+        let cipher = if cipher.is_empty() {
+            Cipher::default()
+        } else {
+            match Cipher::from_str(&cipher) {
+                Ok(cipher) => cipher,
+                Err(e) => return eprintln!("Error: {}", e),
+            }
+        };
+        let key = match ask_encryption_key() {
+            Some(key) => key,
+            None => return,
+        };
+        let options = EncryptionOptions { key, cipher };
+
+        let result = self.engine
+            .storage_api()
+            .find_database_by_file_path(connected_db.file_path());
+        let encrypted = match result.data {
+            Some(Some(db)) => db.encryption().is_some(),
+            _ => return println!("Cannot find connected database"),
+        };
+
+        let result = if encrypted {
+            self.engine
+                .storage_api()
+                .rotate_database_key(connected_db.file_path(), &options)
+        } else {
+            self.engine
+                .storage_api()
+                .encrypt_database(connected_db.file_path(), &options)
+        };
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if encrypted {
+                println!("Database key rotated");
+            } else {
+                println!("Database encrypted");
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to decrypt the connected database.
+    pub fn decrypt_database(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let confirm = match ask_action_confirm(
+            "The database file will be stored unencrypted. Decrypt database?"
+        ) {
+            Ok(confirm) => confirm,
+            Err(_) => return,
+        };
+
+        match confirm.as_str() {
+            CONFIRM_OPTION_YES => {
+                let result = self.engine
+                    .storage_api()
+                    .decrypt_database(connected_db.file_path());
+
+                if result.success {
+                    event_log_failed(result.log_error);
+
+                    println!("Database decrypted");
+                } else {
+                    error_log_failed(result.log_error);
+
+                    if let Some(e) = result.error {
+                        eprintln!("Error: {}", e);
+                    }
+                }
+            },
+            _ => return,
+        }
+    }
+
+    /// Show menu to create a key file.
+    pub fn create_key_file(&self) {
+        let file_path = match ask_user_input("Key file path: ") {
+            Ok(file_path) => file_path,
+            Err(_) => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .create_key_file(Path::new(&file_path));
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            println!("Key file created. Keep it safe, the database cannot be read without it");
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+}
+
+/// Asks for a passphrase or a key file path.
+/// 
+/// Returns `None` if both are empty.
+fn ask_encryption_key() -> Option<EncryptionKey> {
+    let passphrase = match ask_secret_input("Passphrase (empty to use a key file): ") {
+        Ok(passphrase) => passphrase,
+        Err(_) => return None,
+    };
+    if !passphrase.is_empty() {
+        return Some(EncryptionKey::Passphrase(passphrase));
+    }
+
+    let file_path = match ask_user_input("Key file path: ") {
+        Ok(file_path) => file_path,
+        Err(_) => return None,
+    };
+    if file_path.is_empty() {
+        return None;
+    }
+
+    Some(EncryptionKey::KeyFile(PathBuf::from(file_path)))
 }
@@ -6,9 +6,22 @@ use crate::{
     db_not_connected,
     event_log_failed,
     error_log_failed,
+    transfer::ask_yes_or_no,
+};
+use engine::{
+    Engine,
+    DocumentInputDataField,
+    storage::{
+        document::FindOptions,
+        history::DocumentRevisionDto,
+        pb::document::Id,
+        pb::IdStrategy,
+    },
+};
+use std::{
+    io,
+    path::Path,
 };
-use engine::DocumentInputDataField;
-use std::io;
 
 impl Cli {
     /// Show menu to create a new document to a collection.
@@ -21,6 +34,19 @@ impl Cli {
             Ok(collection_name) => collection_name,
             Err(_) => return,
         };
+        let document_id = match ask_user_input("Document ID (empty to generate): ") {
+            Ok(document_id) => document_id,
+            Err(_) => return,
+        };
+        let document_id = if document_id.is_empty() {
+            None
+        } else {
+            let id_strategy = find_id_strategy(&self.engine, connected_db.file_path(), &collection_name);
+            match parse_document_id(&document_id, id_strategy) {
+                Some(id) => Some(id),
+                None => return eprintln!("Invalid document ID"),
+            }
+        };
 
         // input data for the new document
         let mut data: Vec<DocumentInputDataField> = Vec::new();
@@ -44,12 +70,14 @@ impl Cli {
 
         let result = self.engine
             .storage_api()
-            .create_document(connected_db.file_path(), &collection_name, data);
+            .create_document_with_id(connected_db.file_path(), &collection_name, data, document_id);
 
         if result.success {
             event_log_failed(result.log_error);
 
-            println!("Document created");
+            if let Some(document) = result.data {
+                println!("Document created with ID '{}'", document.id());
+            }
         } else {
             error_log_failed(result.log_error);
 
@@ -73,9 +101,10 @@ impl Cli {
             Ok(document_id) => document_id,
             Err(_) => return,
         };
-        let document_id: u64 = match document_id.parse() {
-            Ok(id) => id,
-            Err(e) => return eprintln!("Invalid document ID: {e}"),
+        let id_strategy = find_id_strategy(&self.engine, connected_db.file_path(), &collection_name);
+        let document_id = match parse_document_id(&document_id, id_strategy) {
+            Some(id) => id,
+            None => return eprintln!("Invalid document ID"),
         };
 
         // input data for the new document
@@ -129,9 +158,10 @@ impl Cli {
             Ok(document_id) => document_id,
             Err(_) => return,
         };
-        let document_id: u64 = match document_id.parse() {
-            Ok(id) => id,
-            Err(e) => return eprintln!("Invalid document ID: {e}"),
+        let id_strategy = find_id_strategy(&self.engine, connected_db.file_path(), &collection_name);
+        let document_id = match parse_document_id(&document_id, id_strategy) {
+            Some(id) => id,
+            None => return eprintln!("Invalid document ID"),
         };
         let confirm = match ask_action_confirm(
             &format!("Delete document with ID '{}'?", document_id)
@@ -265,9 +295,10 @@ impl Cli {
             Ok(id) => id,
             Err(_) => return,
         };
-        let document_id: u64 = match document_id.parse() {
-            Ok(id) => id,
-            Err(e) => return eprintln!("Invalid document ID: {e}"),
+        let id_strategy = find_id_strategy(&self.engine, connected_db.file_path(), &collection_name);
+        let document_id = match parse_document_id(&document_id, id_strategy) {
+            Some(id) => id,
+            None => return eprintln!("Invalid document ID"),
         };
         let result = self.engine
             .storage_api()
@@ -292,6 +323,120 @@ impl Cli {
         }
     }
 
+    /// Show menu to list the revisions of a document in a collection.
+    pub fn list_document_revisions(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+        let document_id = match ask_user_input("Document ID: ") {
+            Ok(id) => id,
+            Err(_) => return,
+        };
+        let id_strategy = find_id_strategy(&self.engine, connected_db.file_path(), &collection_name);
+        let document_id = match parse_document_id(&document_id, id_strategy) {
+            Some(id) => id,
+            None => return eprintln!("Invalid document ID"),
+        };
+        let result = self.engine
+            .storage_api()
+            .find_document_revisions(connected_db.file_path(), &collection_name, &document_id);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(revisions) = result.data {
+                println!("Number of revisions: {}", revisions.len());
+
+                for revision in revisions {
+                    print_document_revision(&revision);
+                }
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to list a single revision of a document in a collection.
+    pub fn list_document_revision(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let (collection_name, document_id, revision) = match prompt_document_revision_input(&self.engine, connected_db.file_path()) {
+            Some(input) => input,
+            None => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .find_document_revision(connected_db.file_path(), &collection_name, &document_id, revision);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(data) = result.data {
+                if let Some(revision) = data {
+                    print_document_revision(&revision);
+                } else {
+                    println!("Revision was not found");
+                }
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to restore a document in a collection to a revision.
+    pub fn restore_document_revision(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let (collection_name, document_id, revision) = match prompt_document_revision_input(&self.engine, connected_db.file_path()) {
+            Some(input) => input,
+            None => return,
+        };
+        let confirm = match ask_action_confirm(
+            &format!("Restore document with ID '{}' to revision {}?", document_id, revision)
+        ) {
+            Ok(confirm) => confirm,
+            Err(_) => return,
+        };
+
+        match confirm.as_str() {
+            CONFIRM_OPTION_YES => {
+                let result = self.engine
+                    .storage_api()
+                    .restore_document_revision(connected_db.file_path(), &collection_name, &document_id, revision);
+
+                if result.success {
+                    event_log_failed(result.log_error);
+
+                    println!("Document restored");
+                } else {
+                    error_log_failed(result.log_error);
+
+                    if let Some(e) = result.error {
+                        eprintln!("Error: {}", e);
+                    }
+                }
+            },
+            _ => return println!("Canceled action"),
+        }
+    }
+
     /// Show menu to list documents in a collection using query.
     /// 
     /// The query contains data fields with values that the document needs to match.
@@ -316,6 +461,14 @@ impl Cli {
             };
             limit = Some(result);
         }
+        let strict_types = match ask_yes_or_no("Strict data types (yes, no, empty for no): ", false) {
+            Some(strict_types) => strict_types,
+            None => return,
+        };
+        let options = FindOptions {
+            limit,
+            strict_types,
+        };
         let mut query: Vec<DocumentInputDataField> = Vec::new();
         
         println!("Specify fields that will be added to query");
@@ -337,7 +490,7 @@ impl Cli {
 
         let result = self.engine
             .storage_api()
-            .find_documents(connected_db.file_path(), &collection_name, &query, limit);
+            .find_documents_with_options(connected_db.file_path(), &collection_name, &query, &options);
 
         if result.success {
             event_log_failed(result.log_error);
@@ -369,3 +522,100 @@ fn prompt_data_field_input() -> io::Result<DocumentInputDataField> {
 
     Ok(DocumentInputDataField::new(&field, &data_type, &value))
 }
+
+/// Prompts user input for collection name, document id and revision number.
+/// 
+/// Returns `None` if the input is not valid.
+fn prompt_document_revision_input(engine: &Engine, db_file_path: &Path) -> Option<(String, Id, u64)> {
+    let collection_name = ask_user_input("Collection: ").ok()?;
+    let document_id = ask_user_input("Document ID: ").ok()?;
+    let id_strategy = find_id_strategy(engine, db_file_path, &collection_name);
+    let document_id = match parse_document_id(&document_id, id_strategy) {
+        Some(id) => id,
+        None => {
+            eprintln!("Invalid document ID");
+            return None;
+        },
+    };
+    let revision = ask_user_input("Revision: ").ok()?;
+    let revision = match revision.parse::<u64>() {
+        Ok(revision) => revision,
+        Err(e) => {
+            eprintln!("Invalid revision: {}", e);
+            return None;
+        },
+    };
+
+    Some((collection_name, document_id, revision))
+}
+
+/// Prints a document revision.
+fn print_document_revision(revision: &DocumentRevisionDto) {
+    let deleted = if revision.deleted { ", deleted" } else { "" };
+    println!("Revision {} ({}{})", revision.revision, revision.timestamp, deleted);
+    println!("{}", revision.document);
+}
+
+/// Finds the id strategy of a collection.
+/// 
+/// Returns `None` if the collection cannot be found.
+fn find_id_strategy(engine: &Engine, db_file_path: &Path, collection_name: &str) -> Option<IdStrategy> {
+    let result = engine
+        .storage_api()
+        .find_collection(collection_name, db_file_path);
+
+    if result.success {
+        event_log_failed(result.log_error);
+    } else {
+        error_log_failed(result.log_error);
+    }
+
+    result.data.flatten().map(|collection| collection.id_strategy())
+}
+
+/// Parses document id from user input.
+/// 
+/// The input is parsed into the type of ids that the collection's id strategy gives,
+/// so text ids can contain only digits. If the id strategy is not known,
+/// numbers are parsed into integer ids and other input into text ids.
+/// 
+/// Returns `None` if the input is empty or not a valid id.
+fn parse_document_id(input: &str, id_strategy: Option<IdStrategy>) -> Option<Id> {
+    if input.is_empty() {
+        return None;
+    }
+
+    match id_strategy {
+        Some(IdStrategy::AutoIncrement | IdStrategy::ClientInteger) => {
+            input.parse::<u64>().ok().map(Id::Integer)
+        },
+        Some(IdStrategy::UuidV4 | IdStrategy::UuidV7 | IdStrategy::ClientText) => {
+            Some(Id::Text(input.to_string()))
+        },
+        None => match input.parse::<u64>() {
+            Ok(id) => Some(Id::Integer(id)),
+            Err(_) => Some(Id::Text(input.to_string())),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_document_id() {
+        assert_eq!(
+            parse_document_id("12345", Some(IdStrategy::ClientText)),
+            Some(Id::Text("12345".to_string()))
+        );
+        assert_eq!(
+            parse_document_id("12345", Some(IdStrategy::ClientInteger)),
+            Some(Id::Integer(12345))
+        );
+        assert_eq!(parse_document_id("abc", Some(IdStrategy::AutoIncrement)), None);
+        assert_eq!(parse_document_id("12345", None), Some(Id::Integer(12345)));
+        assert_eq!(parse_document_id("abc", None), Some(Id::Text("abc".to_string())));
+        assert_eq!(parse_document_id("", Some(IdStrategy::ClientText)), None);
+    }
+}
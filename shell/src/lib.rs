@@ -3,6 +3,7 @@
 pub mod database;
 pub mod collection;
 pub mod document;
+pub mod transfer;
 pub mod util;
 pub mod config;
 pub mod cli;
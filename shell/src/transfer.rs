@@ -0,0 +1,412 @@
+use std::path::Path;
+use engine::storage::{
+    json::{
+        JsonFormat,
+        JsonMode,
+        JsonOptions,
+    },
+    csv::{
+        CsvColumn,
+        CsvExportOptions,
+        CsvImportOptions,
+    },
+};
+use crate::{
+    cli::Cli,
+    ask_user_input,
+    db_not_connected,
+    event_log_failed,
+    error_log_failed,
+};
+
+impl Cli {
+    /// Show menu to export a collection in the connected database to a JSON file.
+    pub fn export_collection_to_json(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+        let json_file_path = match ask_user_input("JSON file path (.json or .jsonl): ") {
+            Ok(json_file_path) => json_file_path,
+            Err(_) => return,
+        };
+        let options = match ask_json_options(Path::new(&json_file_path)) {
+            Some(options) => options,
+            None => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .export_collection_to_json(
+                connected_db.file_path(),
+                &collection_name,
+                Path::new(&json_file_path),
+                &options
+            );
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(document_count) = result.data {
+                println!("Exported {} documents to '{}'", document_count, json_file_path);
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to import documents from a JSON file to a collection in the connected database.
+    pub fn import_json_to_collection(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+        let json_file_path = match ask_user_input("JSON file path (.json or .jsonl): ") {
+            Ok(json_file_path) => json_file_path,
+            Err(_) => return,
+        };
+        let options = match ask_json_options(Path::new(&json_file_path)) {
+            Some(options) => options,
+            None => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .import_json_to_collection(
+                connected_db.file_path(),
+                &collection_name,
+                Path::new(&json_file_path),
+                &options
+            );
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(document_count) = result.data {
+                println!("Imported {} documents to collection '{}'", document_count, collection_name);
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to export the connected database to a JSON file.
+    pub fn export_database_to_json(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let json_file_path = match ask_user_input("JSON file path (.json or .jsonl): ") {
+            Ok(json_file_path) => json_file_path,
+            Err(_) => return,
+        };
+        let options = match ask_json_options(Path::new(&json_file_path)) {
+            Some(options) => options,
+            None => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .export_database_to_json(connected_db.file_path(), Path::new(&json_file_path), &options);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(document_count) = result.data {
+                println!("Exported {} documents to '{}'", document_count, json_file_path);
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to import documents from a JSON file to the connected database.
+    pub fn import_json_to_database(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let json_file_path = match ask_user_input("JSON file path (.json or .jsonl): ") {
+            Ok(json_file_path) => json_file_path,
+            Err(_) => return,
+        };
+        let options = match ask_json_options(Path::new(&json_file_path)) {
+            Some(options) => options,
+            None => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .import_json_to_database(connected_db.file_path(), Path::new(&json_file_path), &options);
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(document_count) = result.data {
+                println!("Imported {} documents to database '{}'", document_count, connected_db.name());
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to dump all databases in the database directory to a dump file.
+    pub fn dump_all_databases(&self) {
+        let dump_file_path = match ask_user_input("Dump file path: ") {
+            Ok(dump_file_path) => dump_file_path,
+            Err(_) => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .dump_all_databases(Path::new(&dump_file_path));
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(report) = result.data {
+                println!(
+                    "Dumped {} databases, {} collections and {} documents to '{}'",
+                    report.databases.len(),
+                    report.collection_count,
+                    report.document_count,
+                    dump_file_path
+                );
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to restore all databases in a dump file to the database directory.
+    pub fn restore_dump(&self) {
+        let dump_file_path = match ask_user_input("Dump file path: ") {
+            Ok(dump_file_path) => dump_file_path,
+            Err(_) => return,
+        };
+        let result = self.engine
+            .storage_api()
+            .restore_dump(Path::new(&dump_file_path));
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(report) = result.data {
+                println!(
+                    "Restored {} databases, {} collections and {} documents",
+                    report.databases.len(),
+                    report.collection_count,
+                    report.document_count
+                );
+                for db_name in report.databases {
+                    println!("  {}", db_name);
+                }
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to export a collection in the connected database to a CSV file.
+    pub fn export_collection_to_csv(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+        let csv_file_path = match ask_user_input("CSV file path: ") {
+            Ok(csv_file_path) => csv_file_path,
+            Err(_) => return,
+        };
+        let fields = match ask_user_input("Fields separated by commas (empty for all fields): ") {
+            Ok(fields) => fields,
+            Err(_) => return,
+        };
+        let header = match ask_yes_or_no("Header row (yes, no, empty for yes): ", true) {
+            Some(header) => header,
+            None => return,
+        };
+        let options = CsvExportOptions {
+            fields: split_list(&fields),
+            header,
+        };
+        let result = self.engine
+            .storage_api()
+            .export_collection_to_csv(
+                connected_db.file_path(),
+                &collection_name,
+                Path::new(&csv_file_path),
+                &options
+            );
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(document_count) = result.data {
+                println!("Exported {} documents to '{}'", document_count, csv_file_path);
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Show menu to import documents from a CSV file to a collection in the connected database.
+    pub fn import_csv_to_collection(&self) {
+        let connected_db = match &self.connected_db {
+            Some(db) => db,
+            None => return db_not_connected(),
+        };
+        let collection_name = match ask_user_input("Collection: ") {
+            Ok(collection_name) => collection_name,
+            Err(_) => return,
+        };
+        let csv_file_path = match ask_user_input("CSV file path: ") {
+            Ok(csv_file_path) => csv_file_path,
+            Err(_) => return,
+        };
+        let header = match ask_yes_or_no("Header row (yes, no, empty for yes): ", true) {
+            Some(header) => header,
+            None => return,
+        };
+        let columns_text = if header {
+            "Column data types, e.g. age:Int32,name:Text (empty for all Text): "
+        } else {
+            "Columns in file order, e.g. name:Text,age:Int32: "
+        };
+        let columns = match ask_user_input(columns_text) {
+            Ok(columns) => columns,
+            Err(_) => return,
+        };
+        let mut csv_columns = Vec::new();
+        for column in split_list(&columns) {
+            match column.split_once(':') {
+                Some((name, data_type)) => csv_columns.push(CsvColumn::new(name.trim(), data_type.trim())),
+                None => return eprintln!("Invalid column '{}'. Columns must be given as name:DataType", column),
+            }
+        }
+        let dry_run = match ask_yes_or_no("Dry run (yes, no, empty for no): ", false) {
+            Some(dry_run) => dry_run,
+            None => return,
+        };
+        let options = CsvImportOptions {
+            columns: csv_columns,
+            header,
+            dry_run,
+        };
+        let result = self.engine
+            .storage_api()
+            .import_csv_to_collection(
+                connected_db.file_path(),
+                &collection_name,
+                Path::new(&csv_file_path),
+                &options
+            );
+
+        if result.success {
+            event_log_failed(result.log_error);
+
+            if let Some(report) = result.data {
+                if report.dry_run {
+                    println!("Dry run, nothing was imported");
+                    println!("Valid rows: {}", report.imported_count);
+                } else {
+                    println!("Imported documents: {}", report.imported_count);
+                }
+                println!("Failed rows: {}", report.errors.len());
+
+                for error in report.errors {
+                    println!("  {}", error);
+                }
+            }
+        } else {
+            error_log_failed(result.log_error);
+
+            if let Some(e) = result.error {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+}
+
+/// Asks a yes or no question. Empty input gives the default answer.
+/// 
+/// Returns `None` if the answer is not valid.
+pub(crate) fn ask_yes_or_no(text_to_ask: &str, default: bool) -> Option<bool> {
+    let answer = match ask_user_input(text_to_ask) {
+        Ok(answer) => answer,
+        Err(_) => return None,
+    };
+
+    match answer.as_str() {
+        "" => Some(default),
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => {
+            eprintln!("Invalid answer. Answer must be 'yes' or 'no'");
+            None
+        },
+    }
+}
+
+/// Splits a comma separated list. Empty items are skipped.
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+        .collect()
+}
+
+/// Asks the JSON mode. The format is chosen by the file extension.
+/// 
+/// Returns `None` if the mode is not valid.
+fn ask_json_options(json_file_path: &Path) -> Option<JsonOptions> {
+    let mode = match ask_user_input("Mode (typed, relaxed, empty for typed): ") {
+        Ok(mode) => mode,
+        Err(_) => return None,
+    };
+    let mode = match mode.as_str() {
+        "" | "typed" => JsonMode::Typed,
+        "relaxed" => JsonMode::Relaxed,
+        _ => {
+            eprintln!("Invalid mode. Mode must be 'typed' or 'relaxed'");
+            return None;
+        },
+    };
+
+    Some(JsonOptions::new(JsonFormat::from_file_path(json_file_path), mode))
+}
@@ -47,6 +47,19 @@ pub fn ask_user_input(text_to_ask: &str) -> io::Result<String> {
     Ok(input)
 }
 
+/// Asks for secret user input, such as a passphrase, without echoing it.
+/// 
+/// Returns the input trimmed.
+pub fn ask_secret_input(text_to_ask: &str) -> io::Result<String> {
+    match rpassword::prompt_password(text_to_ask) {
+        Ok(input) => Ok(input.trim().to_string()),
+        Err(e) => {
+            eprintln!("Failed to read line: {e}");
+            return Err(e);
+        },
+    }
+}
+
 /// Asks user to confirm an action, such as delete action.
 /// 
 /// Returns the input trimmed.